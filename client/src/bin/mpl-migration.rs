@@ -0,0 +1,67 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use mpl_migration_validator_client::{scanner::plan_migration, snapshot::Snapshot};
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Parser)]
+#[clap(author, version, about)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Plan the migration of a collection from account snapshot files.
+    Scan {
+        /// Mint of the collection NFT.
+        #[clap(long)]
+        collection_mint: Pubkey,
+
+        /// JSON files with the Token Metadata, token and program accounts.
+        #[clap(required = true)]
+        snapshots: Vec<PathBuf>,
+
+        /// Where to write the plan. Printed to stdout if omitted.
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Scan {
+            collection_mint,
+            snapshots,
+            output,
+        } => {
+            let snapshot = Snapshot::load(&snapshots)?;
+            let plan = plan_migration(&snapshot, &collection_mint)?;
+
+            eprintln!(
+                "{} ready, {} migrated, {} failing, {} unknown",
+                plan.ready, plan.migrated, plan.failing, plan.unknown
+            );
+
+            let counters = &plan.counters;
+            eprintln!(
+                "{} items migrated by {} payers, {} lamports of rent paid",
+                counters.items_migrated,
+                counters.unique_payers,
+                counters.rent_paid()
+            );
+
+            let json = serde_json::to_string_pretty(&plan)?;
+            match output {
+                Some(path) => fs::write(path, json)?,
+                None => println!("{}", json),
+            }
+        }
+    }
+
+    Ok(())
+}
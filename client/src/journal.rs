@@ -0,0 +1,132 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_with::{As, DisplayFromStr, Same};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum JournalError {
+    #[error("Failed to access journal {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error("Invalid journal {0}: {1}")]
+    Json(PathBuf, serde_json::Error),
+
+    #[error("Journal {0} belongs to collection {1}")]
+    CollectionMismatch(PathBuf, Pubkey),
+}
+
+/// Progress of a single item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ItemStatus {
+    /// Sent in a transaction whose outcome is unknown.
+    Submitted {
+        #[serde(with = "As::<DisplayFromStr>")]
+        signature: Signature,
+    },
+    /// Migrated, either by a confirmed transaction or as found on-chain.
+    Confirmed {
+        #[serde(with = "As::<Option<DisplayFromStr>>")]
+        signature: Option<Signature>,
+    },
+    /// The last attempt failed.
+    Failed {
+        error: String,
+        /// Whether the program rejected the item, so sending it again fails the same way.
+        permanent: bool,
+        /// Transactions sent for the item in the run that failed it.
+        attempts: u32,
+    },
+}
+
+/// Progress of a collection migration, persisted after every change so a run can be
+/// resumed after a crash.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Journal {
+    #[serde(skip)]
+    path: PathBuf,
+
+    #[serde(with = "As::<DisplayFromStr>")]
+    pub collection_mint: Pubkey,
+
+    #[serde(with = "As::<BTreeMap<DisplayFromStr, Same>>")]
+    items: BTreeMap<Pubkey, ItemStatus>,
+}
+
+impl Journal {
+    /// Opens the journal at `path`, creating an empty one if it does not exist.
+    pub fn open<P: AsRef<Path>>(path: P, collection_mint: Pubkey) -> Result<Self, JournalError> {
+        let path = path.as_ref().to_path_buf();
+
+        if !path.exists() {
+            return Ok(Self {
+                path,
+                collection_mint,
+                items: BTreeMap::new(),
+            });
+        }
+
+        let json = fs::read_to_string(&path).map_err(|e| JournalError::Io(path.clone(), e))?;
+        let mut journal: Journal =
+            serde_json::from_str(&json).map_err(|e| JournalError::Json(path.clone(), e))?;
+
+        if journal.collection_mint != collection_mint {
+            return Err(JournalError::CollectionMismatch(
+                path,
+                journal.collection_mint,
+            ));
+        }
+
+        journal.path = path;
+        Ok(journal)
+    }
+
+    pub fn status(&self, mint: &Pubkey) -> Option<&ItemStatus> {
+        self.items.get(mint)
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = (&Pubkey, &ItemStatus)> {
+        self.items.iter()
+    }
+
+    pub fn is_confirmed(&self, mint: &Pubkey) -> bool {
+        matches!(self.items.get(mint), Some(ItemStatus::Confirmed { .. }))
+    }
+
+    pub fn count(&self, f: impl Fn(&ItemStatus) -> bool) -> usize {
+        self.items.values().filter(|status| f(status)).count()
+    }
+
+    /// Records the new status of the items and saves the journal.
+    pub fn record(
+        &mut self,
+        mints: impl IntoIterator<Item = Pubkey>,
+        status: ItemStatus,
+    ) -> Result<(), JournalError> {
+        for mint in mints {
+            self.items.insert(mint, status.clone());
+        }
+        self.save()
+    }
+
+    pub fn forget(&mut self, mint: &Pubkey) -> Result<(), JournalError> {
+        self.items.remove(mint);
+        self.save()
+    }
+
+    /// Writes to a temporary file first so a crash never leaves a truncated journal.
+    fn save(&self) -> Result<(), JournalError> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| JournalError::Json(self.path.clone(), e))?;
+
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, json).map_err(|e| JournalError::Io(tmp.clone(), e))?;
+        fs::rename(&tmp, &self.path).map_err(|e| JournalError::Io(self.path.clone(), e))
+    }
+}
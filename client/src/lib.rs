@@ -0,0 +1,7 @@
+pub mod journal;
+pub mod lookup_table;
+pub mod runner;
+pub mod scanner;
+pub mod snapshot;
+
+pub use mpl_migration_validator;
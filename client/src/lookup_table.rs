@@ -0,0 +1,163 @@
+//! Address lookup tables for migrate transactions.
+//!
+//! Most accounts of a migrate instruction are the same for every item of a collection. Moving
+//! them to a lookup table shrinks each instruction to its per-item accounts, so more items fit
+//! in a versioned transaction.
+
+use mpl_migration_validator::{
+    errors::MigrationError,
+    instruction::MigrateAccounts,
+    utils::{
+        find_bounty_vault_pda, find_collection_signer_pda, find_delegate_record_pda,
+        find_migration_state_pda, find_owner_program_list_pda, find_payer_record_pda,
+    },
+    MPL_TOKEN_AUTH_RULES_ID,
+};
+use mpl_token_metadata::pda::find_metadata_account;
+use solana_address_lookup_table_program::{
+    instruction::{create_lookup_table, extend_lookup_table},
+    state::AddressLookupTable,
+};
+use solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount,
+    clock::Slot,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::{Instruction, InstructionError},
+    message::{v0, CompileError, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::{Signer, SignerError},
+    sysvar,
+    transaction::VersionedTransaction,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum LookupTableError {
+    #[error("Failed to compile message: {0}")]
+    Compile(#[from] CompileError),
+
+    #[error("Failed to sign transaction: {0}")]
+    Sign(#[from] SignerError),
+
+    #[error("Invalid lookup table {0}: {1}")]
+    InvalidLookupTable(Pubkey, InstructionError),
+
+    #[error("Invalid migrate accounts: {0:?}")]
+    MigrateAccounts(#[from] MigrationError),
+}
+
+/// Accounts shared by every migrate instruction of a collection sent by `payer`.
+pub fn migration_lookup_table_addresses(
+    collection_mint: &Pubkey,
+    rule_set: &Pubkey,
+    payer: &Pubkey,
+) -> Vec<Pubkey> {
+    vec![
+        find_collection_signer_pda(collection_mint).0,
+        find_metadata_account(collection_mint).0,
+        find_delegate_record_pda(collection_mint).0,
+        find_migration_state_pda(collection_mint).0,
+        find_owner_program_list_pda(collection_mint).0,
+        find_bounty_vault_pda(collection_mint).0,
+        find_payer_record_pda(collection_mint, payer).0,
+        *rule_set,
+        solana_sdk::system_program::ID,
+        sysvar::instructions::ID,
+        spl_token::ID,
+        MPL_TOKEN_AUTH_RULES_ID,
+        mpl_token_metadata::ID,
+    ]
+}
+
+/// Instructions creating a lookup table with the shared accounts of a migration, and the
+/// address of the table.
+///
+/// `recent_slot` must be a recent finalized slot. The table can be used from the slot after
+/// the one it is extended in.
+pub fn create_migration_lookup_table(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    recent_slot: Slot,
+    collection_mint: &Pubkey,
+    rule_set: &Pubkey,
+) -> (Vec<Instruction>, Pubkey) {
+    let (create, table) = create_lookup_table(*authority, *payer, recent_slot);
+    let extend = extend_lookup_table(
+        table,
+        *authority,
+        Some(*payer),
+        migration_lookup_table_addresses(collection_mint, rule_set, payer),
+    );
+
+    (vec![create, extend], table)
+}
+
+/// Decodes a lookup table account fetched from the cluster.
+pub fn decode_lookup_table(
+    key: Pubkey,
+    data: &[u8],
+) -> Result<AddressLookupTableAccount, LookupTableError> {
+    let table = AddressLookupTable::deserialize(data)
+        .map_err(|e| LookupTableError::InvalidLookupTable(key, e))?;
+
+    Ok(AddressLookupTableAccount {
+        key,
+        addresses: table.addresses.to_vec(),
+    })
+}
+
+/// A compute budget request followed by one migrate instruction per item. The collection
+/// parent is migrated with `MigrateCollectionParent`.
+pub fn batch_migrate_instructions(
+    items: &[MigrateAccounts],
+    compute_units: u32,
+) -> Result<Vec<Instruction>, MigrationError> {
+    let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+        compute_units,
+    )];
+    for item in items {
+        instructions.push(if item.item_mint == item.collection_mint {
+            item.collection_parent_instruction()?
+        } else {
+            item.instruction()?
+        });
+    }
+
+    Ok(instructions)
+}
+
+/// A v0 transaction migrating the items, loading shared accounts from the lookup tables.
+pub fn batch_migrate_transaction(
+    payer: &Keypair,
+    items: &[MigrateAccounts],
+    compute_units: u32,
+    lookup_tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<VersionedTransaction, LookupTableError> {
+    let instructions = batch_migrate_instructions(items, compute_units)?;
+
+    let message =
+        v0::Message::try_compile(&payer.pubkey(), &instructions, lookup_tables, blockhash)?;
+    let transaction = VersionedTransaction::try_new(VersionedMessage::V0(message), &[payer])?;
+
+    Ok(transaction)
+}
+
+/// A v0 transaction migrating a single item.
+pub fn migrate_transaction(
+    payer: &Keypair,
+    item: &MigrateAccounts,
+    compute_units: u32,
+    lookup_tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<VersionedTransaction, LookupTableError> {
+    batch_migrate_transaction(
+        payer,
+        std::slice::from_ref(item),
+        compute_units,
+        lookup_tables,
+        blockhash,
+    )
+}
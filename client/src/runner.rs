@@ -0,0 +1,509 @@
+//! Resumable migration of many items.
+//!
+//! The runner groups migrate instructions into transactions that fit the packet and compute
+//! limits, records every submission in a [`Journal`] and retries transient failures with
+//! backoff. After a crash, [`MigrationRunner::reconcile`] settles the items whose outcome
+//! was not recorded against the chain.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use mpl_migration_validator::{
+    errors::MigrationError, instruction::MigrateAccounts, state::MigrationState,
+    utils::find_migration_state_pda,
+};
+use mpl_token_metadata::{
+    pda::find_metadata_account,
+    state::{Metadata, TokenMetadataAccount, TokenStandard},
+};
+use num_traits::FromPrimitive;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account,
+    address_lookup_table_account::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::InstructionError,
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError, VersionedTransaction},
+};
+use thiserror::Error;
+
+use crate::{
+    journal::{ItemStatus, Journal, JournalError},
+    lookup_table::{batch_migrate_instructions, batch_migrate_transaction, LookupTableError},
+};
+
+#[derive(Error, Debug)]
+pub enum RunnerError {
+    #[error(transparent)]
+    Journal(#[from] JournalError),
+
+    #[error("Client error: {0}")]
+    Client(String),
+
+    #[error("Invalid migration state {0}")]
+    InvalidMigrationState(Pubkey),
+
+    #[error("Item {0} does not fit in a transaction")]
+    ItemTooLarge(Pubkey),
+
+    #[error(transparent)]
+    LookupTable(#[from] LookupTableError),
+}
+
+/// Why a transaction was not confirmed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SendError {
+    /// The transaction was executed and failed.
+    Transaction(TransactionError),
+    /// The transaction may not have reached the cluster.
+    Transport(String),
+}
+
+/// The cluster access needed by the runner.
+#[async_trait]
+pub trait MigrationClient {
+    async fn get_account(&mut self, pubkey: &Pubkey) -> Result<Option<Account>, RunnerError>;
+
+    async fn get_latest_blockhash(&mut self) -> Result<Hash, RunnerError>;
+
+    async fn send_and_confirm(
+        &mut self,
+        transaction: VersionedTransaction,
+    ) -> Result<(), SendError>;
+}
+
+#[async_trait]
+impl MigrationClient for RpcClient {
+    async fn get_account(&mut self, pubkey: &Pubkey) -> Result<Option<Account>, RunnerError> {
+        self.get_account_with_commitment(pubkey, self.commitment())
+            .await
+            .map(|response| response.value)
+            .map_err(|e| RunnerError::Client(e.to_string()))
+    }
+
+    async fn get_latest_blockhash(&mut self) -> Result<Hash, RunnerError> {
+        RpcClient::get_latest_blockhash(self)
+            .await
+            .map_err(|e| RunnerError::Client(e.to_string()))
+    }
+
+    async fn send_and_confirm(
+        &mut self,
+        transaction: VersionedTransaction,
+    ) -> Result<(), SendError> {
+        self.send_and_confirm_transaction(&transaction)
+            .await
+            .map(|_| ())
+            .map_err(|e| match e.get_transaction_error() {
+                Some(error) => SendError::Transaction(error),
+                None => SendError::Transport(e.to_string()),
+            })
+    }
+}
+
+#[cfg(feature = "program-test")]
+#[async_trait]
+impl MigrationClient for solana_program_test::BanksClient {
+    async fn get_account(&mut self, pubkey: &Pubkey) -> Result<Option<Account>, RunnerError> {
+        solana_program_test::BanksClient::get_account(self, *pubkey)
+            .await
+            .map_err(|e| RunnerError::Client(e.to_string()))
+    }
+
+    async fn get_latest_blockhash(&mut self) -> Result<Hash, RunnerError> {
+        solana_program_test::BanksClient::get_latest_blockhash(self)
+            .await
+            .map_err(|e| RunnerError::Client(e.to_string()))
+    }
+
+    async fn send_and_confirm(
+        &mut self,
+        transaction: VersionedTransaction,
+    ) -> Result<(), SendError> {
+        use solana_program_test::BanksClientError;
+
+        self.process_transaction(transaction)
+            .await
+            .map_err(|e| match e {
+                BanksClientError::TransactionError(error)
+                | BanksClientError::SimulationError { err: error, .. } => {
+                    SendError::Transaction(error)
+                }
+                e => SendError::Transport(e.to_string()),
+            })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunnerConfig {
+    /// Compute units requested for each migrate instruction.
+    pub compute_units_per_item: u32,
+    /// Compute units a transaction may request.
+    pub max_compute_units: u32,
+    pub max_items_per_transaction: usize,
+    /// Attempts made for an item before it is recorded as failed.
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled on every following one.
+    pub retry_delay: Duration,
+    /// Whether items the program rejected in a previous run are sent again.
+    pub retry_rejected: bool,
+}
+
+impl Default for RunnerConfig {
+    fn default() -> Self {
+        Self {
+            compute_units_per_item: 250_000,
+            max_compute_units: 1_400_000,
+            max_items_per_transaction: 8,
+            max_attempts: 5,
+            retry_delay: Duration::from_millis(500),
+            retry_rejected: false,
+        }
+    }
+}
+
+/// Outcome of a run, counted over the items passed to it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RunSummary {
+    pub confirmed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub transactions: usize,
+}
+
+/// Result of settling the journal against the chain.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Reconciliation {
+    /// Unconfirmed items found migrated on-chain.
+    pub found_migrated: usize,
+    /// Submitted items that were not migrated and will be sent again.
+    pub reset: usize,
+    /// Confirmed items, excluding the collection parent.
+    pub confirmed_items: usize,
+    /// `items_migrated` of the migration state. Larger than `confirmed_items` when items
+    /// were migrated by someone else.
+    pub items_migrated: u32,
+}
+
+pub struct MigrationRunner<C: MigrationClient> {
+    client: C,
+    payer: Keypair,
+    collection_mint: Pubkey,
+    journal: Journal,
+    config: RunnerConfig,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+impl<C: MigrationClient> MigrationRunner<C> {
+    pub fn new(
+        client: C,
+        payer: Keypair,
+        collection_mint: Pubkey,
+        journal: Journal,
+        config: RunnerConfig,
+    ) -> Self {
+        Self {
+            client,
+            payer,
+            collection_mint,
+            journal,
+            config,
+            lookup_tables: vec![],
+        }
+    }
+
+    /// Sends v0 transactions loading shared accounts from the lookup tables, so more items
+    /// fit in each transaction.
+    pub fn with_lookup_tables(mut self, lookup_tables: Vec<AddressLookupTableAccount>) -> Self {
+        self.lookup_tables = lookup_tables;
+        self
+    }
+
+    pub fn journal(&self) -> &Journal {
+        &self.journal
+    }
+
+    pub fn client(&mut self) -> &mut C {
+        &mut self.client
+    }
+
+    /// Settles items without a confirmed outcome: items migrated on-chain are confirmed and
+    /// submitted items that were not are sent again.
+    pub async fn reconcile(&mut self) -> Result<Reconciliation, RunnerError> {
+        let mut reconciliation = Reconciliation::default();
+
+        let unsettled: Vec<(Pubkey, ItemStatus)> = self
+            .journal
+            .items()
+            .filter(|(_, status)| !matches!(status, ItemStatus::Confirmed { .. }))
+            .map(|(mint, status)| (*mint, status.clone()))
+            .collect();
+
+        for (mint, status) in unsettled {
+            if self.is_migrated(&mint).await? {
+                let signature = match status {
+                    ItemStatus::Submitted { signature } => Some(signature),
+                    _ => None,
+                };
+                self.journal
+                    .record([mint], ItemStatus::Confirmed { signature })?;
+                reconciliation.found_migrated += 1;
+            } else if matches!(status, ItemStatus::Submitted { .. }) {
+                self.journal.forget(&mint)?;
+                reconciliation.reset += 1;
+            }
+        }
+
+        reconciliation.confirmed_items = self
+            .journal
+            .items()
+            .filter(|(mint, status)| {
+                **mint != self.collection_mint && matches!(status, ItemStatus::Confirmed { .. })
+            })
+            .count();
+
+        let (migration_state_pubkey, _) = find_migration_state_pda(&self.collection_mint);
+        if let Some(account) = self.client.get_account(&migration_state_pubkey).await? {
+            let migration_state = MigrationState::from_data(&account.data)
+                .map_err(|_| RunnerError::InvalidMigrationState(migration_state_pubkey))?;
+            reconciliation.items_migrated = migration_state.status.items_migrated;
+        }
+
+        Ok(reconciliation)
+    }
+
+    /// Migrates the items in order, skipping the ones the journal has settled.
+    ///
+    /// The payer of the accounts is replaced by the runner's payer. The collection parent is
+    /// always sent in its own transaction.
+    pub async fn run(&mut self, items: &[MigrateAccounts]) -> Result<RunSummary, RunnerError> {
+        let mut summary = RunSummary::default();
+
+        let mut queue: Vec<MigrateAccounts> = vec![];
+        for item in items {
+            if self.is_settled(&item.item_mint) {
+                summary.skipped += 1;
+            } else {
+                queue.push(MigrateAccounts {
+                    payer: self.payer.pubkey(),
+                    ..item.clone()
+                });
+            }
+        }
+
+        let mut attempt = 0;
+        while !queue.is_empty() {
+            let batch_len = self.batch_len(&queue)?;
+            let batch: Vec<MigrateAccounts> = queue.drain(..batch_len).collect();
+            let mints: Vec<Pubkey> = batch.iter().map(|item| item.item_mint).collect();
+
+            let blockhash = self.client.get_latest_blockhash().await?;
+            let transaction = self.transaction(&batch, blockhash)?;
+            let signature = transaction.signatures[0];
+            let instruction_items = instruction_items(&transaction);
+            let failed_item = |index: u8| instruction_items.get(index as usize).copied().flatten();
+
+            self.journal
+                .record(mints.iter().copied(), ItemStatus::Submitted { signature })?;
+            summary.transactions += 1;
+
+            match self.client.send_and_confirm(transaction).await {
+                Ok(()) => {
+                    self.journal.record(
+                        mints,
+                        ItemStatus::Confirmed {
+                            signature: Some(signature),
+                        },
+                    )?;
+                    summary.confirmed += batch.len();
+                    attempt = 0;
+                }
+                // The instruction of one item failed: the others are sent again without it.
+                Err(SendError::Transaction(TransactionError::InstructionError(index, error)))
+                    if failed_item(index).is_some() =>
+                {
+                    let rejected = batch[failed_item(index).unwrap()].item_mint;
+
+                    // A transaction reported as lost may have landed after all.
+                    if self.is_migrated(&rejected).await? {
+                        self.journal
+                            .record([rejected], ItemStatus::Confirmed { signature: None })?;
+                        summary.confirmed += 1;
+                    } else {
+                        // Errors returned by the program reject the item for good. Others,
+                        // such as running out of compute, may not happen in a later run.
+                        let (error, permanent) = match error {
+                            InstructionError::Custom(code) => {
+                                let error = match MigrationError::from_u32(code) {
+                                    Some(error) => format!("{:?}: {}", error, error),
+                                    None => format!("custom program error: {:#x}", code),
+                                };
+                                (error, true)
+                            }
+                            error => (format!("{:?}", error), false),
+                        };
+
+                        self.journal.record(
+                            [rejected],
+                            ItemStatus::Failed {
+                                error,
+                                permanent,
+                                attempts: attempt + 1,
+                            },
+                        )?;
+                        summary.failed += 1;
+                    }
+
+                    let rest = batch.into_iter().filter(|item| item.item_mint != rejected);
+                    requeue(&mut queue, rest);
+                }
+                Err(error) => {
+                    attempt += 1;
+
+                    if attempt >= self.config.max_attempts {
+                        // The error names no item, so give up on the first one for the rest
+                        // of the run to go on.
+                        let mut batch = batch.into_iter();
+                        let item = batch.next().unwrap();
+                        self.journal.record(
+                            [item.item_mint],
+                            ItemStatus::Failed {
+                                error: format!("{:?}", error),
+                                permanent: false,
+                                attempts: attempt,
+                            },
+                        )?;
+                        summary.failed += 1;
+                        attempt = 0;
+                        requeue(&mut queue, batch);
+                    } else {
+                        tokio::time::sleep(self.config.retry_delay * 2u32.pow(attempt - 1)).await;
+                        requeue(&mut queue, batch);
+                    }
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    fn is_settled(&self, mint: &Pubkey) -> bool {
+        match self.journal.status(mint) {
+            Some(ItemStatus::Confirmed { .. }) => true,
+            Some(ItemStatus::Failed { permanent, .. }) => *permanent && !self.config.retry_rejected,
+            _ => false,
+        }
+    }
+
+    async fn is_migrated(&mut self, mint: &Pubkey) -> Result<bool, RunnerError> {
+        let (metadata_pubkey, _) = find_metadata_account(mint);
+
+        Ok(self
+            .client
+            .get_account(&metadata_pubkey)
+            .await?
+            .and_then(|account| Metadata::safe_deserialize(&account.data).ok())
+            .map(|metadata| metadata.token_standard == Some(TokenStandard::ProgrammableNonFungible))
+            .unwrap_or(false))
+    }
+
+    /// Number of items from the front of the queue that fit in the next transaction.
+    fn batch_len(&self, queue: &[MigrateAccounts]) -> Result<usize, RunnerError> {
+        let is_parent = |item: &MigrateAccounts| item.item_mint == self.collection_mint;
+
+        if is_parent(&queue[0]) {
+            return self
+                .fits(&queue[..1])
+                .then_some(1)
+                .ok_or(RunnerError::ItemTooLarge(queue[0].item_mint));
+        }
+
+        let mut len = 0;
+        while len < queue.len()
+            && len < self.config.max_items_per_transaction
+            && !is_parent(&queue[len])
+            && self.fits(&queue[..=len])
+        {
+            len += 1;
+        }
+
+        if len == 0 {
+            return Err(RunnerError::ItemTooLarge(queue[0].item_mint));
+        }
+        Ok(len)
+    }
+
+    fn fits(&self, batch: &[MigrateAccounts]) -> bool {
+        let compute_units = self.config.compute_units_per_item as u64 * batch.len() as u64;
+        if compute_units > self.config.max_compute_units as u64 {
+            return false;
+        }
+
+        // Too many accounts for a message fails to compile.
+        match self.transaction(batch, Hash::default()) {
+            Ok(transaction) => bincode::serialized_size(&transaction)
+                .map(|size| size as usize <= PACKET_DATA_SIZE)
+                .unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+
+    fn transaction(
+        &self,
+        batch: &[MigrateAccounts],
+        blockhash: Hash,
+    ) -> Result<VersionedTransaction, LookupTableError> {
+        let compute_units = (self.config.compute_units_per_item * batch.len() as u32)
+            .min(self.config.max_compute_units);
+
+        if self.lookup_tables.is_empty() {
+            let transaction = Transaction::new_signed_with_payer(
+                &batch_migrate_instructions(batch, compute_units)?,
+                Some(&self.payer.pubkey()),
+                &[&self.payer],
+                blockhash,
+            );
+            return Ok(transaction.into());
+        }
+
+        batch_migrate_transaction(
+            &self.payer,
+            batch,
+            compute_units,
+            &self.lookup_tables,
+            blockhash,
+        )
+    }
+}
+
+/// The position in the batch of the item migrated by each instruction of the transaction, or
+/// `None` for instructions that migrate no item, such as the compute budget request.
+fn instruction_items(transaction: &VersionedTransaction) -> Vec<Option<usize>> {
+    // Program ids are never loaded from lookup tables.
+    let program_ids = transaction.message.static_account_keys();
+
+    let mut item = 0;
+    transaction
+        .message
+        .instructions()
+        .iter()
+        .map(|instruction| {
+            let program_id = program_ids.get(instruction.program_id_index as usize);
+            (program_id == Some(&mpl_migration_validator::ID)).then(|| {
+                item += 1;
+                item - 1
+            })
+        })
+        .collect()
+}
+
+/// Puts items back at the front of the queue, keeping their order.
+fn requeue(queue: &mut Vec<MigrateAccounts>, items: impl IntoIterator<Item = MigrateAccounts>) {
+    let rest = std::mem::take(queue);
+    queue.extend(items);
+    queue.extend(rest);
+}
@@ -0,0 +1,465 @@
+//! Offline planning of a collection migration from an account snapshot.
+//!
+//! The scanner finds every verified member of a collection in a [`Snapshot`] and runs the
+//! checks `Migrate` runs on-chain, so items that would fail are known before any
+//! transaction is sent.
+
+use std::collections::HashMap;
+
+use mpl_migration_validator::{
+    errors::MigrationError,
+    instruction::MigrateAccounts,
+    state::{
+        CollectionInfo, CollectionParentOrder, MigrationAccount, MigrationState, OwnerProgramList,
+        UpdateAuthorityMode,
+    },
+    utils::{
+        find_legacy_delegate_record_pda, find_migration_state_pda, find_owner_program_list_pda,
+    },
+    validation::{
+        check_eligibility, metadata_belongs_to_mint, token_owner_program_buffer_matches,
+        update_authority_matches, verified_collection_member, EligibilityContext,
+    },
+};
+use mpl_token_metadata::{
+    pda::{find_master_edition_account, find_metadata_account},
+    state::{Key, MasterEditionV2, Metadata, TokenMetadataAccount, TokenStandard},
+};
+use serde::Serialize;
+use serde_with::{As, DisplayFromStr};
+use solana_sdk::{
+    bpf_loader_upgradeable::UpgradeableLoaderState, program_pack::Pack, pubkey::Pubkey,
+    system_program,
+};
+use spl_token::state::{Account as TokenAccount, Mint};
+use thiserror::Error;
+
+use crate::snapshot::Snapshot;
+
+#[derive(Error, Debug)]
+pub enum ScanError {
+    #[error("Collection metadata {0} not found in snapshot")]
+    MissingCollectionMetadata(Pubkey),
+
+    #[error("Invalid migration state {0}")]
+    InvalidMigrationState(Pubkey),
+
+    #[error("Invalid owner program list {0}")]
+    InvalidOwnerProgramList(Pubkey),
+}
+
+/// The predicted outcome of migrating an item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Prediction {
+    /// Every check `Migrate` runs passes.
+    Ready,
+    /// The item is already a programmable NFT.
+    Migrated,
+    /// `Migrate` would fail with this error.
+    Fails {
+        code: u32,
+        error: String,
+        message: String,
+    },
+    /// An account needed to check the item is missing from the snapshot.
+    Unknown { reason: String },
+}
+
+impl From<MigrationError> for Prediction {
+    fn from(error: MigrationError) -> Self {
+        Prediction::Fails {
+            code: error as u32,
+            error: format!("{:?}", error),
+            message: error.to_string(),
+        }
+    }
+}
+
+/// The inputs of a migrate instruction for an item, and its predicted outcome.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedItem {
+    #[serde(with = "As::<DisplayFromStr>")]
+    pub mint: Pubkey,
+    #[serde(with = "As::<Option<DisplayFromStr>>")]
+    pub token: Option<Pubkey>,
+    #[serde(with = "As::<Option<DisplayFromStr>>")]
+    pub token_owner: Option<Pubkey>,
+    #[serde(with = "As::<Option<DisplayFromStr>>")]
+    pub token_owner_program: Option<Pubkey>,
+    #[serde(with = "As::<Option<DisplayFromStr>>")]
+    pub token_owner_program_buffer: Option<Pubkey>,
+    pub is_collection_parent: bool,
+    pub prediction: Prediction,
+}
+
+impl PlannedItem {
+    /// Accounts of the migrate instruction, if the snapshot had every input.
+    pub fn migrate_accounts(&self, plan: &MigrationPlan, payer: Pubkey) -> Option<MigrateAccounts> {
+        Some(MigrateAccounts {
+            payer,
+            item_mint: self.mint,
+            item_token: self.token?,
+            token_owner: self.token_owner?,
+            token_owner_program: self.token_owner_program?,
+            // The scan picked the buffer of upgradeable owner programs, so `None` means
+            // the owner program has none.
+            token_owner_program_buffer: self.token_owner_program_buffer.into(),
+            collection_mint: plan.collection_mint,
+            auth_rule_set: plan.rule_set,
+            legacy_signer: plan.legacy_signer,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationPlan {
+    #[serde(with = "As::<DisplayFromStr>")]
+    pub collection_mint: Pubkey,
+    #[serde(with = "As::<DisplayFromStr>")]
+    pub migration_state: Pubkey,
+    #[serde(with = "As::<DisplayFromStr>")]
+    pub rule_set: Pubkey,
+    /// Whether the migration state exists in the snapshot. Otherwise the plan assumes the
+    /// defaults set by `Initialize`.
+    pub initialized: bool,
+    pub in_progress: bool,
+    /// Whether the migration was started with the global `PROGRAM_SIGNER` rather than
+    /// the collection's own signer.
+    pub legacy_signer: bool,
+    /// Counters recorded by the migration state so far.
+    pub counters: MigrationCounters,
+    pub ready: usize,
+    pub migrated: usize,
+    pub failing: usize,
+    pub unknown: usize,
+    /// Items in the order they can be migrated.
+    pub items: Vec<PlannedItem>,
+}
+
+impl MigrationPlan {
+    pub fn ready_items(&self) -> impl Iterator<Item = &PlannedItem> {
+        self.items
+            .iter()
+            .filter(|item| item.prediction == Prediction::Ready)
+    }
+}
+
+/// Progress and rent counters recorded by a migration state.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MigrationCounters {
+    pub items_migrated: u32,
+    pub collection_parent_migrated: bool,
+    /// Payers counted when their payer record was created, so payers migrating again
+    /// after closing their record are counted twice.
+    pub unique_payers: u32,
+    /// Lamports paid by payers for the rent of created token records.
+    pub token_record_rent_paid: u64,
+    /// Lamports paid by payers for the rent of their payer records, which can be returned
+    /// once every item has been migrated.
+    pub payer_record_rent_paid: u64,
+    pub first_migration_slot: u64,
+    pub last_migration_slot: u64,
+    pub first_migration_timestamp: i64,
+    pub last_migration_timestamp: i64,
+}
+
+impl MigrationCounters {
+    /// Total rent paid by payers for the accounts created by their migrations.
+    pub fn rent_paid(&self) -> u64 {
+        self.token_record_rent_paid
+            .saturating_add(self.payer_record_rent_paid)
+    }
+}
+
+impl From<&MigrationState> for MigrationCounters {
+    fn from(migration_state: &MigrationState) -> Self {
+        let status = &migration_state.status;
+
+        MigrationCounters {
+            items_migrated: status.items_migrated,
+            collection_parent_migrated: status.collection_parent_migrated,
+            unique_payers: status.unique_payers,
+            token_record_rent_paid: status.token_record_rent_paid,
+            payer_record_rent_paid: migration_state.payer_record_rent_paid,
+            first_migration_slot: status.first_migration_slot,
+            last_migration_slot: status.last_migration_slot,
+            first_migration_timestamp: status.first_migration_timestamp,
+            last_migration_timestamp: status.last_migration_timestamp,
+        }
+    }
+}
+
+/// Why the checks of an item stopped.
+enum Stop {
+    Fails(MigrationError),
+    Missing(String),
+}
+
+impl From<MigrationError> for Stop {
+    fn from(error: MigrationError) -> Self {
+        Stop::Fails(error)
+    }
+}
+
+impl From<Stop> for Prediction {
+    fn from(stop: Stop) -> Self {
+        match stop {
+            Stop::Fails(error) => error.into(),
+            Stop::Missing(reason) => Prediction::Unknown { reason },
+        }
+    }
+}
+
+struct Collection<'a> {
+    metadata: &'a Metadata,
+    migration_state: &'a MigrationState,
+    owner_program_list: Option<&'a OwnerProgramList>,
+}
+
+/// Plans the migration of every verified member of the collection found in the snapshot.
+pub fn plan_migration(
+    snapshot: &Snapshot,
+    collection_mint: &Pubkey,
+) -> Result<MigrationPlan, ScanError> {
+    let (collection_metadata_pubkey, _) = find_metadata_account(collection_mint);
+    let collection_metadata = snapshot
+        .get(&collection_metadata_pubkey)
+        .and_then(|account| Metadata::safe_deserialize(&account.data).ok())
+        .ok_or(ScanError::MissingCollectionMetadata(
+            collection_metadata_pubkey,
+        ))?;
+
+    let (migration_state_pubkey, _) = find_migration_state_pda(collection_mint);
+    let (migration_state, initialized) = match snapshot.get(&migration_state_pubkey) {
+        Some(account) => (
+            MigrationState::from_data(&account.data)
+                .map_err(|_| ScanError::InvalidMigrationState(migration_state_pubkey))?,
+            true,
+        ),
+        None => (
+            MigrationState {
+                collection_info: CollectionInfo {
+                    authority: collection_metadata.update_authority,
+                    mint: *collection_mint,
+                    ..CollectionInfo::default()
+                },
+                ..MigrationState::default()
+            },
+            false,
+        ),
+    };
+
+    let (owner_program_list_pubkey, _) = find_owner_program_list_pda(collection_mint);
+    let owner_program_list = match snapshot.get(&owner_program_list_pubkey) {
+        Some(account) if !account.data.is_empty() => Some(
+            OwnerProgramList::safe_deserialize(&account.data)
+                .map_err(|_| ScanError::InvalidOwnerProgramList(owner_program_list_pubkey))?,
+        ),
+        _ => None,
+    };
+
+    let collection = Collection {
+        metadata: &collection_metadata,
+        migration_state: &migration_state,
+        owner_program_list: owner_program_list.as_ref(),
+    };
+
+    // NFTs have a single token account holding the whole supply.
+    let mut tokens: HashMap<Pubkey, (Pubkey, TokenAccount)> = HashMap::new();
+    for (pubkey, account) in snapshot.iter() {
+        if account.owner != spl_token::ID || account.data.len() != TokenAccount::LEN {
+            continue;
+        }
+        if let Ok(token) = TokenAccount::unpack(&account.data) {
+            if token.amount > 0 {
+                tokens.insert(token.mint, (*pubkey, token));
+            }
+        }
+    }
+
+    let mut members: Vec<Metadata> = snapshot
+        .iter()
+        .filter(|(_, account)| {
+            account.owner == mpl_token_metadata::ID
+                && account.data.first() == Some(&(Key::MetadataV1 as u8))
+        })
+        .filter_map(|(pubkey, account)| {
+            let metadata = Metadata::safe_deserialize(&account.data).ok()?;
+            (find_metadata_account(&metadata.mint).0 == *pubkey).then_some(metadata)
+        })
+        .filter(|metadata| {
+            metadata.mint == *collection_mint
+                || matches!(&metadata.collection, Some(c) if c.verified && c.key == *collection_mint)
+        })
+        .collect();
+
+    // The collection parent is migrated in its own step, before or after every item.
+    members.sort_by_key(|metadata| metadata.mint);
+    members.sort_by_key(|metadata| {
+        let is_parent = metadata.mint == *collection_mint;
+        match migration_state.collection_parent_order {
+            CollectionParentOrder::First => !is_parent,
+            CollectionParentOrder::Last => is_parent,
+        }
+    });
+
+    let items: Vec<PlannedItem> = members
+        .iter()
+        .map(|metadata| plan_item(snapshot, &collection, metadata, tokens.get(&metadata.mint)))
+        .collect();
+
+    let count = |f: fn(&Prediction) -> bool| items.iter().filter(|i| f(&i.prediction)).count();
+
+    Ok(MigrationPlan {
+        collection_mint: *collection_mint,
+        migration_state: migration_state_pubkey,
+        rule_set: migration_state.collection_info.rule_set,
+        initialized,
+        in_progress: migration_state.status.in_progress,
+        legacy_signer: initialized
+            && migration_state.collection_info.delegate_record
+                == find_legacy_delegate_record_pda(collection_mint).0,
+        counters: MigrationCounters::from(&migration_state),
+        ready: count(|p| *p == Prediction::Ready),
+        migrated: count(|p| *p == Prediction::Migrated),
+        failing: count(|p| matches!(p, Prediction::Fails { .. })),
+        unknown: count(|p| matches!(p, Prediction::Unknown { .. })),
+        items,
+    })
+}
+
+fn plan_item(
+    snapshot: &Snapshot,
+    collection: &Collection,
+    metadata: &Metadata,
+    token: Option<&(Pubkey, TokenAccount)>,
+) -> PlannedItem {
+    let mut item = PlannedItem {
+        mint: metadata.mint,
+        token: None,
+        token_owner: None,
+        token_owner_program: None,
+        token_owner_program_buffer: None,
+        is_collection_parent: metadata.mint == collection.migration_state.collection_info.mint,
+        prediction: Prediction::Ready,
+    };
+
+    if metadata.token_standard == Some(TokenStandard::ProgrammableNonFungible) {
+        item.prediction = Prediction::Migrated;
+        return item;
+    }
+
+    if let Err(stop) = check_item(snapshot, collection, metadata, token, &mut item) {
+        item.prediction = stop.into();
+    }
+
+    item
+}
+
+/// Runs the checks of `validate_relationships` and `validate_eligibility`, filling in the
+/// instruction inputs as they are found.
+fn check_item(
+    snapshot: &Snapshot,
+    collection: &Collection,
+    metadata: &Metadata,
+    token: Option<&(Pubkey, TokenAccount)>,
+    item: &mut PlannedItem,
+) -> Result<(), Stop> {
+    let migration_state = collection.migration_state;
+    let collection_info = &migration_state.collection_info;
+
+    // Collection NFT
+    metadata_belongs_to_mint(collection.metadata, &collection_info.mint)?;
+    update_authority_matches(collection.metadata, &collection_info.authority)?;
+
+    // Migration Item
+    if migration_state.update_authority_mode == UpdateAuthorityMode::Match {
+        update_authority_matches(metadata, &collection_info.authority)?;
+    }
+    if !item.is_collection_parent {
+        verified_collection_member(metadata, &collection_info.mint)?;
+    }
+
+    let (token_pubkey, token) =
+        token.ok_or_else(|| Stop::Missing("no token account holds the item".to_string()))?;
+    item.token = Some(*token_pubkey);
+    item.token_owner = Some(token.owner);
+
+    let mint = snapshot
+        .get(&metadata.mint)
+        .and_then(|account| Mint::unpack(&account.data).ok())
+        .ok_or_else(|| Stop::Missing(format!("mint {} not in snapshot", metadata.mint)))?;
+
+    let (edition_pubkey, _) = find_master_edition_account(&metadata.mint);
+    let edition = snapshot
+        .get(&edition_pubkey)
+        .ok_or_else(|| Stop::Missing(format!("edition {} not in snapshot", edition_pubkey)))?;
+    if edition.owner != mpl_token_metadata::ID {
+        return Err(MigrationError::IncorrectEditionProgramOwner.into());
+    }
+    let is_master_edition = MasterEditionV2::safe_deserialize(&edition.data).is_ok()
+        && mint.decimals == 0
+        && mint.supply == 1;
+    if !is_master_edition {
+        return Err(MigrationError::NotMasterEdition.into());
+    }
+
+    // Wallets without lamports are not in the snapshot, like they are not on-chain, and
+    // belong to the system program.
+    let token_owner_program = snapshot
+        .get(&token.owner)
+        .map(|account| account.owner)
+        .unwrap_or(system_program::ID);
+    item.token_owner_program = Some(token_owner_program);
+
+    // Only upgradeable loader programs have a buffer.
+    if token_owner_program != system_program::ID {
+        let program = snapshot.get(&token_owner_program).ok_or_else(|| {
+            Stop::Missing(format!("program {} not in snapshot", token_owner_program))
+        })?;
+
+        let program_state: Option<UpgradeableLoaderState> =
+            bincode::deserialize(&program.data).ok();
+        if let Some(UpgradeableLoaderState::Program {
+            programdata_address,
+        }) = program_state
+        {
+            item.token_owner_program_buffer = Some(programdata_address);
+        }
+        token_owner_program_buffer_matches(
+            program_state.as_ref(),
+            &item
+                .token_owner_program_buffer
+                .unwrap_or(mpl_migration_validator::ID),
+        )?;
+    }
+
+    let buffer_state = match item.token_owner_program_buffer {
+        Some(buffer) => {
+            let program_data = snapshot
+                .get(&buffer)
+                .ok_or_else(|| Stop::Missing(format!("program data {} not in snapshot", buffer)))?;
+            Some(
+                bincode::deserialize(&program_data.data)
+                    .map_err(|_| MigrationError::IncorrectTokenOwnerProgramBuffer)?,
+            )
+        }
+        None => None,
+    };
+
+    check_eligibility(&EligibilityContext {
+        metadata,
+        mint: &mint,
+        edition: &edition_pubkey,
+        is_master_edition,
+        token,
+        token_owner_program: &token_owner_program,
+        token_owner_program_buffer: buffer_state.as_ref(),
+        delegate_handling: migration_state.delegate_handling,
+        owner_program_list: collection.owner_program_list,
+    })
+    .result()?;
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde::Deserialize;
+use solana_client::rpc_response::RpcKeyedAccount;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("Failed to read snapshot {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error("Invalid snapshot JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Invalid account pubkey {0}")]
+    InvalidPubkey(String),
+
+    #[error("Account {0} could not be decoded")]
+    UndecodableAccount(String),
+}
+
+/// A snapshot file holds either a single keyed account, as printed by
+/// `solana account --output json`, or a list of them, as returned by `getProgramAccounts`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SnapshotFile {
+    Many(Vec<RpcKeyedAccount>),
+    One(RpcKeyedAccount),
+}
+
+/// Accounts dumped from a cluster, used to plan migrations offline.
+#[derive(Debug, Default, Clone)]
+pub struct Snapshot {
+    accounts: HashMap<Pubkey, Account>,
+}
+
+impl Snapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads and merges snapshot files. Later files override earlier ones.
+    pub fn load<P: AsRef<Path>>(paths: &[P]) -> Result<Self, SnapshotError> {
+        let mut snapshot = Self::new();
+
+        for path in paths {
+            let path = path.as_ref();
+            let json =
+                fs::read_to_string(path).map_err(|e| SnapshotError::Io(path.to_path_buf(), e))?;
+            snapshot.extend_from_json(&json)?;
+        }
+
+        Ok(snapshot)
+    }
+
+    pub fn extend_from_json(&mut self, json: &str) -> Result<(), SnapshotError> {
+        let keyed_accounts = match serde_json::from_str(json)? {
+            SnapshotFile::Many(accounts) => accounts,
+            SnapshotFile::One(account) => vec![account],
+        };
+
+        for keyed_account in keyed_accounts {
+            let pubkey = Pubkey::from_str(&keyed_account.pubkey)
+                .map_err(|_| SnapshotError::InvalidPubkey(keyed_account.pubkey.clone()))?;
+            let account = keyed_account
+                .account
+                .decode()
+                .ok_or(SnapshotError::UndecodableAccount(keyed_account.pubkey))?;
+
+            self.accounts.insert(pubkey, account);
+        }
+
+        Ok(())
+    }
+
+    pub fn insert(&mut self, pubkey: Pubkey, account: Account) {
+        self.accounts.insert(pubkey, account);
+    }
+
+    pub fn get(&self, pubkey: &Pubkey) -> Option<&Account> {
+        self.accounts.get(pubkey)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Pubkey, &Account)> {
+        self.accounts.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+}
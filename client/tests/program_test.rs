@@ -0,0 +1,81 @@
+//! Runs the migration runner through a `BanksClient` against the migration validator and
+//! Token Metadata programs.
+#![cfg(feature = "program-test")]
+
+use mpl_migration_validator_client::{
+    journal::Journal,
+    runner::{MigrationRunner, RunnerConfig},
+};
+use mpl_migration_validator_test_utils::*;
+use mpl_token_metadata::state::TokenState;
+use solana_sdk::signer::Signer;
+
+#[tokio::test]
+async fn runner_migrates_a_collection() {
+    let mut context = setup_pnft_context().await;
+    let payer = context.payer.dirty_clone();
+
+    let mut collection = TestCollection::builder()
+        .items(3)
+        .build(&mut context)
+        .await
+        .unwrap();
+    collection.start(&mut context).await.unwrap();
+
+    let TestCollection {
+        parent: mut collection_nft,
+        mut items,
+        rule_set,
+        migratorr,
+        ..
+    } = collection;
+    let collection_mint = collection_nft.mint_pubkey();
+
+    // The collection parent is migrated last.
+    let mut accounts = vec![];
+    for nft in items.iter().chain([&collection_nft]) {
+        accounts.push(
+            migratorr
+                .migrate_accounts(
+                    &mut context,
+                    &payer,
+                    nft.mint_pubkey(),
+                    nft.token_pubkey(),
+                    payer.pubkey(),
+                    collection_mint,
+                )
+                .await,
+        );
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let journal = Journal::open(dir.path().join("journal.json"), collection_mint).unwrap();
+    let mut runner = MigrationRunner::new(
+        context.banks_client.clone(),
+        payer,
+        collection_mint,
+        journal,
+        RunnerConfig::default(),
+    );
+
+    let summary = runner.run(&accounts).await.unwrap();
+    assert_eq!(summary.confirmed, 4);
+    assert_eq!(summary.failed, 0);
+
+    let reconciliation = runner.reconcile().await.unwrap();
+    assert_eq!(reconciliation.found_migrated, 0);
+    assert_eq!(reconciliation.confirmed_items, 3);
+    assert_eq!(reconciliation.items_migrated, 3);
+
+    for nft in items.iter_mut().chain([&mut collection_nft]) {
+        nft.assert_pnft_migration(
+            &mut context,
+            Some(rule_set),
+            None,
+            None,
+            TokenState::Unlocked,
+        )
+        .await
+        .unwrap();
+    }
+}
@@ -0,0 +1,403 @@
+pub mod utils;
+
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+use async_trait::async_trait;
+use borsh::BorshSerialize;
+use mpl_migration_validator::{
+    errors::MigrationError, instruction::MigrateAccounts, state::MigrationState,
+    utils::find_migration_state_pda,
+};
+use mpl_migration_validator_client::{
+    journal::{ItemStatus, Journal},
+    lookup_table::migration_lookup_table_addresses,
+    runner::{MigrationClient, MigrationRunner, RunnerConfig, RunnerError, SendError},
+};
+use mpl_token_metadata::{pda::find_metadata_account, state::TokenStandard};
+use solana_sdk::{
+    account::Account,
+    address_lookup_table_account::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::InstructionError,
+    message::VersionedMessage,
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    transaction::{TransactionError, VersionedTransaction},
+};
+use tempfile::TempDir;
+use utils::*;
+
+// Position of the item mint in the migrate accounts.
+const ITEM_MINT: usize = 6;
+
+/// Executes migrate instructions by marking the items as programmable NFTs.
+#[derive(Default)]
+struct MockClient {
+    accounts: HashMap<Pubkey, Account>,
+    /// Items the program rejects.
+    rejected: HashMap<Pubkey, MigrationError>,
+    /// Items whose instruction fails with an error the program did not return.
+    failing: HashMap<Pubkey, InstructionError>,
+    /// Number of sends that fail before reaching the cluster.
+    transport_failures: u32,
+    /// Tables the addresses of v0 messages are loaded from.
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    sent: Vec<VersionedTransaction>,
+}
+
+impl MockClient {
+    fn migrate(&mut self, mint: Pubkey) {
+        let (metadata_pubkey, _) = find_metadata_account(&mint);
+        let metadata = metadata(
+            mint,
+            Pubkey::new_unique(),
+            None,
+            TokenStandard::ProgrammableNonFungible,
+        );
+        self.accounts
+            .insert(metadata_pubkey, metadata_account(&metadata));
+    }
+
+    /// Static keys followed by the writable, then readonly, addresses loaded from tables.
+    fn account_keys(&self, message: &VersionedMessage) -> Vec<Pubkey> {
+        let mut keys = message.static_account_keys().to_vec();
+
+        if let Some(lookups) = message.address_table_lookups() {
+            let table = |key: &Pubkey| {
+                self.lookup_tables
+                    .iter()
+                    .find(|table| table.key == *key)
+                    .expect("unknown lookup table")
+            };
+            for lookup in lookups {
+                let table = table(&lookup.account_key);
+                keys.extend(
+                    lookup
+                        .writable_indexes
+                        .iter()
+                        .map(|i| table.addresses[*i as usize]),
+                );
+            }
+            for lookup in lookups {
+                let table = table(&lookup.account_key);
+                keys.extend(
+                    lookup
+                        .readonly_indexes
+                        .iter()
+                        .map(|i| table.addresses[*i as usize]),
+                );
+            }
+        }
+
+        keys
+    }
+}
+
+#[async_trait]
+impl MigrationClient for MockClient {
+    async fn get_account(&mut self, pubkey: &Pubkey) -> Result<Option<Account>, RunnerError> {
+        Ok(self.accounts.get(pubkey).cloned())
+    }
+
+    async fn get_latest_blockhash(&mut self) -> Result<Hash, RunnerError> {
+        Ok(Hash::new_unique())
+    }
+
+    async fn send_and_confirm(
+        &mut self,
+        transaction: VersionedTransaction,
+    ) -> Result<(), SendError> {
+        let size = bincode::serialized_size(&transaction).unwrap() as usize;
+        assert!(size <= PACKET_DATA_SIZE, "transaction too large: {}", size);
+        self.sent.push(transaction.clone());
+
+        if self.transport_failures > 0 {
+            self.transport_failures -= 1;
+            return Err(SendError::Transport("connection reset".to_string()));
+        }
+
+        let account_keys = self.account_keys(&transaction.message);
+        let mut mints = vec![];
+        for (index, instruction) in transaction.message.instructions().iter().enumerate() {
+            if account_keys[instruction.program_id_index as usize] != mpl_migration_validator::ID {
+                continue;
+            }
+
+            let mint = account_keys[instruction.accounts[ITEM_MINT] as usize];
+            if let Some(error) = self.rejected.get(&mint) {
+                return Err(SendError::Transaction(TransactionError::InstructionError(
+                    index as u8,
+                    InstructionError::Custom(*error as u32),
+                )));
+            }
+            if let Some(error) = self.failing.get(&mint) {
+                return Err(SendError::Transaction(TransactionError::InstructionError(
+                    index as u8,
+                    error.clone(),
+                )));
+            }
+            mints.push(mint);
+        }
+
+        for mint in mints {
+            self.migrate(mint);
+        }
+        Ok(())
+    }
+}
+
+/// A journal path in a directory removed when the returned guard is dropped.
+fn journal_path() -> (TempDir, PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("journal.json");
+    (dir, path)
+}
+
+fn items(collection_mint: Pubkey, count: usize) -> Vec<MigrateAccounts> {
+    (0..count)
+        .map(|_| {
+            MigrateAccounts::new(
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                collection_mint,
+            )
+            .no_buffer()
+        })
+        .collect()
+}
+
+fn config() -> RunnerConfig {
+    RunnerConfig {
+        retry_delay: Duration::ZERO,
+        ..RunnerConfig::default()
+    }
+}
+
+#[tokio::test]
+async fn run_batches_items_and_resumes() {
+    let collection_mint = Pubkey::new_unique();
+    let (_dir, path) = journal_path();
+    let items = items(collection_mint, 5);
+
+    let journal = Journal::open(&path, collection_mint).unwrap();
+    let mut runner = MigrationRunner::new(
+        MockClient::default(),
+        Keypair::new(),
+        collection_mint,
+        journal,
+        config(),
+    );
+
+    let summary = runner.run(&items).await.unwrap();
+    assert_eq!(summary.confirmed, 5);
+    assert_eq!(summary.failed, 0);
+
+    // Several items share each transaction.
+    assert!(summary.transactions < 5);
+    assert_eq!(runner.client().sent.len(), summary.transactions);
+
+    // A new run with the same journal has nothing left to do.
+    let journal = Journal::open(&path, collection_mint).unwrap();
+    assert_eq!(
+        journal.count(|status| matches!(status, ItemStatus::Confirmed { .. })),
+        5
+    );
+
+    let mut runner = MigrationRunner::new(
+        MockClient::default(),
+        Keypair::new(),
+        collection_mint,
+        journal,
+        config(),
+    );
+    let summary = runner.run(&items).await.unwrap();
+    assert_eq!(summary.skipped, 5);
+    assert!(runner.client().sent.is_empty());
+}
+
+#[tokio::test]
+async fn rejected_item_does_not_block_its_batch() {
+    let collection_mint = Pubkey::new_unique();
+    let items = items(collection_mint, 3);
+    let rejected = items[1].item_mint;
+
+    let mut client = MockClient::default();
+    client
+        .rejected
+        .insert(rejected, MigrationError::ImmutableMetadata);
+
+    let (_dir, path) = journal_path();
+    let journal = Journal::open(path, collection_mint).unwrap();
+    let mut runner =
+        MigrationRunner::new(client, Keypair::new(), collection_mint, journal, config());
+
+    let summary = runner.run(&items).await.unwrap();
+    assert_eq!(summary.confirmed, 2);
+    assert_eq!(summary.failed, 1);
+
+    match runner.journal().status(&rejected) {
+        Some(ItemStatus::Failed {
+            error, permanent, ..
+        }) => {
+            assert!(error.starts_with("ImmutableMetadata"));
+            assert!(permanent);
+        }
+        status => panic!("unexpected status {:?}", status),
+    }
+    assert!(runner.journal().is_confirmed(&items[0].item_mint));
+    assert!(runner.journal().is_confirmed(&items[2].item_mint));
+}
+
+#[tokio::test]
+async fn failing_item_in_the_middle_of_a_batch_is_set_aside() {
+    let collection_mint = Pubkey::new_unique();
+    let items = items(collection_mint, 3);
+    let failing = items[1].item_mint;
+
+    let mut client = MockClient::default();
+    client
+        .failing
+        .insert(failing, InstructionError::ComputationalBudgetExceeded);
+
+    let (_dir, path) = journal_path();
+    let journal = Journal::open(path, collection_mint).unwrap();
+    let mut runner =
+        MigrationRunner::new(client, Keypair::new(), collection_mint, journal, config());
+
+    let summary = runner.run(&items).await.unwrap();
+    assert_eq!(summary.confirmed, 2);
+    assert_eq!(summary.failed, 1);
+
+    // The failure is traced to its item past the compute budget request, without
+    // retrying the batch.
+    assert_eq!(summary.transactions, 2);
+
+    // Errors the program did not return are retried by a later run.
+    match runner.journal().status(&failing) {
+        Some(ItemStatus::Failed {
+            error, permanent, ..
+        }) => {
+            assert_eq!(error, "ComputationalBudgetExceeded");
+            assert!(!permanent);
+        }
+        status => panic!("unexpected status {:?}", status),
+    }
+    assert!(runner.journal().is_confirmed(&items[0].item_mint));
+    assert!(runner.journal().is_confirmed(&items[2].item_mint));
+}
+
+#[tokio::test]
+async fn transport_failures_are_retried() {
+    let collection_mint = Pubkey::new_unique();
+    let items = items(collection_mint, 2);
+
+    let client = MockClient {
+        transport_failures: 2,
+        ..MockClient::default()
+    };
+
+    let (_dir, path) = journal_path();
+    let journal = Journal::open(path, collection_mint).unwrap();
+    let mut runner =
+        MigrationRunner::new(client, Keypair::new(), collection_mint, journal, config());
+
+    let summary = runner.run(&items).await.unwrap();
+    assert_eq!(summary.confirmed, 2);
+    assert_eq!(summary.failed, 0);
+    assert_eq!(summary.transactions, 3);
+}
+
+#[tokio::test]
+async fn reconcile_settles_submitted_items() {
+    let collection_mint = Pubkey::new_unique();
+    let landed = Pubkey::new_unique();
+    let lost = Pubkey::new_unique();
+
+    // A previous run crashed after submitting both items.
+    let (_dir, path) = journal_path();
+    let mut journal = Journal::open(&path, collection_mint).unwrap();
+    journal
+        .record(
+            [landed, lost],
+            ItemStatus::Submitted {
+                signature: Signature::default(),
+            },
+        )
+        .unwrap();
+
+    let mut client = MockClient::default();
+    client.migrate(landed);
+
+    let mut migration_state = MigrationState::default();
+    migration_state.status.items_migrated = 1;
+    let (migration_state_pubkey, _) = find_migration_state_pda(&collection_mint);
+    client.accounts.insert(
+        migration_state_pubkey,
+        account(
+            mpl_migration_validator::ID,
+            migration_state.try_to_vec().unwrap(),
+        ),
+    );
+
+    let journal = Journal::open(&path, collection_mint).unwrap();
+    let mut runner =
+        MigrationRunner::new(client, Keypair::new(), collection_mint, journal, config());
+
+    let reconciliation = runner.reconcile().await.unwrap();
+    assert_eq!(reconciliation.found_migrated, 1);
+    assert_eq!(reconciliation.reset, 1);
+    assert_eq!(reconciliation.confirmed_items, 1);
+    assert_eq!(reconciliation.items_migrated, 1);
+
+    assert!(runner.journal().is_confirmed(&landed));
+    assert_eq!(runner.journal().status(&lost), None);
+}
+
+#[tokio::test]
+async fn lookup_tables_fit_more_items_per_transaction() {
+    let collection_mint = Pubkey::new_unique();
+    let items = items(collection_mint, 8);
+
+    let (_legacy_dir, path) = journal_path();
+    let journal = Journal::open(path, collection_mint).unwrap();
+    let mut legacy = MigrationRunner::new(
+        MockClient::default(),
+        Keypair::new(),
+        collection_mint,
+        journal,
+        config(),
+    );
+    let legacy = legacy.run(&items).await.unwrap();
+    assert_eq!(legacy.confirmed, 8);
+
+    let payer = Keypair::new();
+    let lookup_table = AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: migration_lookup_table_addresses(
+            &collection_mint,
+            &Pubkey::default(),
+            &payer.pubkey(),
+        ),
+    };
+    let client = MockClient {
+        lookup_tables: vec![lookup_table.clone()],
+        ..MockClient::default()
+    };
+
+    let (_dir, path) = journal_path();
+    let journal = Journal::open(path, collection_mint).unwrap();
+    let mut runner = MigrationRunner::new(client, payer, collection_mint, journal, config())
+        .with_lookup_tables(vec![lookup_table]);
+    let summary = runner.run(&items).await.unwrap();
+    assert_eq!(summary.confirmed, 8);
+    assert!(summary.transactions < legacy.transactions);
+
+    assert!(runner
+        .client()
+        .sent
+        .iter()
+        .all(|transaction| matches!(transaction.message, VersionedMessage::V0(_))));
+}
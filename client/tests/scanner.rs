@@ -0,0 +1,333 @@
+pub mod utils;
+
+use borsh::BorshSerialize;
+use mpl_migration_validator::{
+    errors::MigrationError,
+    instruction::TokenOwnerProgramBuffer,
+    state::{CollectionInfo, MigrationState, MigrationStatus, MIGRATION_STATE_ACCOUNT_LEN},
+    utils::{find_legacy_delegate_record_pda, find_migration_state_pda},
+    PROGRAM_SIGNER,
+};
+use mpl_migration_validator_client::{
+    scanner::{plan_migration, MigrationCounters, Prediction},
+    snapshot::Snapshot,
+};
+use mpl_token_metadata::{
+    pda::{find_master_edition_account, find_metadata_account},
+    state::{Collection, Key, MasterEditionV2, Metadata, TokenStandard, MAX_MASTER_EDITION_LEN},
+};
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_client::rpc_response::RpcKeyedAccount;
+use solana_sdk::{
+    account::Account, bpf_loader_upgradeable, bpf_loader_upgradeable::UpgradeableLoaderState,
+    program_option::COption, program_pack::Pack, pubkey::Pubkey, system_program,
+};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+use utils::*;
+
+struct TestCollection {
+    snapshot: Snapshot,
+    mint: Pubkey,
+    authority: Pubkey,
+}
+
+impl TestCollection {
+    fn new() -> Self {
+        let mut collection = Self {
+            snapshot: Snapshot::new(),
+            mint: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+        };
+        collection.add_nft(collection.mint, None, |_| {});
+        collection
+    }
+
+    /// Adds an NFT held by a wallet and returns its mint.
+    fn add_item(&mut self, f: impl FnOnce(&mut Metadata)) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        let collection = Some(Collection {
+            verified: true,
+            key: self.mint,
+        });
+        self.add_nft(mint, collection, f);
+        mint
+    }
+
+    fn add_nft(
+        &mut self,
+        mint: Pubkey,
+        collection: Option<Collection>,
+        f: impl FnOnce(&mut Metadata),
+    ) {
+        let (metadata_pubkey, _) = find_metadata_account(&mint);
+        let (edition_pubkey, _) = find_master_edition_account(&mint);
+
+        let mut metadata = metadata(mint, self.authority, collection, TokenStandard::NonFungible);
+        f(&mut metadata);
+
+        let edition = MasterEditionV2 {
+            key: Key::MasterEditionV2,
+            supply: 0,
+            max_supply: Some(0),
+        };
+
+        let mut mint_data = vec![0; Mint::LEN];
+        Mint {
+            mint_authority: COption::Some(edition_pubkey),
+            supply: 1,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: COption::Some(edition_pubkey),
+        }
+        .pack_into_slice(&mut mint_data);
+
+        let mut token_data = vec![0; TokenAccount::LEN];
+        TokenAccount {
+            mint,
+            owner: Pubkey::new_unique(),
+            amount: 1,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        }
+        .pack_into_slice(&mut token_data);
+
+        let snapshot = &mut self.snapshot;
+        snapshot.insert(metadata_pubkey, metadata_account(&metadata));
+        snapshot.insert(
+            edition_pubkey,
+            account(
+                mpl_token_metadata::ID,
+                padded(edition.try_to_vec().unwrap(), MAX_MASTER_EDITION_LEN),
+            ),
+        );
+        snapshot.insert(mint, account(spl_token::ID, mint_data));
+        snapshot.insert(Pubkey::new_unique(), account(spl_token::ID, token_data));
+    }
+
+    fn token(&self, mint: &Pubkey) -> (Pubkey, TokenAccount) {
+        self.snapshot
+            .iter()
+            .filter(|(_, account)| account.owner == spl_token::ID)
+            .filter_map(|(pubkey, account)| {
+                Some((*pubkey, TokenAccount::unpack(&account.data).ok()?))
+            })
+            .find(|(_, token)| token.mint == *mint)
+            .unwrap()
+    }
+}
+
+#[test]
+fn plan_predicts_failures() {
+    let mut collection = TestCollection::new();
+
+    let ready = collection.add_item(|_| {});
+    let immutable = collection.add_item(|metadata| metadata.is_mutable = false);
+    let migrated = collection.add_item(|metadata| {
+        metadata.token_standard = Some(TokenStandard::ProgrammableNonFungible)
+    });
+    let other_authority =
+        collection.add_item(|metadata| metadata.update_authority = Pubkey::new_unique());
+
+    // Unverified items are not part of the collection.
+    collection.add_item(|metadata| metadata.collection.as_mut().unwrap().verified = false);
+
+    let plan = plan_migration(&collection.snapshot, &collection.mint).unwrap();
+
+    assert!(!plan.initialized);
+    assert_eq!(plan.items.len(), 5);
+    assert_eq!(plan.ready, 2);
+    assert_eq!(plan.migrated, 1);
+    assert_eq!(plan.failing, 2);
+
+    // The collection parent is migrated last by default.
+    let parent = plan.items.last().unwrap();
+    assert!(parent.is_collection_parent);
+    assert_eq!(parent.mint, collection.mint);
+
+    let prediction = |mint: Pubkey| {
+        plan.items
+            .iter()
+            .find(|item| item.mint == mint)
+            .unwrap()
+            .prediction
+            .clone()
+    };
+
+    assert_eq!(prediction(ready), Prediction::Ready);
+    assert_eq!(prediction(migrated), Prediction::Migrated);
+    assert_eq!(
+        prediction(immutable),
+        Prediction::from(MigrationError::ImmutableMetadata)
+    );
+    assert_eq!(
+        prediction(other_authority),
+        Prediction::from(MigrationError::InvalidAuthority)
+    );
+
+    let (token, token_account) = collection.token(&ready);
+    let item = plan.ready_items().find(|item| item.mint == ready).unwrap();
+    let accounts = item.migrate_accounts(&plan, Pubkey::new_unique()).unwrap();
+    assert_eq!(accounts.item_token, token);
+    assert_eq!(accounts.token_owner, token_account.owner);
+    assert_eq!(accounts.token_owner_program, system_program::ID);
+    assert_eq!(
+        accounts.token_owner_program_buffer,
+        TokenOwnerProgramBuffer::None
+    );
+}
+
+#[test]
+fn plan_checks_upgradeable_owner_programs() {
+    let mut collection = TestCollection::new();
+    let item = collection.add_item(|_| {});
+
+    // Hand the token to a PDA of an upgradeable program with no upgrade authority.
+    let program = Pubkey::new_unique();
+    let programdata = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+
+    let (token, mut token_account) = collection.token(&item);
+    token_account.owner = vault;
+    let mut token_data = vec![0; TokenAccount::LEN];
+    token_account.pack_into_slice(&mut token_data);
+
+    let snapshot = &mut collection.snapshot;
+    snapshot.insert(token, account(spl_token::ID, token_data));
+    snapshot.insert(vault, account(program, vec![0; 8]));
+
+    // Exercise the JSON snapshot format for the program accounts.
+    let program_account = account(
+        bpf_loader_upgradeable::ID,
+        bincode::serialize(&UpgradeableLoaderState::Program {
+            programdata_address: programdata,
+        })
+        .unwrap(),
+    );
+    let programdata_account = account(
+        bpf_loader_upgradeable::ID,
+        bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: None,
+        })
+        .unwrap(),
+    );
+    let keyed = |pubkey: Pubkey, account: &Account| RpcKeyedAccount {
+        pubkey: pubkey.to_string(),
+        account: UiAccount::encode(&pubkey, account, UiAccountEncoding::Base64, None, None),
+    };
+    let json = serde_json::to_string(&vec![
+        keyed(program, &program_account),
+        keyed(programdata, &programdata_account),
+    ])
+    .unwrap();
+    snapshot.extend_from_json(&json).unwrap();
+
+    let plan = plan_migration(&collection.snapshot, &collection.mint).unwrap();
+    let planned = plan.items.iter().find(|i| i.mint == item).unwrap();
+
+    assert_eq!(planned.token_owner, Some(vault));
+    assert_eq!(planned.token_owner_program, Some(program));
+    assert_eq!(planned.token_owner_program_buffer, Some(programdata));
+    assert_eq!(
+        planned.prediction,
+        Prediction::from(MigrationError::ImmutableProgramOwner)
+    );
+}
+
+#[test]
+fn plan_reports_migration_counters() {
+    let mut collection = TestCollection::new();
+    collection.add_item(|_| {});
+
+    let state = MigrationState {
+        collection_info: CollectionInfo {
+            authority: collection.authority,
+            mint: collection.mint,
+            size: 2,
+            ..CollectionInfo::default()
+        },
+        status: MigrationStatus {
+            in_progress: true,
+            items_migrated: 1,
+            unique_payers: 1,
+            token_record_rent_paid: 2_000,
+            first_migration_slot: 10,
+            last_migration_slot: 10,
+            ..MigrationStatus::default()
+        },
+        payer_record_rent_paid: 900,
+        ..MigrationState::default()
+    };
+    let (migration_state, _) = find_migration_state_pda(&collection.mint);
+    collection.snapshot.insert(
+        migration_state,
+        account(
+            mpl_migration_validator::ID,
+            padded(state.try_to_vec().unwrap(), MIGRATION_STATE_ACCOUNT_LEN),
+        ),
+    );
+
+    let plan = plan_migration(&collection.snapshot, &collection.mint).unwrap();
+
+    assert!(plan.initialized);
+    assert_eq!(
+        plan.counters,
+        MigrationCounters {
+            items_migrated: 1,
+            unique_payers: 1,
+            token_record_rent_paid: 2_000,
+            payer_record_rent_paid: 900,
+            first_migration_slot: 10,
+            last_migration_slot: 10,
+            ..MigrationCounters::default()
+        }
+    );
+    assert_eq!(plan.counters.rent_paid(), 2_900);
+}
+
+#[test]
+fn plan_keeps_the_legacy_program_signer() {
+    let mut collection = TestCollection::new();
+    let item = collection.add_item(|_| {});
+
+    // Migrations started before collections had their own signer delegated to
+    // the global program signer.
+    let (delegate_record, _) = find_legacy_delegate_record_pda(&collection.mint);
+    let state = MigrationState {
+        collection_info: CollectionInfo {
+            authority: collection.authority,
+            mint: collection.mint,
+            delegate_record,
+            size: 1,
+            ..CollectionInfo::default()
+        },
+        status: MigrationStatus {
+            in_progress: true,
+            ..MigrationStatus::default()
+        },
+        ..MigrationState::default()
+    };
+    let (migration_state, _) = find_migration_state_pda(&collection.mint);
+    collection.snapshot.insert(
+        migration_state,
+        account(
+            mpl_migration_validator::ID,
+            padded(state.try_to_vec().unwrap(), MIGRATION_STATE_ACCOUNT_LEN),
+        ),
+    );
+
+    let plan = plan_migration(&collection.snapshot, &collection.mint).unwrap();
+    assert!(plan.legacy_signer);
+
+    let planned = plan.items.iter().find(|i| i.mint == item).unwrap();
+    let payer = Pubkey::new_unique();
+    let accounts = planned.migrate_accounts(&plan, payer).unwrap();
+    assert!(accounts.legacy_signer);
+
+    let metas = accounts.to_account_metas().unwrap();
+    assert_eq!(metas[8].pubkey, PROGRAM_SIGNER);
+    assert_eq!(metas[10].pubkey, delegate_record);
+}
@@ -0,0 +1,53 @@
+use borsh::BorshSerialize;
+use mpl_token_metadata::state::{Collection, Data, Key, Metadata, TokenStandard, MAX_METADATA_LEN};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+pub fn account(owner: Pubkey, data: Vec<u8>) -> Account {
+    Account {
+        lamports: 1_000_000,
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+pub fn padded(mut data: Vec<u8>, len: usize) -> Vec<u8> {
+    data.resize(len, 0);
+    data
+}
+
+pub fn metadata(
+    mint: Pubkey,
+    update_authority: Pubkey,
+    collection: Option<Collection>,
+    token_standard: TokenStandard,
+) -> Metadata {
+    Metadata {
+        key: Key::MetadataV1,
+        update_authority,
+        mint,
+        data: Data {
+            name: "Item".to_string(),
+            symbol: "ITEM".to_string(),
+            uri: "https://example.com".to_string(),
+            seller_fee_basis_points: 0,
+            creators: None,
+        },
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: Some(token_standard),
+        collection,
+        uses: None,
+        collection_details: None,
+        programmable_config: None,
+    }
+}
+
+pub fn metadata_account(metadata: &Metadata) -> Account {
+    account(
+        mpl_token_metadata::ID,
+        padded(metadata.try_to_vec().unwrap(), MAX_METADATA_LEN),
+    )
+}
@@ -0,0 +1,32 @@
+import BN from 'bn.js';
+import { MigrationState } from '../generated';
+
+/**
+ * Progress and rent counters recorded by a migration state.
+ */
+export type MigrationCounters = {
+  itemsMigrated: number;
+  collectionParentMigrated: boolean;
+  uniquePayers: number;
+  /** Lamports paid by payers for the rent of created token records. */
+  tokenRecordRentPaid: BN;
+  /** Lamports paid by payers for the rent of their payer records, returned by `ClosePayerRecord`. */
+  payerRecordRentPaid: BN;
+  /** Total rent paid by payers for the accounts created by their migrations. */
+  rentPaid: BN;
+};
+
+export function migrationCounters(state: MigrationState): MigrationCounters {
+  const { status } = state;
+  const tokenRecordRentPaid = new BN(status.tokenRecordRentPaid);
+  const payerRecordRentPaid = new BN(state.payerRecordRentPaid);
+
+  return {
+    itemsMigrated: status.itemsMigrated,
+    collectionParentMigrated: status.collectionParentMigrated,
+    uniquePayers: status.uniquePayers,
+    tokenRecordRentPaid,
+    payerRecordRentPaid,
+    rentPaid: tokenRecordRentPaid.add(payerRecordRentPaid),
+  };
+}
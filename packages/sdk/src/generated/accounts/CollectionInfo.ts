@@ -20,6 +20,7 @@ export type CollectionInfoArgs = {
   ruleSet: web3.PublicKey;
   delegateRecord: web3.PublicKey;
   size: number;
+  isSized: boolean;
 };
 /**
  * Holds the data for the {@link CollectionInfo} Account and provides de/serialization
@@ -35,6 +36,7 @@ export class CollectionInfo implements CollectionInfoArgs {
     readonly ruleSet: web3.PublicKey,
     readonly delegateRecord: web3.PublicKey,
     readonly size: number,
+    readonly isSized: boolean,
   ) {}
 
   /**
@@ -47,6 +49,7 @@ export class CollectionInfo implements CollectionInfoArgs {
       args.ruleSet,
       args.delegateRecord,
       args.size,
+      args.isSized,
     );
   }
 
@@ -147,6 +150,7 @@ export class CollectionInfo implements CollectionInfoArgs {
       ruleSet: this.ruleSet.toBase58(),
       delegateRecord: this.delegateRecord.toBase58(),
       size: this.size,
+      isSized: this.isSized,
     };
   }
 }
@@ -162,6 +166,7 @@ export const collectionInfoBeet = new beet.BeetStruct<CollectionInfo, Collection
     ['ruleSet', beetSolana.publicKey],
     ['delegateRecord', beetSolana.publicKey],
     ['size', beet.u32],
+    ['isSized', beet.bool],
   ],
   CollectionInfo.fromArgs,
   'CollectionInfo',
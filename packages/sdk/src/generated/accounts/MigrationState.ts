@@ -5,12 +5,16 @@
  * See: https://github.com/metaplex-foundation/solita
  */
 
-import * as web3 from '@solana/web3.js';
 import * as beet from '@metaplex-foundation/beet';
+import * as web3 from '@solana/web3.js';
 import * as beetSolana from '@metaplex-foundation/beet-solana';
+import { Key, keyBeet } from '../types/Key';
 import { CollectionInfo, collectionInfoBeet } from './CollectionInfo';
 import { UnlockMethod, unlockMethodBeet } from '../types/UnlockMethod';
 import { MigrationStatus, migrationStatusBeet } from './MigrationStatus';
+import { DelegateHandling, delegateHandlingBeet } from '../types/DelegateHandling';
+import { CollectionParentOrder, collectionParentOrderBeet } from '../types/CollectionParentOrder';
+import { UpdateAuthorityMode, updateAuthorityModeBeet } from '../types/UpdateAuthorityMode';
 
 /**
  * Arguments used to create {@link MigrationState}
@@ -18,9 +22,23 @@ import { MigrationStatus, migrationStatusBeet } from './MigrationStatus';
  * @category generated
  */
 export type MigrationStateArgs = {
+  key: Key;
   collectionInfo: CollectionInfo;
   unlockMethod: UnlockMethod;
   status: MigrationStatus;
+  delegateHandling: DelegateHandling;
+  collectionParentOrder: CollectionParentOrder;
+  bountyReward: beet.bignum;
+  pendingAuthority: web3.PublicKey;
+  pendingAuthorityExpiry: beet.bignum;
+  updateAuthorityMode: UpdateAuthorityMode;
+  bump: number;
+  delegateRecordBump: number;
+  collectionParentReleased: boolean;
+  payerRecordRentPaid: beet.bignum;
+  collectionSignerBump: number;
+  ownerProgramListBump: number;
+  bountyVaultBump: number;
 };
 /**
  * Holds the data for the {@link MigrationState} Account and provides de/serialization
@@ -31,16 +49,48 @@ export type MigrationStateArgs = {
  */
 export class MigrationState implements MigrationStateArgs {
   private constructor(
+    readonly key: Key,
     readonly collectionInfo: CollectionInfo,
     readonly unlockMethod: UnlockMethod,
     readonly status: MigrationStatus,
+    readonly delegateHandling: DelegateHandling,
+    readonly collectionParentOrder: CollectionParentOrder,
+    readonly bountyReward: beet.bignum,
+    readonly pendingAuthority: web3.PublicKey,
+    readonly pendingAuthorityExpiry: beet.bignum,
+    readonly updateAuthorityMode: UpdateAuthorityMode,
+    readonly bump: number,
+    readonly delegateRecordBump: number,
+    readonly collectionParentReleased: boolean,
+    readonly payerRecordRentPaid: beet.bignum,
+    readonly collectionSignerBump: number,
+    readonly ownerProgramListBump: number,
+    readonly bountyVaultBump: number,
   ) {}
 
   /**
    * Creates a {@link MigrationState} instance from the provided args.
    */
   static fromArgs(args: MigrationStateArgs) {
-    return new MigrationState(args.collectionInfo, args.unlockMethod, args.status);
+    return new MigrationState(
+      args.key,
+      args.collectionInfo,
+      args.unlockMethod,
+      args.status,
+      args.delegateHandling,
+      args.collectionParentOrder,
+      args.bountyReward,
+      args.pendingAuthority,
+      args.pendingAuthorityExpiry,
+      args.updateAuthorityMode,
+      args.bump,
+      args.delegateRecordBump,
+      args.collectionParentReleased,
+      args.payerRecordRentPaid,
+      args.collectionSignerBump,
+      args.ownerProgramListBump,
+      args.bountyVaultBump,
+    );
   }
 
   /**
@@ -135,9 +185,54 @@ export class MigrationState implements MigrationStateArgs {
    */
   pretty() {
     return {
+      key: 'Key.' + Key[this.key],
       collectionInfo: this.collectionInfo,
       unlockMethod: 'UnlockMethod.' + UnlockMethod[this.unlockMethod],
       status: this.status,
+      delegateHandling: 'DelegateHandling.' + DelegateHandling[this.delegateHandling],
+      collectionParentOrder:
+        'CollectionParentOrder.' + CollectionParentOrder[this.collectionParentOrder],
+      bountyReward: (() => {
+        const x = <{ toNumber: () => number }>this.bountyReward;
+        if (typeof x.toNumber === 'function') {
+          try {
+            return x.toNumber();
+          } catch (_) {
+            return x;
+          }
+        }
+        return x;
+      })(),
+      pendingAuthority: this.pendingAuthority.toBase58(),
+      pendingAuthorityExpiry: (() => {
+        const x = <{ toNumber: () => number }>this.pendingAuthorityExpiry;
+        if (typeof x.toNumber === 'function') {
+          try {
+            return x.toNumber();
+          } catch (_) {
+            return x;
+          }
+        }
+        return x;
+      })(),
+      updateAuthorityMode: 'UpdateAuthorityMode.' + UpdateAuthorityMode[this.updateAuthorityMode],
+      bump: this.bump,
+      delegateRecordBump: this.delegateRecordBump,
+      collectionParentReleased: this.collectionParentReleased,
+      payerRecordRentPaid: (() => {
+        const x = <{ toNumber: () => number }>this.payerRecordRentPaid;
+        if (typeof x.toNumber === 'function') {
+          try {
+            return x.toNumber();
+          } catch (_) {
+            return x;
+          }
+        }
+        return x;
+      })(),
+      collectionSignerBump: this.collectionSignerBump,
+      ownerProgramListBump: this.ownerProgramListBump,
+      bountyVaultBump: this.bountyVaultBump,
     };
   }
 }
@@ -148,9 +243,23 @@ export class MigrationState implements MigrationStateArgs {
  */
 export const migrationStateBeet = new beet.BeetStruct<MigrationState, MigrationStateArgs>(
   [
+    ['key', keyBeet],
     ['collectionInfo', collectionInfoBeet],
     ['unlockMethod', unlockMethodBeet],
     ['status', migrationStatusBeet],
+    ['delegateHandling', delegateHandlingBeet],
+    ['collectionParentOrder', collectionParentOrderBeet],
+    ['bountyReward', beet.u64],
+    ['pendingAuthority', beetSolana.publicKey],
+    ['pendingAuthorityExpiry', beet.i64],
+    ['updateAuthorityMode', updateAuthorityModeBeet],
+    ['bump', beet.u8],
+    ['delegateRecordBump', beet.u8],
+    ['collectionParentReleased', beet.bool],
+    ['payerRecordRentPaid', beet.u64],
+    ['collectionSignerBump', beet.u8],
+    ['ownerProgramListBump', beet.u8],
+    ['bountyVaultBump', beet.u8],
   ],
   MigrationState.fromArgs,
   'MigrationState',
@@ -19,6 +19,13 @@ export type MigrationStatusArgs = {
   isLocked: boolean;
   inProgress: boolean;
   itemsMigrated: number;
+  collectionParentMigrated: boolean;
+  tokenRecordRentPaid: beet.bignum;
+  uniquePayers: number;
+  firstMigrationSlot: beet.bignum;
+  lastMigrationSlot: beet.bignum;
+  firstMigrationTimestamp: beet.bignum;
+  lastMigrationTimestamp: beet.bignum;
 };
 /**
  * Holds the data for the {@link MigrationStatus} Account and provides de/serialization
@@ -33,13 +40,32 @@ export class MigrationStatus implements MigrationStatusArgs {
     readonly isLocked: boolean,
     readonly inProgress: boolean,
     readonly itemsMigrated: number,
+    readonly collectionParentMigrated: boolean,
+    readonly tokenRecordRentPaid: beet.bignum,
+    readonly uniquePayers: number,
+    readonly firstMigrationSlot: beet.bignum,
+    readonly lastMigrationSlot: beet.bignum,
+    readonly firstMigrationTimestamp: beet.bignum,
+    readonly lastMigrationTimestamp: beet.bignum,
   ) {}
 
   /**
    * Creates a {@link MigrationStatus} instance from the provided args.
    */
   static fromArgs(args: MigrationStatusArgs) {
-    return new MigrationStatus(args.unlockTime, args.isLocked, args.inProgress, args.itemsMigrated);
+    return new MigrationStatus(
+      args.unlockTime,
+      args.isLocked,
+      args.inProgress,
+      args.itemsMigrated,
+      args.collectionParentMigrated,
+      args.tokenRecordRentPaid,
+      args.uniquePayers,
+      args.firstMigrationSlot,
+      args.lastMigrationSlot,
+      args.firstMigrationTimestamp,
+      args.lastMigrationTimestamp,
+    );
   }
 
   /**
@@ -148,6 +174,63 @@ export class MigrationStatus implements MigrationStatusArgs {
       isLocked: this.isLocked,
       inProgress: this.inProgress,
       itemsMigrated: this.itemsMigrated,
+      collectionParentMigrated: this.collectionParentMigrated,
+      tokenRecordRentPaid: (() => {
+        const x = <{ toNumber: () => number }>this.tokenRecordRentPaid;
+        if (typeof x.toNumber === 'function') {
+          try {
+            return x.toNumber();
+          } catch (_) {
+            return x;
+          }
+        }
+        return x;
+      })(),
+      uniquePayers: this.uniquePayers,
+      firstMigrationSlot: (() => {
+        const x = <{ toNumber: () => number }>this.firstMigrationSlot;
+        if (typeof x.toNumber === 'function') {
+          try {
+            return x.toNumber();
+          } catch (_) {
+            return x;
+          }
+        }
+        return x;
+      })(),
+      lastMigrationSlot: (() => {
+        const x = <{ toNumber: () => number }>this.lastMigrationSlot;
+        if (typeof x.toNumber === 'function') {
+          try {
+            return x.toNumber();
+          } catch (_) {
+            return x;
+          }
+        }
+        return x;
+      })(),
+      firstMigrationTimestamp: (() => {
+        const x = <{ toNumber: () => number }>this.firstMigrationTimestamp;
+        if (typeof x.toNumber === 'function') {
+          try {
+            return x.toNumber();
+          } catch (_) {
+            return x;
+          }
+        }
+        return x;
+      })(),
+      lastMigrationTimestamp: (() => {
+        const x = <{ toNumber: () => number }>this.lastMigrationTimestamp;
+        if (typeof x.toNumber === 'function') {
+          try {
+            return x.toNumber();
+          } catch (_) {
+            return x;
+          }
+        }
+        return x;
+      })(),
     };
   }
 }
@@ -162,6 +245,13 @@ export const migrationStatusBeet = new beet.BeetStruct<MigrationStatus, Migratio
     ['isLocked', beet.bool],
     ['inProgress', beet.bool],
     ['itemsMigrated', beet.u32],
+    ['collectionParentMigrated', beet.bool],
+    ['tokenRecordRentPaid', beet.u64],
+    ['uniquePayers', beet.u32],
+    ['firstMigrationSlot', beet.u64],
+    ['lastMigrationSlot', beet.u64],
+    ['firstMigrationTimestamp', beet.i64],
+    ['lastMigrationTimestamp', beet.i64],
   ],
   MigrationStatus.fromArgs,
   'MigrationStatus',
@@ -8,6 +8,7 @@
 import * as beet from '@metaplex-foundation/beet';
 import * as web3 from '@solana/web3.js';
 import * as beetSolana from '@metaplex-foundation/beet-solana';
+import { Key, keyBeet } from '../types/Key';
 
 /**
  * Arguments used to create {@link ProgramSigner}
@@ -15,6 +16,7 @@ import * as beetSolana from '@metaplex-foundation/beet-solana';
  * @category generated
  */
 export type ProgramSignerArgs = {
+  key: Key;
   bump: number;
 };
 /**
@@ -25,13 +27,13 @@ export type ProgramSignerArgs = {
  * @category generated
  */
 export class ProgramSigner implements ProgramSignerArgs {
-  private constructor(readonly bump: number) {}
+  private constructor(readonly key: Key, readonly bump: number) {}
 
   /**
    * Creates a {@link ProgramSigner} instance from the provided args.
    */
   static fromArgs(args: ProgramSignerArgs) {
-    return new ProgramSigner(args.bump);
+    return new ProgramSigner(args.key, args.bump);
   }
 
   /**
@@ -126,6 +128,7 @@ export class ProgramSigner implements ProgramSignerArgs {
    */
   pretty() {
     return {
+      key: 'Key.' + Key[this.key],
       bump: this.bump,
     };
   }
@@ -136,7 +139,10 @@ export class ProgramSigner implements ProgramSignerArgs {
  * @category generated
  */
 export const programSignerBeet = new beet.BeetStruct<ProgramSigner, ProgramSignerArgs>(
-  [['bump', beet.u8]],
+  [
+    ['key', keyBeet],
+    ['bump', beet.u8],
+  ],
   ProgramSigner.fromArgs,
   'ProgramSigner',
 );
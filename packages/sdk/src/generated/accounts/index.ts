@@ -1,11 +1,22 @@
 export * from './CollectionInfo';
 export * from './MigrationState';
 export * from './MigrationStatus';
+export * from './OwnerProgramList';
+export * from './PayerRecord';
 export * from './ProgramSigner';
 
 import { MigrationState } from './MigrationState';
 import { CollectionInfo } from './CollectionInfo';
 import { MigrationStatus } from './MigrationStatus';
+import { OwnerProgramList } from './OwnerProgramList';
 import { ProgramSigner } from './ProgramSigner';
+import { PayerRecord } from './PayerRecord';
 
-export const accountProviders = { MigrationState, CollectionInfo, MigrationStatus, ProgramSigner };
+export const accountProviders = {
+  MigrationState,
+  CollectionInfo,
+  MigrationStatus,
+  OwnerProgramList,
+  ProgramSigner,
+  PayerRecord,
+};
@@ -890,6 +890,651 @@ export class InvalidRuleSetError extends Error {
 createErrorFromCodeLookup.set(0x29, () => new InvalidRuleSetError());
 createErrorFromNameLookup.set('InvalidRuleSet', () => new InvalidRuleSetError());
 
+/**
+ * DeprecatedInstruction: 'This instruction has been deprecated'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class DeprecatedInstructionError extends Error {
+  readonly code: number = 0x2a;
+  readonly name: string = 'DeprecatedInstruction';
+  constructor() {
+    super('This instruction has been deprecated');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, DeprecatedInstructionError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x2a, () => new DeprecatedInstructionError());
+createErrorFromNameLookup.set('DeprecatedInstruction', () => new DeprecatedInstructionError());
+
+/**
+ * IncorrectEditionProgramOwner: 'Incorrect program owner for edition account'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class IncorrectEditionProgramOwnerError extends Error {
+  readonly code: number = 0x2b;
+  readonly name: string = 'IncorrectEditionProgramOwner';
+  constructor() {
+    super('Incorrect program owner for edition account');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, IncorrectEditionProgramOwnerError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x2b, () => new IncorrectEditionProgramOwnerError());
+createErrorFromNameLookup.set(
+  'IncorrectEditionProgramOwner',
+  () => new IncorrectEditionProgramOwnerError(),
+);
+
+/**
+ * NotMasterEdition: 'Item edition is not a master edition'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class NotMasterEditionError extends Error {
+  readonly code: number = 0x2c;
+  readonly name: string = 'NotMasterEdition';
+  constructor() {
+    super('Item edition is not a master edition');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, NotMasterEditionError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x2c, () => new NotMasterEditionError());
+createErrorFromNameLookup.set('NotMasterEdition', () => new NotMasterEditionError());
+
+/**
+ * InvalidOwnerProgramListDerivation: 'Owner program list account derivation is incorrect'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidOwnerProgramListDerivationError extends Error {
+  readonly code: number = 0x2d;
+  readonly name: string = 'InvalidOwnerProgramListDerivation';
+  constructor() {
+    super('Owner program list account derivation is incorrect');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidOwnerProgramListDerivationError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x2d, () => new InvalidOwnerProgramListDerivationError());
+createErrorFromNameLookup.set(
+  'InvalidOwnerProgramListDerivation',
+  () => new InvalidOwnerProgramListDerivationError(),
+);
+
+/**
+ * IncorrectOwnerProgramListProgramOwner: 'Incorrect program owner for owner program list account'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class IncorrectOwnerProgramListProgramOwnerError extends Error {
+  readonly code: number = 0x2e;
+  readonly name: string = 'IncorrectOwnerProgramListProgramOwner';
+  constructor() {
+    super('Incorrect program owner for owner program list account');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, IncorrectOwnerProgramListProgramOwnerError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x2e, () => new IncorrectOwnerProgramListProgramOwnerError());
+createErrorFromNameLookup.set(
+  'IncorrectOwnerProgramListProgramOwner',
+  () => new IncorrectOwnerProgramListProgramOwnerError(),
+);
+
+/**
+ * InvalidOwnerProgramList: 'Owner program list did not deserialize correctly'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidOwnerProgramListError extends Error {
+  readonly code: number = 0x2f;
+  readonly name: string = 'InvalidOwnerProgramList';
+  constructor() {
+    super('Owner program list did not deserialize correctly');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidOwnerProgramListError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x2f, () => new InvalidOwnerProgramListError());
+createErrorFromNameLookup.set('InvalidOwnerProgramList', () => new InvalidOwnerProgramListError());
+
+/**
+ * TokenOwnerProgramNotAllowed: 'Token owner program is not allowed to migrate by the owner program list'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class TokenOwnerProgramNotAllowedError extends Error {
+  readonly code: number = 0x30;
+  readonly name: string = 'TokenOwnerProgramNotAllowed';
+  constructor() {
+    super('Token owner program is not allowed to migrate by the owner program list');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, TokenOwnerProgramNotAllowedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x30, () => new TokenOwnerProgramNotAllowedError());
+createErrorFromNameLookup.set(
+  'TokenOwnerProgramNotAllowed',
+  () => new TokenOwnerProgramNotAllowedError(),
+);
+
+/**
+ * TokenDelegateNotAllowed: 'Items with an SPL token delegate cannot be migrated'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class TokenDelegateNotAllowedError extends Error {
+  readonly code: number = 0x31;
+  readonly name: string = 'TokenDelegateNotAllowed';
+  constructor() {
+    super('Items with an SPL token delegate cannot be migrated');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, TokenDelegateNotAllowedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x31, () => new TokenDelegateNotAllowedError());
+createErrorFromNameLookup.set('TokenDelegateNotAllowed', () => new TokenDelegateNotAllowedError());
+
+/**
+ * FrozenTokenNotAllowed: 'Items with a frozen token account cannot be migrated'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class FrozenTokenNotAllowedError extends Error {
+  readonly code: number = 0x32;
+  readonly name: string = 'FrozenTokenNotAllowed';
+  constructor() {
+    super('Items with a frozen token account cannot be migrated');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, FrozenTokenNotAllowedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x32, () => new FrozenTokenNotAllowedError());
+createErrorFromNameLookup.set('FrozenTokenNotAllowed', () => new FrozenTokenNotAllowedError());
+
+/**
+ * InvalidTokenDelegate: 'SPL token delegate must be delegated the full token amount'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidTokenDelegateError extends Error {
+  readonly code: number = 0x33;
+  readonly name: string = 'InvalidTokenDelegate';
+  constructor() {
+    super('SPL token delegate must be delegated the full token amount');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidTokenDelegateError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x33, () => new InvalidTokenDelegateError());
+createErrorFromNameLookup.set('InvalidTokenDelegate', () => new InvalidTokenDelegateError());
+
+/**
+ * UnexpectedCollectionParent: 'Collection parent must be migrated with MigrateCollectionParent'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class UnexpectedCollectionParentError extends Error {
+  readonly code: number = 0x34;
+  readonly name: string = 'UnexpectedCollectionParent';
+  constructor() {
+    super('Collection parent must be migrated with MigrateCollectionParent');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, UnexpectedCollectionParentError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x34, () => new UnexpectedCollectionParentError());
+createErrorFromNameLookup.set(
+  'UnexpectedCollectionParent',
+  () => new UnexpectedCollectionParentError(),
+);
+
+/**
+ * NotCollectionParent: 'Item is not the collection parent'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class NotCollectionParentError extends Error {
+  readonly code: number = 0x35;
+  readonly name: string = 'NotCollectionParent';
+  constructor() {
+    super('Item is not the collection parent');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, NotCollectionParentError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x35, () => new NotCollectionParentError());
+createErrorFromNameLookup.set('NotCollectionParent', () => new NotCollectionParentError());
+
+/**
+ * CollectionParentNotMigrated: 'Collection parent must be migrated before its items'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class CollectionParentNotMigratedError extends Error {
+  readonly code: number = 0x36;
+  readonly name: string = 'CollectionParentNotMigrated';
+  constructor() {
+    super('Collection parent must be migrated before its items');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, CollectionParentNotMigratedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x36, () => new CollectionParentNotMigratedError());
+createErrorFromNameLookup.set(
+  'CollectionParentNotMigrated',
+  () => new CollectionParentNotMigratedError(),
+);
+
+/**
+ * CollectionParentAlreadyMigrated: 'Collection parent has already been migrated'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class CollectionParentAlreadyMigratedError extends Error {
+  readonly code: number = 0x37;
+  readonly name: string = 'CollectionParentAlreadyMigrated';
+  constructor() {
+    super('Collection parent has already been migrated');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, CollectionParentAlreadyMigratedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x37, () => new CollectionParentAlreadyMigratedError());
+createErrorFromNameLookup.set(
+  'CollectionParentAlreadyMigrated',
+  () => new CollectionParentAlreadyMigratedError(),
+);
+
+/**
+ * CollectionItemsNotMigrated: 'Collection items must be migrated before the collection parent'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class CollectionItemsNotMigratedError extends Error {
+  readonly code: number = 0x38;
+  readonly name: string = 'CollectionItemsNotMigrated';
+  constructor() {
+    super('Collection items must be migrated before the collection parent');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, CollectionItemsNotMigratedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x38, () => new CollectionItemsNotMigratedError());
+createErrorFromNameLookup.set(
+  'CollectionItemsNotMigrated',
+  () => new CollectionItemsNotMigratedError(),
+);
+
+/**
+ * CollectionSizeMismatch: 'Collection size does not match the size of the sized collection'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class CollectionSizeMismatchError extends Error {
+  readonly code: number = 0x39;
+  readonly name: string = 'CollectionSizeMismatch';
+  constructor() {
+    super('Collection size does not match the size of the sized collection');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, CollectionSizeMismatchError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x39, () => new CollectionSizeMismatchError());
+createErrorFromNameLookup.set('CollectionSizeMismatch', () => new CollectionSizeMismatchError());
+
+/**
+ * MigrationStateNotUpgraded: 'Migration state account must be upgraded to the current version'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class MigrationStateNotUpgradedError extends Error {
+  readonly code: number = 0x3a;
+  readonly name: string = 'MigrationStateNotUpgraded';
+  constructor() {
+    super('Migration state account must be upgraded to the current version');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, MigrationStateNotUpgradedError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x3a, () => new MigrationStateNotUpgradedError());
+createErrorFromNameLookup.set(
+  'MigrationStateNotUpgraded',
+  () => new MigrationStateNotUpgradedError(),
+);
+
+/**
+ * InvalidMigrationStateKey: 'Account is not a migration state account'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidMigrationStateKeyError extends Error {
+  readonly code: number = 0x3b;
+  readonly name: string = 'InvalidMigrationStateKey';
+  constructor() {
+    super('Account is not a migration state account');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidMigrationStateKeyError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x3b, () => new InvalidMigrationStateKeyError());
+createErrorFromNameLookup.set(
+  'InvalidMigrationStateKey',
+  () => new InvalidMigrationStateKeyError(),
+);
+
+/**
+ * InvalidProgramSignerKey: 'Account is not a program signer account'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidProgramSignerKeyError extends Error {
+  readonly code: number = 0x3c;
+  readonly name: string = 'InvalidProgramSignerKey';
+  constructor() {
+    super('Account is not a program signer account');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidProgramSignerKeyError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x3c, () => new InvalidProgramSignerKeyError());
+createErrorFromNameLookup.set('InvalidProgramSignerKey', () => new InvalidProgramSignerKeyError());
+
+/**
+ * InvalidOwnerProgramListKey: 'Account is not an owner program list account'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidOwnerProgramListKeyError extends Error {
+  readonly code: number = 0x3d;
+  readonly name: string = 'InvalidOwnerProgramListKey';
+  constructor() {
+    super('Account is not an owner program list account');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidOwnerProgramListKeyError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x3d, () => new InvalidOwnerProgramListKeyError());
+createErrorFromNameLookup.set(
+  'InvalidOwnerProgramListKey',
+  () => new InvalidOwnerProgramListKeyError(),
+);
+
+/**
+ * InvalidBountyVaultDerivation: 'Bounty vault account derivation is incorrect'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidBountyVaultDerivationError extends Error {
+  readonly code: number = 0x3e;
+  readonly name: string = 'InvalidBountyVaultDerivation';
+  constructor() {
+    super('Bounty vault account derivation is incorrect');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidBountyVaultDerivationError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x3e, () => new InvalidBountyVaultDerivationError());
+createErrorFromNameLookup.set(
+  'InvalidBountyVaultDerivation',
+  () => new InvalidBountyVaultDerivationError(),
+);
+
+/**
+ * IncorrectBountyVaultProgramOwner: 'Incorrect program owner for bounty vault account'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class IncorrectBountyVaultProgramOwnerError extends Error {
+  readonly code: number = 0x3f;
+  readonly name: string = 'IncorrectBountyVaultProgramOwner';
+  constructor() {
+    super('Incorrect program owner for bounty vault account');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, IncorrectBountyVaultProgramOwnerError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x3f, () => new IncorrectBountyVaultProgramOwnerError());
+createErrorFromNameLookup.set(
+  'IncorrectBountyVaultProgramOwner',
+  () => new IncorrectBountyVaultProgramOwnerError(),
+);
+
+/**
+ * MigrationNotComplete: 'Migration has not completed yet'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class MigrationNotCompleteError extends Error {
+  readonly code: number = 0x40;
+  readonly name: string = 'MigrationNotComplete';
+  constructor() {
+    super('Migration has not completed yet');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, MigrationNotCompleteError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x40, () => new MigrationNotCompleteError());
+createErrorFromNameLookup.set('MigrationNotComplete', () => new MigrationNotCompleteError());
+
+/**
+ * InvalidPayerRecordDerivation: 'Payer record account derivation is incorrect'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidPayerRecordDerivationError extends Error {
+  readonly code: number = 0x41;
+  readonly name: string = 'InvalidPayerRecordDerivation';
+  constructor() {
+    super('Payer record account derivation is incorrect');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidPayerRecordDerivationError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x41, () => new InvalidPayerRecordDerivationError());
+createErrorFromNameLookup.set(
+  'InvalidPayerRecordDerivation',
+  () => new InvalidPayerRecordDerivationError(),
+);
+
+/**
+ * IncorrectPayerRecordProgramOwner: 'Incorrect program owner for payer record account'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class IncorrectPayerRecordProgramOwnerError extends Error {
+  readonly code: number = 0x42;
+  readonly name: string = 'IncorrectPayerRecordProgramOwner';
+  constructor() {
+    super('Incorrect program owner for payer record account');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, IncorrectPayerRecordProgramOwnerError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x42, () => new IncorrectPayerRecordProgramOwnerError());
+createErrorFromNameLookup.set(
+  'IncorrectPayerRecordProgramOwner',
+  () => new IncorrectPayerRecordProgramOwnerError(),
+);
+
+/**
+ * InvalidPayerRecordKey: 'Payer record account has an incorrect key'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidPayerRecordKeyError extends Error {
+  readonly code: number = 0x43;
+  readonly name: string = 'InvalidPayerRecordKey';
+  constructor() {
+    super('Payer record account has an incorrect key');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidPayerRecordKeyError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x43, () => new InvalidPayerRecordKeyError());
+createErrorFromNameLookup.set('InvalidPayerRecordKey', () => new InvalidPayerRecordKeyError());
+
+/**
+ * InvalidPayerRecord: 'Invalid payer record account'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidPayerRecordError extends Error {
+  readonly code: number = 0x44;
+  readonly name: string = 'InvalidPayerRecord';
+  constructor() {
+    super('Invalid payer record account');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidPayerRecordError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x44, () => new InvalidPayerRecordError());
+createErrorFromNameLookup.set('InvalidPayerRecord', () => new InvalidPayerRecordError());
+
+/**
+ * InvalidPendingAuthority: 'Signer is not the pending authority'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class InvalidPendingAuthorityError extends Error {
+  readonly code: number = 0x45;
+  readonly name: string = 'InvalidPendingAuthority';
+  constructor() {
+    super('Signer is not the pending authority');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, InvalidPendingAuthorityError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x45, () => new InvalidPendingAuthorityError());
+createErrorFromNameLookup.set('InvalidPendingAuthority', () => new InvalidPendingAuthorityError());
+
+/**
+ * PendingAuthorityExpired: 'Pending authority handoff has expired'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class PendingAuthorityExpiredError extends Error {
+  readonly code: number = 0x46;
+  readonly name: string = 'PendingAuthorityExpired';
+  constructor() {
+    super('Pending authority handoff has expired');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, PendingAuthorityExpiredError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x46, () => new PendingAuthorityExpiredError());
+createErrorFromNameLookup.set('PendingAuthorityExpired', () => new PendingAuthorityExpiredError());
+
+/**
+ * EmptyCollection: 'Collection has no items to migrate'
+ *
+ * @category Errors
+ * @category generated
+ */
+export class EmptyCollectionError extends Error {
+  readonly code: number = 0x47;
+  readonly name: string = 'EmptyCollection';
+  constructor() {
+    super('Collection has no items to migrate');
+    if (typeof Error.captureStackTrace === 'function') {
+      Error.captureStackTrace(this, EmptyCollectionError);
+    }
+  }
+}
+
+createErrorFromCodeLookup.set(0x47, () => new EmptyCollectionError());
+createErrorFromNameLookup.set('EmptyCollection', () => new EmptyCollectionError());
+
 /**
  * Attempts to resolve a custom program error from the provided error code.
  * @category Errors
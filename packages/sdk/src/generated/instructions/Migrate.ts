@@ -7,14 +7,30 @@
 
 import * as beet from '@metaplex-foundation/beet';
 import * as web3 from '@solana/web3.js';
+import { MigrateItemArgs, migrateItemArgsBeet } from '../types/MigrateItemArgs';
 
 /**
  * @category Instructions
  * @category Migrate
  * @category generated
  */
-export const MigrateStruct = new beet.BeetArgsStruct<{ instructionDiscriminator: number }>(
-  [['instructionDiscriminator', beet.u8]],
+export type MigrateInstructionArgs = {
+  migrateItemArgs: MigrateItemArgs;
+};
+/**
+ * @category Instructions
+ * @category Migrate
+ * @category generated
+ */
+export const MigrateStruct = new beet.BeetArgsStruct<
+  MigrateInstructionArgs & {
+    instructionDiscriminator: number;
+  }
+>(
+  [
+    ['instructionDiscriminator', beet.u8],
+    ['migrateItemArgs', migrateItemArgsBeet],
+  ],
   'MigrateInstructionArgs',
 );
 /**
@@ -28,16 +44,19 @@ export const MigrateStruct = new beet.BeetArgsStruct<{ instructionDiscriminator:
  * @property [] tokenOwnerProgramBuffer Executable buffer account of the program owner
  * @property [] itemMint Mint account
  * @property [_writable_, **signer**] payer Pays for migration costs
- * @property [] programSigner Program signer PDA
+ * @property [] programSigner Program signer PDA for the collection
  * @property [] collectionMetadata Collection metadata account
- * @property [] delegateRecord Update authority or delegate
- * @property [_writable_] tokenRecord Update authority or delegate
+ * @property [] delegateRecord Collection delegate record for the program signer
+ * @property [_writable_] tokenRecord Token record account created by the migration
  * @property [] sysvarInstructions Instruction sysvar account
  * @property [] splTokenProgram Token Program
  * @property [] authorizationRulesProgram (optional) Token Authorization Rules Program
  * @property [] authorizationRules (optional) Token Authorization Rules account
  * @property [_writable_] migrationState The migration state account
  * @property [] tokenMetadataProgram Token Metadata program for the CPI call
+ * @property [] ownerProgramList The owner program list account
+ * @property [_writable_] bountyVault The bounty vault paying the payer for the migration
+ * @property [_writable_] payerRecord The record marking the payer as having paid for a migration
  * @category Instructions
  * @category Migrate
  * @category generated
@@ -62,6 +81,9 @@ export type MigrateInstructionAccounts = {
   authorizationRules?: web3.PublicKey;
   migrationState: web3.PublicKey;
   tokenMetadataProgram: web3.PublicKey;
+  ownerProgramList: web3.PublicKey;
+  bountyVault: web3.PublicKey;
+  payerRecord: web3.PublicKey;
 };
 
 export const migrateInstructionDiscriminator = 5;
@@ -73,16 +95,20 @@ export const migrateInstructionDiscriminator = 5;
  * this was indicated in the IDL from which this instruction was generated.
  *
  * @param accounts that will be accessed while the instruction is processed
+ * @param args to provide as instruction data to the program
+ *
  * @category Instructions
  * @category Migrate
  * @category generated
  */
 export function createMigrateInstruction(
   accounts: MigrateInstructionAccounts,
+  args: MigrateInstructionArgs,
   programId = new web3.PublicKey('migrxZFChTqicHpNa1CAjPcF29Mui2JU2q4Ym7qQUTi'),
 ) {
   const [data] = MigrateStruct.serialize({
     instructionDiscriminator: migrateInstructionDiscriminator,
+    ...args,
   });
   const keys: web3.AccountMeta[] = [
     {
@@ -180,6 +206,21 @@ export function createMigrateInstruction(
       isWritable: false,
       isSigner: false,
     },
+    {
+      pubkey: accounts.ownerProgramList,
+      isWritable: false,
+      isSigner: false,
+    },
+    {
+      pubkey: accounts.bountyVault,
+      isWritable: true,
+      isSigner: false,
+    },
+    {
+      pubkey: accounts.payerRecord,
+      isWritable: true,
+      isSigner: false,
+    },
   ];
 
   const ix = new web3.TransactionInstruction({
@@ -21,10 +21,10 @@ export const StartStruct = new beet.BeetArgsStruct<{ instructionDiscriminator: n
  * Accounts required by the _Start_ instruction
  *
  * @property [_writable_, **signer**] payer Paying account for initiate migration
- * @property [**signer**] authority The collection authority
+ * @property [_writable_, **signer**] authority The collection authority
  * @property [] collectionMint The mint account of the collection parent NFT
  * @property [] collectionMetadata The metadata account of the collection parent NFT
- * @property [] programSigner Program signer PDA
+ * @property [] programSigner Program signer PDA for the collection
  * @property [_writable_] delegateRecord The collection delegate record of for the program signer and the collection
  * @property [_writable_] migrationState The migration state account
  * @property [] splTokenProgram Token Program
@@ -71,7 +71,7 @@ export function createStartInstruction(
     },
     {
       pubkey: accounts.authority,
-      isWritable: false,
+      isWritable: true,
       isSigner: true,
     },
     {
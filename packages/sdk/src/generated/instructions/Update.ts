@@ -38,6 +38,7 @@ export const UpdateStruct = new beet.FixableBeetArgsStruct<
  *
  * @property [_writable_, **signer**] authority The collection authority
  * @property [_writable_] migrationState The migration state account
+ * @property [] collectionMetadata The metadata account of the collection parent NFT
  * @category Instructions
  * @category Update
  * @category generated
@@ -45,6 +46,7 @@ export const UpdateStruct = new beet.FixableBeetArgsStruct<
 export type UpdateInstructionAccounts = {
   authority: web3.PublicKey;
   migrationState: web3.PublicKey;
+  collectionMetadata: web3.PublicKey;
 };
 
 export const updateInstructionDiscriminator = 2;
@@ -79,6 +81,11 @@ export function createUpdateInstruction(
       isWritable: true,
       isSigner: false,
     },
+    {
+      pubkey: accounts.collectionMetadata,
+      isWritable: false,
+      isSigner: false,
+    },
   ];
 
   const ix = new web3.TransactionInstruction({
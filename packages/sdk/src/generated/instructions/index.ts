@@ -1,6 +1,14 @@
+export * from './AcceptAuthority';
 export * from './Close';
+export * from './ClosePayerRecord';
+export * from './FinalizeBounty';
+export * from './FundBounty';
 export * from './InitSigner';
 export * from './Initialize';
 export * from './Migrate';
+export * from './MigrateCollectionParent';
+export * from './SetOwnerProgramList';
 export * from './Start';
+export * from './SyncAuthority';
 export * from './Update';
+export * from './UpgradeState';
@@ -8,10 +8,17 @@
 import * as web3 from '@solana/web3.js';
 import * as beet from '@metaplex-foundation/beet';
 import * as beetSolana from '@metaplex-foundation/beet-solana';
+import { DelegateHandling, delegateHandlingBeet } from './DelegateHandling';
+import { CollectionParentOrder, collectionParentOrderBeet } from './CollectionParentOrder';
+import { UpdateAuthorityMode, updateAuthorityModeBeet } from './UpdateAuthorityMode';
 export type UpdateArgs = {
   ruleSet: beet.COption<web3.PublicKey>;
   collectionSize: beet.COption<number>;
   newUpdateAuthority: beet.COption<web3.PublicKey>;
+  delegateHandling: beet.COption<DelegateHandling>;
+  collectionParentOrder: beet.COption<CollectionParentOrder>;
+  updateAuthorityMode: beet.COption<UpdateAuthorityMode>;
+  releaseCollectionParent: beet.COption<boolean>;
 };
 
 /**
@@ -23,6 +30,10 @@ export const updateArgsBeet = new beet.FixableBeetArgsStruct<UpdateArgs>(
     ['ruleSet', beet.coption(beetSolana.publicKey)],
     ['collectionSize', beet.coption(beet.u32)],
     ['newUpdateAuthority', beet.coption(beetSolana.publicKey)],
+    ['delegateHandling', beet.coption(delegateHandlingBeet)],
+    ['collectionParentOrder', beet.coption(collectionParentOrderBeet)],
+    ['updateAuthorityMode', beet.coption(updateAuthorityModeBeet)],
+    ['releaseCollectionParent', beet.coption(beet.bool)],
   ],
   'UpdateArgs',
 );
@@ -1,3 +1,14 @@
+export * from './CollectionInfoV0';
+export * from './CollectionParentOrder';
+export * from './DelegateHandling';
+export * from './FundBountyArgs';
 export * from './InitializeArgs';
+export * from './Key';
+export * from './ListType';
+export * from './MigrateItemArgs';
+export * from './MigrationStateV0';
+export * from './MigrationStatusV0';
+export * from './SetOwnerProgramListArgs';
 export * from './UnlockMethod';
 export * from './UpdateArgs';
+export * from './UpdateAuthorityMode';
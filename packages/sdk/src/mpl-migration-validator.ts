@@ -1,3 +1,4 @@
 export * from './errors';
+export * from './custom/counters';
 // @ts-ignore
 export * from './generated';
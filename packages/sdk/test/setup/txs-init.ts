@@ -131,18 +131,22 @@ export class InitTransactions {
   async update(
     handler: PayerTransactionHandler,
     authority: Keypair,
-    migrationState: PublicKey,
+    collectionMint: PublicKey,
     args: UpdateArgs,
   ): Promise<{
     tx: ConfirmedTransactionAssertablePromise;
     migrationState: PublicKey;
   }> {
     amman.addr.addLabel('Authority', authority.publicKey);
-    amman.addr.addLabel('Migration State', migrationState);
+    amman.addr.addLabel('Collection Mint', collectionMint);
+
+    const collectionMetadata = findMetadataAddress(collectionMint);
+    const migrationState = findMigrationState(collectionMint);
 
     const accounts: UpdateInstructionAccounts = {
       authority: authority.publicKey,
       migrationState,
+      collectionMetadata,
     };
 
     const ixArgs: UpdateInstructionArgs = {
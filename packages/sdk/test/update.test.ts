@@ -49,9 +49,14 @@ test('Update: successfully update state account', async (t) => {
   const updateArgs: UpdateArgs = {
     ruleSet: newRuleSet,
     collectionSize: null,
+    newUpdateAuthority: null,
+    delegateHandling: null,
+    collectionParentOrder: null,
+    updateAuthorityMode: null,
+    releaseCollectionParent: null,
   };
 
-  const { tx: updateTx } = await API.update(handler, payer, migrationState, updateArgs);
+  const { tx: updateTx } = await API.update(handler, payer, mint, updateArgs);
   await updateTx.assertSuccess(t);
 
   const newState = await MigrationState.fromAccountAddress(connection, migrationState);
@@ -110,7 +115,7 @@ test('Update: successfully update state account', async (t) => {
 //     ruleSet: defaultKey,
 //   };
 
-//   const { tx: updateTx } = await API.update(handler, payer, migrationState, updateArgs);
+//   const { tx: updateTx } = await API.update(handler, payer, mint, updateArgs);
 //   await updateTx.assertSuccess(t);
 
 //   const newState = await MigrationState.fromAccountAddress(connection, migrationState);
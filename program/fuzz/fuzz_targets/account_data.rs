@@ -0,0 +1,110 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use mpl_migration_validator::{
+    instruction::{MigrateAccounts, TokenOwnerProgramBuffer},
+    state::{
+        Key, MigrationState, OwnerProgramList, PayerRecord, ProgramSigner, MIGRATION_STATE_LEN,
+    },
+    validation::{owner_program_is_upgradeable, token_owner_program_buffer_matches},
+    view::MigrationStateView,
+};
+use mpl_migration_validator_fuzz::{AccountBuffer, FuzzKey};
+use solana_program::{
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    pubkey::Pubkey,
+};
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    key: FuzzKey,
+    data: Vec<u8>,
+}
+
+fuzz_target!(|input: Input| {
+    let Input { key, data } = input;
+    let key = key.pubkey();
+    let mut account = AccountBuffer::new(key, mpl_migration_validator::ID, 0, &data);
+    let info = account.info();
+
+    // Program accounts are read from attacker-controlled bytes.
+    let state = MigrationState::from_account_info(&info);
+    let _ = ProgramSigner::from_account_info(&info);
+    let _ = OwnerProgramList::from_account_info(&info);
+    let _ = PayerRecord::from_account_info(&info);
+
+    // The view reads the current layout in place and must agree with Borsh.
+    if let Ok(view) = MigrationStateView::new(&data[..]) {
+        match &state {
+            Ok(state) => {
+                assert_eq!(view.authority(), state.collection_info.authority);
+                assert_eq!(view.mint(), state.collection_info.mint);
+                assert_eq!(view.unlock_method().unwrap(), state.unlock_method);
+                assert_eq!(view.items_migrated(), state.status.items_migrated);
+                assert_eq!(
+                    view.collection_parent_migrated().unwrap(),
+                    state.status.collection_parent_migrated
+                );
+                assert_eq!(
+                    view.token_record_rent_paid(),
+                    state.status.token_record_rent_paid
+                );
+                assert_eq!(view.bump(), state.bump);
+                assert_eq!(view.delegate_record_bump(), state.delegate_record_bump);
+                assert_eq!(
+                    view.collection_parent_released().unwrap(),
+                    state.collection_parent_released
+                );
+                assert_eq!(view.payer_record_rent_paid(), state.payer_record_rent_paid);
+                assert_eq!(view.collection_signer_bump(), state.collection_signer_bump);
+                assert_eq!(
+                    view.owner_program_list_bump(),
+                    state.owner_program_list_bump
+                );
+                assert_eq!(view.bounty_vault_bump(), state.bounty_vault_bump);
+            }
+            // Borsh only rejects a full-length state with the right key for a value the
+            // view rejects too.
+            Err(_) => assert!(
+                view.is_sized().is_err()
+                    || view.unlock_method().is_err()
+                    || view.is_locked().is_err()
+                    || view.in_progress().is_err()
+                    || view.collection_parent_migrated().is_err()
+                    || view.delegate_handling().is_err()
+                    || view.collection_parent_order().is_err()
+                    || view.update_authority_mode().is_err()
+                    || view.collection_parent_released().is_err(),
+                "the view reads a state Borsh rejects"
+            ),
+        }
+    } else if let Ok(state) = &state {
+        assert!(data.len() < MIGRATION_STATE_LEN || state.key != Key::MigrationStateV1);
+    }
+
+    // Token owner programs and their buffers are loader accounts of any program.
+    let loader_state: Option<UpgradeableLoaderState> = bincode::deserialize(&data).ok();
+    let _ = token_owner_program_buffer_matches(loader_state.as_ref(), &key);
+    if let Some(loader_state) = &loader_state {
+        let _ = owner_program_is_upgradeable(loader_state);
+    }
+
+    let accounts = MigrateAccounts::new(
+        Pubkey::default(),
+        Pubkey::default(),
+        Pubkey::default(),
+        Pubkey::default(),
+        Pubkey::default(),
+    )
+    .token_owner_program(key, &bpf_loader_upgradeable::ID, &data);
+    match loader_state {
+        Some(UpgradeableLoaderState::Program {
+            programdata_address,
+        }) => assert_eq!(
+            accounts.unwrap().token_owner_program_buffer,
+            TokenOwnerProgramBuffer::Buffer(programdata_address)
+        ),
+        _ => assert!(accounts.is_err()),
+    }
+});
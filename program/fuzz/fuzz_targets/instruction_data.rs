@@ -0,0 +1,12 @@
+#![no_main]
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use libfuzzer_sys::fuzz_target;
+use mpl_migration_validator::instruction::MigrationInstruction;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(instruction) = MigrationInstruction::try_from_slice(data) {
+        // Borsh has a single encoding per value, so any accepted input re-encodes to itself.
+        assert_eq!(instruction.try_to_vec().unwrap(), data);
+    }
+});
@@ -0,0 +1,58 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use mpl_migration_validator_fuzz::{
+    fixture::{MigrateFixture, CPI_CHECKED_POSITIONS},
+    install_stubs, process_instruction,
+};
+
+#[derive(Arbitrary, Debug)]
+enum Edit {
+    Swap(u8, u8),
+    Replace { position: u8, with: u8 },
+}
+
+// Permutes and substitutes the accounts of a valid migrate instruction. Any edit the
+// program accepts must only touch accounts Token Metadata checks in the CPI, otherwise the
+// program is missing a validation.
+fuzz_target!(|edits: Vec<Edit>| {
+    install_stubs();
+
+    let MigrateFixture {
+        mut accounts,
+        instruction,
+    } = MigrateFixture::new();
+    let original: Vec<_> = instruction
+        .accounts
+        .iter()
+        .map(|meta| meta.pubkey)
+        .collect();
+
+    let len = original.len();
+    let mut keys = original.clone();
+    for edit in edits {
+        match edit {
+            Edit::Swap(a, b) => keys.swap(a as usize % len, b as usize % len),
+            Edit::Replace { position, with } => {
+                keys[position as usize % len] = original[with as usize % len]
+            }
+        }
+    }
+
+    let result = process_instruction(&mut accounts, &keys, &instruction.data);
+
+    let moved: Vec<usize> = (0..len)
+        .filter(|i| keys[*i] != original[*i] && !CPI_CHECKED_POSITIONS.contains(i))
+        .collect();
+
+    if keys == original {
+        assert!(result.is_ok(), "the fixture must migrate: {:?}", result);
+    } else if !moved.is_empty() {
+        assert!(
+            result.is_err(),
+            "migrate accepted accounts moved to positions {:?}",
+            moved
+        );
+    }
+});
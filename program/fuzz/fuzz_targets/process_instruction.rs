@@ -0,0 +1,73 @@
+#![no_main]
+
+use std::{collections::HashSet, sync::OnceLock};
+
+use arbitrary::Arbitrary;
+use borsh::BorshDeserialize;
+use libfuzzer_sys::fuzz_target;
+use mpl_migration_validator::instruction::MigrationInstruction;
+use mpl_migration_validator_fuzz::{
+    install_stubs, process_instruction, stubs::set_unix_timestamp, AccountBuffer, FuzzAccount,
+};
+
+// Number of `MigrationInstruction` variants, found as the first variant index Borsh
+// refuses. Zeroed args decode for every variant, so new variants are picked up.
+fn instruction_count() -> u8 {
+    static COUNT: OnceLock<u8> = OnceLock::new();
+
+    *COUNT.get_or_init(|| {
+        (0..=u8::MAX)
+            .find(|index| {
+                let mut data = [0; 1024];
+                data[0] = *index;
+                MigrationInstruction::deserialize(&mut &data[..]).is_err()
+            })
+            .expect("every variant index decodes")
+    })
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    instruction: u8,
+    args: Vec<u8>,
+    accounts: Vec<FuzzAccount>,
+    // Positions into `accounts`, so the same account can be passed more than once.
+    account_indices: Vec<u8>,
+    unix_timestamp: i64,
+}
+
+fuzz_target!(|input: Input| {
+    install_stubs();
+    set_unix_timestamp(input.unix_timestamp);
+
+    // Every processor is reached by picking the variant, leaving only its args to decode.
+    let mut data = vec![input.instruction % instruction_count()];
+    data.extend_from_slice(&input.args);
+
+    // The runtime never passes two different accounts with the same key.
+    let mut seen = HashSet::new();
+    let mut accounts: Vec<AccountBuffer> = input
+        .accounts
+        .iter()
+        .map(FuzzAccount::buffer)
+        .filter(|account| seen.insert(account.key()))
+        .collect();
+    if accounts.is_empty() {
+        return;
+    }
+
+    let keys: Vec<_> = input
+        .account_indices
+        .iter()
+        .map(|index| accounts[*index as usize % accounts.len()].key())
+        .collect();
+
+    let lamports_before: u128 = accounts.iter().map(|a| a.lamports() as u128).sum();
+    let result = process_instruction(&mut accounts, &keys, &data);
+    let lamports_after: u128 = accounts.iter().map(|a| a.lamports() as u128).sum();
+
+    // The runtime fails instructions that create or destroy lamports.
+    if result.is_ok() {
+        assert_eq!(lamports_before, lamports_after, "unbalanced instruction");
+    }
+});
@@ -0,0 +1,229 @@
+use arbitrary::Arbitrary;
+use mpl_migration_validator::{
+    utils::{
+        find_bounty_vault_pda, find_collection_signer_pda, find_delegate_record_pda,
+        find_legacy_delegate_record_pda, find_migration_state_pda, find_owner_program_list_pda,
+        find_payer_record_pda,
+    },
+    MPL_TOKEN_AUTH_RULES_ID, PROGRAM_SIGNER,
+};
+use mpl_token_metadata::pda::{find_master_edition_account, find_metadata_account};
+use solana_program::{
+    account_info::AccountInfo, bpf_loader_upgradeable, entrypoint::MAX_PERMITTED_DATA_INCREASE,
+    pubkey::Pubkey, system_program, sysvar,
+};
+
+// The runtime serializes the original data length in the four bytes before each account
+// key, where `AccountInfo::original_data_len` reads it.
+#[repr(C)]
+struct KeyHeader {
+    original_data_len: u32,
+    key: Pubkey,
+    owner: Pubkey,
+}
+
+/// The memory of an account, laid out like the runtime's serialized input.
+///
+/// The data is preceded by its length and followed by `MAX_PERMITTED_DATA_INCREASE` bytes,
+/// so `AccountInfo::realloc` stays within the allocation.
+pub struct AccountBuffer {
+    header: Box<KeyHeader>,
+    lamports: u64,
+    data: Vec<u64>,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub executable: bool,
+}
+
+impl AccountBuffer {
+    pub fn new(key: Pubkey, owner: Pubkey, lamports: u64, data: &[u8]) -> Self {
+        let words = 1 + (data.len() + MAX_PERMITTED_DATA_INCREASE + 7) / 8;
+        let mut buffer = vec![0u64; words];
+        buffer[0] = data.len() as u64;
+
+        let mut account = Self {
+            header: Box::new(KeyHeader {
+                original_data_len: data.len() as u32,
+                key,
+                owner,
+            }),
+            lamports,
+            data: buffer,
+            is_signer: false,
+            is_writable: false,
+            executable: false,
+        };
+        account.data_mut()[..data.len()].copy_from_slice(data);
+
+        account
+    }
+
+    pub fn signer(mut self) -> Self {
+        self.is_signer = true;
+        self
+    }
+
+    pub fn writable(mut self) -> Self {
+        self.is_writable = true;
+        self
+    }
+
+    pub fn executable(mut self) -> Self {
+        self.executable = true;
+        self
+    }
+
+    pub fn key(&self) -> Pubkey {
+        self.header.key
+    }
+
+    pub fn owner(&self) -> Pubkey {
+        self.header.owner
+    }
+
+    pub fn lamports(&self) -> u64 {
+        self.lamports
+    }
+
+    /// The current data, including any reallocation by the processor.
+    pub fn data(&self) -> &[u8] {
+        let len = self.data[0] as usize;
+        unsafe { std::slice::from_raw_parts((self.data.as_ptr() as *const u8).add(8), len) }
+    }
+
+    fn data_mut(&mut self) -> &mut [u8] {
+        let len = self.data[0] as usize;
+        unsafe { std::slice::from_raw_parts_mut((self.data.as_mut_ptr() as *mut u8).add(8), len) }
+    }
+
+    pub fn info(&mut self) -> AccountInfo<'_> {
+        let len = self.data[0] as usize;
+        let data = unsafe {
+            std::slice::from_raw_parts_mut((self.data.as_mut_ptr() as *mut u8).add(8), len)
+        };
+
+        AccountInfo::new(
+            &self.header.key,
+            self.is_signer,
+            self.is_writable,
+            &mut self.lamports,
+            data,
+            &self.header.owner,
+            self.executable,
+            0,
+        )
+    }
+}
+
+/// Number of distinct mints and wallets fuzzed keys are drawn from, so PDAs of the same
+/// collection show up together.
+const KEY_SET_SIZE: u8 = 4;
+
+pub fn mint(index: u8) -> Pubkey {
+    Pubkey::new_from_array([1 + index % KEY_SET_SIZE; 32])
+}
+
+pub fn wallet(index: u8) -> Pubkey {
+    Pubkey::new_from_array([1 + KEY_SET_SIZE + index % KEY_SET_SIZE; 32])
+}
+
+/// A key the program checks for by address.
+#[derive(Arbitrary, Debug, Clone, Copy)]
+pub enum KnownKey {
+    MigrationValidator,
+    SystemProgram,
+    TokenMetadata,
+    SplToken,
+    TokenAuthRules,
+    SysvarInstructions,
+    BpfLoaderUpgradeable,
+    ProgramSigner,
+}
+
+/// A PDA derived from one of the fuzzed mints.
+#[derive(Arbitrary, Debug, Clone, Copy)]
+pub enum PdaKind {
+    MigrationState,
+    CollectionSigner,
+    DelegateRecord,
+    LegacyDelegateRecord,
+    OwnerProgramList,
+    BountyVault,
+    PayerRecord { wallet: u8 },
+    Metadata,
+    MasterEdition,
+}
+
+#[derive(Arbitrary, Debug, Clone, Copy)]
+pub enum FuzzKey {
+    Known(KnownKey),
+    Mint(u8),
+    Wallet(u8),
+    Pda { kind: PdaKind, mint: u8 },
+    Raw([u8; 32]),
+}
+
+impl FuzzKey {
+    pub fn pubkey(&self) -> Pubkey {
+        match *self {
+            FuzzKey::Known(known) => match known {
+                KnownKey::MigrationValidator => mpl_migration_validator::ID,
+                KnownKey::SystemProgram => system_program::ID,
+                KnownKey::TokenMetadata => mpl_token_metadata::ID,
+                KnownKey::SplToken => spl_token::ID,
+                KnownKey::TokenAuthRules => MPL_TOKEN_AUTH_RULES_ID,
+                KnownKey::SysvarInstructions => sysvar::instructions::ID,
+                KnownKey::BpfLoaderUpgradeable => bpf_loader_upgradeable::ID,
+                KnownKey::ProgramSigner => PROGRAM_SIGNER,
+            },
+            FuzzKey::Mint(index) => mint(index),
+            FuzzKey::Wallet(index) => wallet(index),
+            FuzzKey::Pda { kind, mint: index } => {
+                let mint = mint(index);
+                let (pda, _) = match kind {
+                    PdaKind::MigrationState => find_migration_state_pda(&mint),
+                    PdaKind::CollectionSigner => find_collection_signer_pda(&mint),
+                    PdaKind::DelegateRecord => find_delegate_record_pda(&mint),
+                    PdaKind::LegacyDelegateRecord => find_legacy_delegate_record_pda(&mint),
+                    PdaKind::OwnerProgramList => find_owner_program_list_pda(&mint),
+                    PdaKind::BountyVault => find_bounty_vault_pda(&mint),
+                    PdaKind::PayerRecord { wallet: payer } => {
+                        find_payer_record_pda(&mint, &wallet(payer))
+                    }
+                    PdaKind::Metadata => find_metadata_account(&mint),
+                    PdaKind::MasterEdition => find_master_edition_account(&mint),
+                };
+                pda
+            }
+            FuzzKey::Raw(bytes) => Pubkey::new_from_array(bytes),
+        }
+    }
+}
+
+/// An account with fuzzed contents.
+#[derive(Arbitrary, Debug)]
+pub struct FuzzAccount {
+    pub key: FuzzKey,
+    pub owner: FuzzKey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    pub executable: bool,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+}
+
+impl FuzzAccount {
+    pub fn buffer(&self) -> AccountBuffer {
+        let mut account = AccountBuffer::new(
+            self.key.pubkey(),
+            self.owner.pubkey(),
+            self.lamports,
+            &self.data,
+        );
+        account.is_signer = self.is_signer;
+        account.is_writable = self.is_writable;
+        account.executable = self.executable;
+
+        account
+    }
+}
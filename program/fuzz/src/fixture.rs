@@ -0,0 +1,274 @@
+use borsh::BorshSerialize;
+use mpl_migration_validator::{
+    instruction::MigrateAccounts,
+    state::{
+        CollectionInfo, Key as MigrationKey, MigrationState, MigrationStatus, PayerRecord,
+        MIGRATION_STATE_ACCOUNT_LEN,
+    },
+    utils::{
+        find_bounty_vault_pda, find_collection_signer_pda, find_delegate_record_pda,
+        find_migration_state_pda, find_owner_program_list_pda, find_payer_record_pda,
+    },
+    MPL_TOKEN_AUTH_RULES_ID,
+};
+use mpl_token_metadata::{
+    pda::{find_master_edition_account, find_metadata_account, find_token_record_account},
+    state::{
+        Collection, CollectionAuthorityRecord, Data, Key, MasterEditionV2, Metadata, TokenStandard,
+        COLLECTION_AUTHORITY_RECORD_SIZE, MAX_MASTER_EDITION_LEN, MAX_METADATA_LEN,
+    },
+};
+use solana_program::{
+    bpf_loader_upgradeable, instruction::Instruction, native_token::LAMPORTS_PER_SOL,
+    program_option::COption, program_pack::Pack, pubkey, pubkey::Pubkey, system_program, sysvar,
+};
+use spl_token::state::{Account, AccountState, Mint};
+
+use crate::{mint, wallet, AccountBuffer};
+
+const NATIVE_LOADER: Pubkey = pubkey!("NativeLoader1111111111111111111111111111111");
+
+/// Positions of the migrate accounts the program passes to Token Metadata without checking
+/// them itself: the token record and the auth rules program.
+pub const CPI_CHECKED_POSITIONS: [usize; 2] = [11, 15];
+
+/// The accounts of a verified collection item, ready to be migrated by `instruction`.
+///
+/// The item is held by the payer's wallet and the collection parent is migrated last.
+pub struct MigrateFixture {
+    pub accounts: Vec<AccountBuffer>,
+    pub instruction: Instruction,
+}
+
+fn padded(mut data: Vec<u8>, len: usize) -> Vec<u8> {
+    data.resize(len, 0);
+    data
+}
+
+fn metadata(mint: Pubkey, authority: Pubkey, collection: Option<Collection>) -> Vec<u8> {
+    let metadata = Metadata {
+        key: Key::MetadataV1,
+        update_authority: authority,
+        mint,
+        data: Data {
+            name: "Item".to_string(),
+            symbol: "ITEM".to_string(),
+            uri: "https://example.com".to_string(),
+            seller_fee_basis_points: 0,
+            creators: None,
+        },
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection,
+        uses: None,
+        collection_details: None,
+        programmable_config: None,
+    };
+
+    padded(metadata.try_to_vec().unwrap(), MAX_METADATA_LEN)
+}
+
+fn packed<T: Pack>(state: T) -> Vec<u8> {
+    let mut data = vec![0; T::LEN];
+    T::pack(state, &mut data).unwrap();
+    data
+}
+
+impl MigrateFixture {
+    pub fn new() -> Self {
+        let collection_mint = mint(0);
+        let item_mint = mint(1);
+        let authority = wallet(0);
+        let payer = wallet(1);
+        let item_token = Pubkey::new_from_array([0x70; 32]);
+        let rule_set = Pubkey::new_from_array([0x71; 32]);
+
+        let (item_edition, _) = find_master_edition_account(&item_mint);
+        let (collection_signer, _) = find_collection_signer_pda(&collection_mint);
+        let (delegate_record, delegate_record_bump) = find_delegate_record_pda(&collection_mint);
+        let (migration_state, bump) = find_migration_state_pda(&collection_mint);
+        let (payer_record, payer_record_bump) = find_payer_record_pda(&collection_mint, &payer);
+        let (token_record, _) = find_token_record_account(&item_mint, &item_token);
+
+        let state = MigrationState {
+            collection_info: CollectionInfo {
+                authority,
+                mint: collection_mint,
+                rule_set,
+                delegate_record,
+                size: 10,
+                is_sized: false,
+            },
+            status: MigrationStatus {
+                in_progress: true,
+                ..MigrationStatus::default()
+            },
+            bump,
+            delegate_record_bump,
+            ..MigrationState::default()
+        };
+
+        let record = CollectionAuthorityRecord {
+            key: Key::CollectionAuthorityRecord,
+            bump: delegate_record_bump,
+            update_authority: Some(authority),
+        };
+
+        let edition = MasterEditionV2 {
+            key: Key::MasterEditionV2,
+            supply: 0,
+            max_supply: Some(0),
+        };
+
+        let item_mint_data = packed(Mint {
+            mint_authority: COption::Some(item_edition),
+            supply: 1,
+            decimals: 0,
+            is_initialized: true,
+            freeze_authority: COption::Some(item_edition),
+        });
+
+        let item_token_data = packed(Account {
+            mint: item_mint,
+            owner: payer,
+            amount: 1,
+            delegate: COption::None,
+            state: AccountState::Initialized,
+            is_native: COption::None,
+            delegated_amount: 0,
+            close_authority: COption::None,
+        });
+
+        let payer_record_data = PayerRecord {
+            key: MigrationKey::PayerRecord,
+            bump: payer_record_bump,
+        }
+        .try_to_vec()
+        .unwrap();
+
+        let token_metadata = mpl_token_metadata::ID;
+        let program_id = mpl_migration_validator::ID;
+
+        let mut accounts = vec![
+            AccountBuffer::new(
+                find_metadata_account(&item_mint).0,
+                token_metadata,
+                LAMPORTS_PER_SOL,
+                &metadata(
+                    item_mint,
+                    authority,
+                    Some(Collection {
+                        verified: true,
+                        key: collection_mint,
+                    }),
+                ),
+            ),
+            AccountBuffer::new(
+                item_edition,
+                token_metadata,
+                LAMPORTS_PER_SOL,
+                &padded(edition.try_to_vec().unwrap(), MAX_MASTER_EDITION_LEN),
+            ),
+            AccountBuffer::new(
+                item_token,
+                spl_token::ID,
+                LAMPORTS_PER_SOL,
+                &item_token_data,
+            ),
+            AccountBuffer::new(item_mint, spl_token::ID, LAMPORTS_PER_SOL, &item_mint_data),
+            // The payer also owns the item.
+            AccountBuffer::new(payer, system_program::ID, 10 * LAMPORTS_PER_SOL, &[]),
+            AccountBuffer::new(collection_signer, system_program::ID, 0, &[]),
+            AccountBuffer::new(
+                find_metadata_account(&collection_mint).0,
+                token_metadata,
+                LAMPORTS_PER_SOL,
+                &metadata(collection_mint, authority, None),
+            ),
+            AccountBuffer::new(
+                delegate_record,
+                token_metadata,
+                LAMPORTS_PER_SOL,
+                &padded(
+                    record.try_to_vec().unwrap(),
+                    COLLECTION_AUTHORITY_RECORD_SIZE,
+                ),
+            ),
+            AccountBuffer::new(token_record, system_program::ID, 0, &[]),
+            AccountBuffer::new(rule_set, MPL_TOKEN_AUTH_RULES_ID, LAMPORTS_PER_SOL, &[0; 8]),
+            AccountBuffer::new(
+                migration_state,
+                program_id,
+                LAMPORTS_PER_SOL,
+                &padded(state.try_to_vec().unwrap(), MIGRATION_STATE_ACCOUNT_LEN),
+            ),
+            AccountBuffer::new(
+                payer_record,
+                program_id,
+                LAMPORTS_PER_SOL,
+                &payer_record_data,
+            ),
+            AccountBuffer::new(
+                find_owner_program_list_pda(&collection_mint).0,
+                system_program::ID,
+                0,
+                &[],
+            ),
+            AccountBuffer::new(
+                find_bounty_vault_pda(&collection_mint).0,
+                system_program::ID,
+                0,
+                &[],
+            ),
+            AccountBuffer::new(sysvar::instructions::ID, sysvar::ID, LAMPORTS_PER_SOL, &[]),
+            // Programs. The token owner program has no buffer, so the program id is
+            // passed in its place.
+            AccountBuffer::new(system_program::ID, NATIVE_LOADER, 1, &[]).executable(),
+            AccountBuffer::new(spl_token::ID, bpf_loader_upgradeable::ID, 1, &[]).executable(),
+            AccountBuffer::new(token_metadata, bpf_loader_upgradeable::ID, 1, &[]).executable(),
+            AccountBuffer::new(MPL_TOKEN_AUTH_RULES_ID, bpf_loader_upgradeable::ID, 1, &[])
+                .executable(),
+            AccountBuffer::new(program_id, bpf_loader_upgradeable::ID, 1, &[]).executable(),
+        ];
+
+        let instruction =
+            MigrateAccounts::new(payer, item_mint, item_token, payer, collection_mint)
+                .no_buffer()
+                .auth_rule_set(rule_set)
+                .instruction()
+                .unwrap();
+
+        // The runtime marks an account passed more than once as signer or writable if
+        // any of its positions is.
+        for account in &mut accounts {
+            for meta in &instruction.accounts {
+                if meta.pubkey == account.key() {
+                    account.is_signer |= meta.is_signer;
+                    account.is_writable |= meta.is_writable;
+                }
+            }
+        }
+
+        Self {
+            accounts,
+            instruction,
+        }
+    }
+
+    /// The keys of the instruction's accounts, in order.
+    pub fn keys(&self) -> Vec<Pubkey> {
+        self.instruction
+            .accounts
+            .iter()
+            .map(|meta| meta.pubkey)
+            .collect()
+    }
+}
+
+impl Default for MigrateFixture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
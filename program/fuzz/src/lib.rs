@@ -0,0 +1,42 @@
+//! Harness shared by the fuzz targets.
+//!
+//! Processors run natively against accounts laid out like the runtime's serialized input.
+//! Syscalls are stubbed with a settable clock, and CPIs into the System Program are emulated
+//! so accounts are funded and created. Other CPIs, such as into Token Metadata, succeed
+//! without effect.
+
+pub mod accounts;
+pub mod fixture;
+pub mod stubs;
+
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+use mpl_migration_validator::processor::Processor;
+
+pub use accounts::*;
+pub use stubs::install_stubs;
+
+/// Runs an instruction of the program with the accounts at `keys`, in order.
+///
+/// Every key must be one of `accounts`. A key listed more than once is passed as the same
+/// account, as the runtime does.
+pub fn process_instruction(
+    accounts: &mut [AccountBuffer],
+    keys: &[Pubkey],
+    data: &[u8],
+) -> ProgramResult {
+    let infos: Vec<AccountInfo> = accounts.iter_mut().map(AccountBuffer::info).collect();
+
+    let account_infos: Vec<AccountInfo> = keys
+        .iter()
+        .map(|key| {
+            infos
+                .iter()
+                .find(|info| info.key == key)
+                .expect("missing account")
+                .clone()
+        })
+        .collect();
+
+    Processor::process_instruction(&mpl_migration_validator::ID, &account_infos, data)
+}
@@ -0,0 +1,187 @@
+use std::sync::{
+    atomic::{AtomicI64, Ordering},
+    Once,
+};
+
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::{ProgramResult, SUCCESS},
+    instruction::Instruction,
+    program_error::ProgramError,
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::{SystemError, SystemInstruction},
+    system_program,
+};
+
+/// Slot reported by the stubbed clock.
+pub const SLOT: u64 = 1_000;
+
+static UNIX_TIMESTAMP: AtomicI64 = AtomicI64::new(1_700_000_000);
+
+/// Sets the time reported by the stubbed clock.
+pub fn set_unix_timestamp(timestamp: i64) {
+    UNIX_TIMESTAMP.store(timestamp, Ordering::Relaxed);
+}
+
+/// Installs the syscall stubs. Safe to call on every run.
+pub fn install_stubs() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        set_syscall_stubs(Box::new(FuzzSyscallStubs));
+    });
+}
+
+struct FuzzSyscallStubs;
+
+impl SyscallStubs for FuzzSyscallStubs {
+    // Logging every failed check slows fuzzing down to a crawl.
+    fn sol_log(&self, _message: &str) {}
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        if instruction.program_id == system_program::ID {
+            return invoke_system_program(instruction, account_infos, signers_seeds);
+        }
+
+        Ok(())
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        let clock = Clock {
+            slot: SLOT,
+            unix_timestamp: UNIX_TIMESTAMP.load(Ordering::Relaxed),
+            ..Clock::default()
+        };
+        unsafe { *(var_addr as *mut Clock) = clock };
+
+        SUCCESS
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+
+        SUCCESS
+    }
+}
+
+fn system_error(error: SystemError) -> ProgramError {
+    ProgramError::Custom(error as u32)
+}
+
+// The subset of the System Program the migration validator calls.
+fn invoke_system_program(
+    instruction: &Instruction,
+    account_infos: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let account = |index: usize| find_account(instruction, account_infos, index);
+
+    match bincode::deserialize(&instruction.data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?
+    {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => {
+            let to = account(1)?;
+            if to.lamports() > 0 {
+                return Err(system_error(SystemError::AccountAlreadyInUse));
+            }
+            transfer(account(0)?, to, lamports, signers_seeds)?;
+            allocate(to, space, &owner, signers_seeds)
+        }
+        SystemInstruction::Transfer { lamports } => {
+            transfer(account(0)?, account(1)?, lamports, signers_seeds)
+        }
+        SystemInstruction::Allocate { space } => {
+            allocate(account(0)?, space, &system_program::ID, signers_seeds)
+        }
+        SystemInstruction::Assign { owner } => {
+            let info = account(0)?;
+            assert_signer(info, signers_seeds)?;
+            info.assign(&owner);
+            Ok(())
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn find_account<'a, 'b>(
+    instruction: &Instruction,
+    account_infos: &'b [AccountInfo<'a>],
+    index: usize,
+) -> Result<&'b AccountInfo<'a>, ProgramError> {
+    let key = instruction
+        .accounts
+        .get(index)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?
+        .pubkey;
+
+    account_infos
+        .iter()
+        .find(|info| info.key == &key)
+        .ok_or(ProgramError::NotEnoughAccountKeys)
+}
+
+// Accounts sign either in the transaction or as PDAs of the calling program.
+fn assert_signer(info: &AccountInfo, signers_seeds: &[&[&[u8]]]) -> ProgramResult {
+    let signed_by_seeds = signers_seeds.iter().any(|seeds| {
+        Pubkey::create_program_address(seeds, &mpl_migration_validator::ID)
+            .map_or(false, |pda| pda == *info.key)
+    });
+
+    if info.is_signer || signed_by_seeds {
+        Ok(())
+    } else {
+        Err(ProgramError::MissingRequiredSignature)
+    }
+}
+
+fn transfer(
+    from: &AccountInfo,
+    to: &AccountInfo,
+    lamports: u64,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    assert_signer(from, signers_seeds)?;
+    if !from.data_is_empty() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let from_lamports = from
+        .lamports()
+        .checked_sub(lamports)
+        .ok_or_else(|| system_error(SystemError::ResultWithNegativeLamports))?;
+    **from.try_borrow_mut_lamports()? = from_lamports;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+fn allocate(
+    info: &AccountInfo,
+    space: u64,
+    owner: &Pubkey,
+    signers_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    assert_signer(info, signers_seeds)?;
+    if !info.data_is_empty() || info.owner != &system_program::ID {
+        return Err(system_error(SystemError::AccountAlreadyInUse));
+    }
+
+    info.realloc(space as usize, true)?;
+    info.assign(owner);
+
+    Ok(())
+}
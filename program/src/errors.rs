@@ -227,6 +227,122 @@ pub enum MigrationError {
     /// 42 0x2A
     #[error("This instruction has been deprecated")]
     DeprecatedInstruction,
+
+    /// 43 0x2B
+    #[error("Incorrect program owner for edition account")]
+    IncorrectEditionProgramOwner,
+
+    /// 44 0x2C
+    #[error("Item edition is not a master edition")]
+    NotMasterEdition,
+
+    /// 45 0x2D
+    #[error("Owner program list account derivation is incorrect")]
+    InvalidOwnerProgramListDerivation,
+
+    /// 46 0x2E
+    #[error("Incorrect program owner for owner program list account")]
+    IncorrectOwnerProgramListProgramOwner,
+
+    /// 47 0x2F
+    #[error("Owner program list did not deserialize correctly")]
+    InvalidOwnerProgramList,
+
+    /// 48 0x30
+    #[error("Token owner program is not allowed to migrate by the owner program list")]
+    TokenOwnerProgramNotAllowed,
+
+    /// 49 0x31
+    #[error("Items with an SPL token delegate cannot be migrated")]
+    TokenDelegateNotAllowed,
+
+    /// 50 0x32
+    #[error("Items with a frozen token account cannot be migrated")]
+    FrozenTokenNotAllowed,
+
+    /// 51 0x33
+    #[error("SPL token delegate must be delegated the full token amount")]
+    InvalidTokenDelegate,
+
+    /// 52 0x34
+    #[error("Collection parent must be migrated with MigrateCollectionParent")]
+    UnexpectedCollectionParent,
+
+    /// 53 0x35
+    #[error("Item is not the collection parent")]
+    NotCollectionParent,
+
+    /// 54 0x36
+    #[error("Collection parent must be migrated before its items")]
+    CollectionParentNotMigrated,
+
+    /// 55 0x37
+    #[error("Collection parent has already been migrated")]
+    CollectionParentAlreadyMigrated,
+
+    /// 56 0x38
+    #[error("Collection items must be migrated before the collection parent")]
+    CollectionItemsNotMigrated,
+
+    /// 57 0x39
+    #[error("Collection size does not match the size of the sized collection")]
+    CollectionSizeMismatch,
+
+    /// 58 0x3A
+    #[error("Migration state account must be upgraded to the current version")]
+    MigrationStateNotUpgraded,
+
+    /// 59 0x3B
+    #[error("Account is not a migration state account")]
+    InvalidMigrationStateKey,
+
+    /// 60 0x3C
+    #[error("Account is not a program signer account")]
+    InvalidProgramSignerKey,
+
+    /// 61 0x3D
+    #[error("Account is not an owner program list account")]
+    InvalidOwnerProgramListKey,
+
+    /// 62 0x3E
+    #[error("Bounty vault account derivation is incorrect")]
+    InvalidBountyVaultDerivation,
+
+    /// 63 0x3F
+    #[error("Incorrect program owner for bounty vault account")]
+    IncorrectBountyVaultProgramOwner,
+
+    /// 64 0x40
+    #[error("Migration has not completed yet")]
+    MigrationNotComplete,
+
+    /// 65 0x41
+    #[error("Payer record account derivation is incorrect")]
+    InvalidPayerRecordDerivation,
+
+    /// 66 0x42
+    #[error("Incorrect program owner for payer record account")]
+    IncorrectPayerRecordProgramOwner,
+
+    /// 67 0x43
+    #[error("Payer record account has an incorrect key")]
+    InvalidPayerRecordKey,
+
+    /// 68 0x44
+    #[error("Invalid payer record account")]
+    InvalidPayerRecord,
+
+    /// 69 0x45
+    #[error("Signer is not the pending authority")]
+    InvalidPendingAuthority,
+
+    /// 70 0x46
+    #[error("Pending authority handoff has expired")]
+    PendingAuthorityExpired,
+
+    /// 71 0x47
+    #[error("Collection has no items to migrate")]
+    EmptyCollection,
 }
 
 // Migration Error Impls
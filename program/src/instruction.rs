@@ -4,13 +4,22 @@ use mpl_token_metadata::pda::{
 };
 use shank::ShankInstruction;
 use solana_program::{
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
 };
 
 use crate::{
-    state::{UnlockMethod, SPL_TOKEN_ID},
-    utils::{find_delegate_record_pda, find_migration_state_pda},
+    errors::MigrationError,
+    state::{
+        CollectionParentOrder, DelegateHandling, ListType, UnlockMethod, UpdateAuthorityMode,
+        SPL_TOKEN_ID,
+    },
+    utils::{
+        find_bounty_vault_pda, find_collection_signer_pda, find_delegate_record_pda,
+        find_legacy_delegate_record_pda, find_migration_state_pda, find_owner_program_list_pda,
+        find_payer_record_pda,
+    },
     MPL_TOKEN_AUTH_RULES_ID, PROGRAM_SIGNER,
 };
 
@@ -28,6 +37,26 @@ pub struct UpdateArgs {
     pub rule_set: Option<Pubkey>,
     pub collection_size: Option<u32>,
     pub new_update_authority: Option<Pubkey>,
+    pub delegate_handling: Option<DelegateHandling>,
+    pub collection_parent_order: Option<CollectionParentOrder>,
+    pub update_authority_mode: Option<UpdateAuthorityMode>,
+    /// Let a collection parent ordered last migrate before every item has been migrated.
+    /// Unlike the other options, this can be changed while the migration is in progress.
+    pub release_collection_parent: Option<bool>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetOwnerProgramListArgs {
+    pub list_type: ListType,
+    pub programs: Vec<Pubkey>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct FundBountyArgs {
+    pub lamports: u64,
+    pub reward: Option<u64>,
 }
 
 #[derive(Debug, Clone, ShankInstruction, BorshSerialize, BorshDeserialize)]
@@ -50,6 +79,7 @@ pub enum MigrationInstruction {
 
     #[account(0, writable, signer, name="authority", desc = "The collection authority")]
     #[account(1, writable, name="migration_state", desc = "The migration state account")]
+    #[account(2, name="collection_metadata", desc = "The metadata account of the collection parent NFT")]
     Update(UpdateArgs),
 
     /// Permissionless handler to initialize the program signer
@@ -60,10 +90,10 @@ pub enum MigrationInstruction {
 
     /// Start a migration if it is eligible.
     #[account(0, writable, signer, name="payer", desc="Paying account for initiate migration")]
-    #[account(1, signer, name="authority", desc = "The collection authority")]
+    #[account(1, writable, signer, name="authority", desc = "The collection authority")]
     #[account(2, name="collection_mint", desc = "The mint account of the collection parent NFT")]
     #[account(3, name="collection_metadata", desc = "The metadata account of the collection parent NFT")]
-    #[account(4, name="program_signer", desc="Program signer PDA")]
+    #[account(4, name="program_signer", desc="Program signer PDA for the collection")]
     #[account(5, writable, name="delegate_record", desc = "The collection delegate record of for the program signer and the collection")]
     #[account(6, writable, name="migration_state", desc = "The migration state account")]
     #[account(7, name="spl_token_program", desc="Token Program")]
@@ -71,19 +101,19 @@ pub enum MigrationInstruction {
     #[account(9, name="token_metadata_program", desc = "Token Metadata program for the CPI call")]
     Start,
 
-    /// Migrate an asset.    
+    /// Migrate an asset.
     #[account(0, writable, name="item_metadata", desc="Metadata account")]
     #[account(1, writable, name="item_edition", desc="Edition account")]
     #[account(2, writable, name="item_token", desc="Token account")]
     #[account(3, name="token_owner", desc="Token owner")]
-    #[account(4, name="token_owner_program,", desc="Program that owns the token owner")]
-    #[account(5, name="token_owner_program_buffer,", desc="Executable buffer account of the program owner")]
+    #[account(4, name="token_owner_program", desc="Program that owns the token owner")]
+    #[account(5, name="token_owner_program_buffer", desc="Executable buffer account of the program owner")]
     #[account(6, name="item_mint", desc="Mint account")]
     #[account(7, writable, signer, name="payer", desc="Pays for migration costs")]
-    #[account(8, name="program_signer", desc="Program signer PDA")]
+    #[account(8, name="program_signer", desc="Program signer PDA for the collection")]
     #[account(9, name="collection_metadata", desc="Collection metadata account")]
-    #[account(10, name="delegate_record", desc="Update authority or delegate")]
-    #[account(11, writable, name="token_record", desc="Update authority or delegate")]
+    #[account(10, name="delegate_record", desc="Collection delegate record for the program signer")]
+    #[account(11, writable, name="token_record", desc="Token record account created by the migration")]
     #[account(12, name="system_program", desc="System program")]
     #[account(13, name="sysvar_instructions", desc="Instruction sysvar account")]
     #[account(14, name="spl_token_program", desc="Token Program")]
@@ -91,8 +121,94 @@ pub enum MigrationInstruction {
     #[account(16, optional, name="authorization_rules", desc="Token Authorization Rules account")]
     #[account(17, writable, name="migration_state", desc = "The migration state account")]
     #[account(18, name="token_metadata_program", desc = "Token Metadata program for the CPI call")]
+    #[account(19, name="owner_program_list", desc = "The owner program list account")]
+    #[account(20, writable, name="bounty_vault", desc = "The bounty vault paying the payer for the migration")]
+    #[account(21, writable, name="payer_record", desc = "The record marking the payer as having paid for a migration")]
     #[default_optional_accounts]
-    Migrate,
+    Migrate(MigrateItemArgs),
+
+    /// Set the allow or deny list of token owner programs checked during migration.
+    #[account(0, writable, signer, name="payer", desc="Pays for the owner program list account")]
+    #[account(1, signer, name="authority", desc = "The collection authority")]
+    #[account(2, writable, name="migration_state", desc = "The migration state account")]
+    #[account(3, writable, name="owner_program_list", desc = "The owner program list account")]
+    #[account(4, name="system_program", desc = "System program")]
+    SetOwnerProgramList(SetOwnerProgramListArgs),
+
+    /// Migrate the collection parent NFT, before or after its items as configured.
+    #[account(0, writable, name="item_metadata", desc="Collection metadata account")]
+    #[account(1, writable, name="item_edition", desc="Collection edition account")]
+    #[account(2, writable, name="item_token", desc="Collection token account")]
+    #[account(3, name="token_owner", desc="Token owner")]
+    #[account(4, name="token_owner_program", desc="Program that owns the token owner")]
+    #[account(5, name="token_owner_program_buffer", desc="Executable buffer account of the program owner")]
+    #[account(6, name="item_mint", desc="Collection mint account")]
+    #[account(7, writable, signer, name="payer", desc="Pays for migration costs")]
+    #[account(8, name="program_signer", desc="Program signer PDA for the collection")]
+    #[account(9, name="collection_metadata", desc="Collection metadata account")]
+    #[account(10, name="delegate_record", desc="Collection delegate record for the program signer")]
+    #[account(11, writable, name="token_record", desc="Token record account created by the migration")]
+    #[account(12, name="system_program", desc="System program")]
+    #[account(13, name="sysvar_instructions", desc="Instruction sysvar account")]
+    #[account(14, name="spl_token_program", desc="Token Program")]
+    #[account(15, optional, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(16, optional, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(17, writable, name="migration_state", desc = "The migration state account")]
+    #[account(18, name="token_metadata_program", desc = "Token Metadata program for the CPI call")]
+    #[account(19, name="owner_program_list", desc = "The owner program list account")]
+    #[account(20, writable, name="bounty_vault", desc = "The bounty vault paying the payer for the migration")]
+    #[account(21, writable, name="payer_record", desc = "The record marking the payer as having paid for a migration")]
+    #[default_optional_accounts]
+    MigrateCollectionParent(MigrateItemArgs),
+
+    /// Permissionless handler to reallocate a migration state account and convert it to the current layout.
+    #[account(0, writable, signer, name="payer", desc="Pays for the reallocated account space")]
+    #[account(1, writable, name="migration_state", desc = "The migration state account")]
+    #[account(2, name="system_program", desc = "System program")]
+    UpgradeState,
+
+    /// Fund the bounty vault and optionally set the reward paid per migration.
+    #[account(0, writable, signer, name="authority", desc = "The collection authority")]
+    #[account(1, writable, name="migration_state", desc = "The migration state account")]
+    #[account(2, writable, name="bounty_vault", desc = "The bounty vault account")]
+    #[account(3, name="system_program", desc = "System program")]
+    FundBounty(FundBountyArgs),
+
+    /// Return the leftover bounty to the authority once the migration has completed, or
+    /// once no item has been migrated for `BOUNTY_RECLAIM_PERIOD`.
+    #[account(0, writable, signer, name="authority", desc = "The collection authority")]
+    #[account(1, name="migration_state", desc = "The migration state account")]
+    #[account(2, writable, name="bounty_vault", desc = "The bounty vault account")]
+    #[account(3, name="system_program", desc = "System program")]
+    #[account(4, name="collection_metadata", desc = "The metadata account of the collection parent NFT")]
+    FinalizeBounty,
+
+    /// Accept the authority handoff proposed through `Update`.
+    #[account(0, signer, name="new_authority", desc = "The pending collection authority")]
+    #[account(1, writable, name="migration_state", desc = "The migration state account")]
+    AcceptAuthority,
+
+    /// Permissionlessly set the stored authority to the collection's current update
+    /// authority. The delegate record is re-created if the update authority also signs.
+    #[account(0, writable, signer, name="payer", desc="Paying account for the sync")]
+    #[account(1, writable, name="authority", desc = "The collection update authority, signing to re-create the delegate record")]
+    #[account(2, name="collection_mint", desc = "The mint account of the collection parent NFT")]
+    #[account(3, name="collection_metadata", desc = "The metadata account of the collection parent NFT")]
+    #[account(4, name="program_signer", desc="Program signer PDA for the collection")]
+    #[account(5, writable, name="delegate_record", desc = "The collection delegate record of for the program signer and the collection")]
+    #[account(6, writable, name="migration_state", desc = "The migration state account")]
+    #[account(7, name="spl_token_program", desc="Token Program")]
+    #[account(8, name="system_program", desc = "System program")]
+    #[account(9, name="token_metadata_program", desc = "Token Metadata program for the CPI call")]
+    SyncAuthority,
+
+    /// Permissionlessly close a payer record once every item has been migrated, returning
+    /// its rent to the payer.
+    #[account(0, writable, name="payer", desc="The payer of the payer record, receiving its rent")]
+    #[account(1, name="migration_state", desc = "The migration state account")]
+    #[account(2, writable, name="payer_record", desc = "The payer record account")]
+    #[account(3, name="collection_metadata", desc = "The metadata account of the collection parent NFT")]
+    ClosePayerRecord,
 }
 
 pub fn initialize(
@@ -119,13 +235,62 @@ pub fn initialize(
     }
 }
 
-pub fn update(authority: Pubkey, migration_state: Pubkey, args: UpdateArgs) -> Instruction {
+pub fn update(authority: Pubkey, collection_mint: Pubkey, args: UpdateArgs) -> Instruction {
+    let (collection_metadata, _) = find_metadata_account(&collection_mint);
+    let (migration_state, _) = find_migration_state_pda(&collection_mint);
+
     let data = MigrationInstruction::Update(args).try_to_vec().unwrap();
     Instruction {
         program_id: crate::ID,
         accounts: vec![
             AccountMeta::new(authority, true),
             AccountMeta::new(migration_state, false),
+            AccountMeta::new_readonly(collection_metadata, false),
+        ],
+        data,
+    }
+}
+
+pub fn accept_authority(new_authority: Pubkey, migration_state: Pubkey) -> Instruction {
+    let data = MigrationInstruction::AcceptAuthority.try_to_vec().unwrap();
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(new_authority, true),
+            AccountMeta::new(migration_state, false),
+        ],
+        data,
+    }
+}
+
+/// The authority is only needed, as a signer, to re-create the delegate record.
+pub fn sync_authority(
+    payer: Pubkey,
+    authority: Option<Pubkey>,
+    collection_mint: Pubkey,
+) -> Instruction {
+    let (collection_metadata, _) = find_metadata_account(&collection_mint);
+    let (program_signer, _) = find_collection_signer_pda(&collection_mint);
+    let (delegate_record, _) = find_delegate_record_pda(&collection_mint);
+    let (migration_state, _) = find_migration_state_pda(&collection_mint);
+
+    let data = MigrationInstruction::SyncAuthority.try_to_vec().unwrap();
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            match authority {
+                Some(authority) => AccountMeta::new(authority, true),
+                None => AccountMeta::new_readonly(crate::ID, false),
+            },
+            AccountMeta::new_readonly(collection_mint, false),
+            AccountMeta::new_readonly(collection_metadata, false),
+            AccountMeta::new_readonly(program_signer, false),
+            AccountMeta::new(delegate_record, false),
+            AccountMeta::new(migration_state, false),
+            AccountMeta::new_readonly(SPL_TOKEN_ID, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
         ],
         data,
     }
@@ -159,6 +324,7 @@ pub fn init_signer(payer: Pubkey) -> Instruction {
 
 pub fn start(payer: Pubkey, authority: Pubkey, collection_mint: Pubkey) -> Instruction {
     let (collection_metadata, _) = find_metadata_account(&collection_mint);
+    let (program_signer, _) = find_collection_signer_pda(&collection_mint);
     let (delegate_record, _) = find_delegate_record_pda(&collection_mint);
     let (migration_state, _) = find_migration_state_pda(&collection_mint);
 
@@ -170,7 +336,7 @@ pub fn start(payer: Pubkey, authority: Pubkey, collection_mint: Pubkey) -> Instr
             AccountMeta::new(authority, true),
             AccountMeta::new_readonly(collection_mint, false),
             AccountMeta::new_readonly(collection_metadata, false),
-            AccountMeta::new_readonly(PROGRAM_SIGNER, false),
+            AccountMeta::new_readonly(program_signer, false),
             AccountMeta::new(delegate_record, false),
             AccountMeta::new(migration_state, false),
             AccountMeta::new_readonly(SPL_TOKEN_ID, false),
@@ -181,6 +347,223 @@ pub fn start(payer: Pubkey, authority: Pubkey, collection_mint: Pubkey) -> Instr
     }
 }
 
+/// Bumps of the item's Token Metadata PDAs and of the payer record, so the program does
+/// not have to search for them. A bump of zero is searched for. A new payer record is
+/// always created with the canonical bump.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct MigrateItemArgs {
+    pub metadata_bump: u8,
+    pub edition_bump: u8,
+    pub payer_record_bump: u8,
+}
+
+/// The executable buffer of the token owner program, passed to the migrate instructions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenOwnerProgramBuffer {
+    /// Not picked yet, so the instruction cannot be built.
+    Unset,
+    /// The token owner program is not deployed by the upgradeable loader. The program
+    /// expects its own id in place of the buffer.
+    None,
+    /// The programdata account of an upgradeable token owner program.
+    Buffer(Pubkey),
+}
+
+impl From<Option<Pubkey>> for TokenOwnerProgramBuffer {
+    fn from(buffer: Option<Pubkey>) -> Self {
+        match buffer {
+            Some(buffer) => Self::Buffer(buffer),
+            None => Self::None,
+        }
+    }
+}
+
+/// Accounts of the `Migrate` and `MigrateCollectionParent` instructions.
+///
+/// Only the accounts picked by the caller are stored; every PDA is derived
+/// from the mints and the payer when the instruction is built.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrateAccounts {
+    pub payer: Pubkey,
+    pub item_mint: Pubkey,
+    pub item_token: Pubkey,
+    pub token_owner: Pubkey,
+    pub token_owner_program: Pubkey,
+    pub token_owner_program_buffer: TokenOwnerProgramBuffer,
+    pub collection_mint: Pubkey,
+    pub auth_rule_set: Pubkey,
+    /// Whether the collection delegated to the global `PROGRAM_SIGNER`, as
+    /// migrations started before collections had their own signer did.
+    pub legacy_signer: bool,
+}
+
+impl MigrateAccounts {
+    /// Accounts for an item held by a wallet, with no auth rule set.
+    ///
+    /// The token owner program buffer is left unset and must be picked with
+    /// `no_buffer`, `token_owner_program_buffer` or `token_owner_program`.
+    pub fn new(
+        payer: Pubkey,
+        item_mint: Pubkey,
+        item_token: Pubkey,
+        token_owner: Pubkey,
+        collection_mint: Pubkey,
+    ) -> Self {
+        Self {
+            payer,
+            item_mint,
+            item_token,
+            token_owner,
+            token_owner_program: solana_program::system_program::ID,
+            token_owner_program_buffer: TokenOwnerProgramBuffer::Unset,
+            collection_mint,
+            auth_rule_set: Pubkey::default(),
+            legacy_signer: false,
+        }
+    }
+
+    /// Sets the program owning the token owner account and picks its buffer.
+    ///
+    /// `loader` is the owner of the program account and `program_data` its data. When
+    /// the program is deployed by the upgradeable loader, its programdata address is
+    /// used as the token owner program buffer, and data that does not decode as an
+    /// upgradeable program is refused.
+    pub fn token_owner_program(
+        mut self,
+        program: Pubkey,
+        loader: &Pubkey,
+        program_data: &[u8],
+    ) -> Result<Self, MigrationError> {
+        self.token_owner_program = program;
+        self.token_owner_program_buffer = if loader == &bpf_loader_upgradeable::ID {
+            match bincode::deserialize(program_data) {
+                Ok(UpgradeableLoaderState::Program {
+                    programdata_address,
+                }) => TokenOwnerProgramBuffer::Buffer(programdata_address),
+                _ => return Err(MigrationError::IncorrectTokenOwnerProgramBuffer),
+            }
+        } else {
+            TokenOwnerProgramBuffer::None
+        };
+        Ok(self)
+    }
+
+    pub fn token_owner_program_buffer(mut self, buffer: Pubkey) -> Self {
+        self.token_owner_program_buffer = TokenOwnerProgramBuffer::Buffer(buffer);
+        self
+    }
+
+    /// Picks no buffer, for token owner programs not deployed by the upgradeable loader.
+    pub fn no_buffer(mut self) -> Self {
+        self.token_owner_program_buffer = TokenOwnerProgramBuffer::None;
+        self
+    }
+
+    pub fn auth_rule_set(mut self, auth_rule_set: Pubkey) -> Self {
+        self.auth_rule_set = auth_rule_set;
+        self
+    }
+
+    /// Uses the global `PROGRAM_SIGNER` and its delegate record, for a migration
+    /// started before collections had their own signer.
+    pub fn legacy_signer(mut self) -> Self {
+        self.legacy_signer = true;
+        self
+    }
+
+    /// Fails if the token owner program buffer has not been picked.
+    pub fn to_account_metas(&self) -> Result<Vec<AccountMeta>, MigrationError> {
+        let (item_metadata, _) = find_metadata_account(&self.item_mint);
+        let (item_edition, _) = find_master_edition_account(&self.item_mint);
+        let (collection_metadata, _) = find_metadata_account(&self.collection_mint);
+        let (program_signer, delegate_record) = if self.legacy_signer {
+            let (delegate_record, _) = find_legacy_delegate_record_pda(&self.collection_mint);
+            (PROGRAM_SIGNER, delegate_record)
+        } else {
+            let (program_signer, _) = find_collection_signer_pda(&self.collection_mint);
+            let (delegate_record, _) = find_delegate_record_pda(&self.collection_mint);
+            (program_signer, delegate_record)
+        };
+        let (token_record, _) = find_token_record_account(&self.item_mint, &self.item_token);
+        let (migration_state, _) = find_migration_state_pda(&self.collection_mint);
+        let (owner_program_list, _) = find_owner_program_list_pda(&self.collection_mint);
+        let (bounty_vault, _) = find_bounty_vault_pda(&self.collection_mint);
+        let (payer_record, _) = find_payer_record_pda(&self.collection_mint, &self.payer);
+
+        // The program expects its own id when the token owner program has no buffer.
+        let token_owner_program_buffer = match self.token_owner_program_buffer {
+            TokenOwnerProgramBuffer::Unset => {
+                return Err(MigrationError::IncorrectTokenOwnerProgramBuffer)
+            }
+            TokenOwnerProgramBuffer::None => crate::ID,
+            TokenOwnerProgramBuffer::Buffer(buffer) => buffer,
+        };
+
+        Ok(vec![
+            AccountMeta::new(item_metadata, false),
+            AccountMeta::new(item_edition, false),
+            AccountMeta::new(self.item_token, false),
+            AccountMeta::new_readonly(self.token_owner, false),
+            AccountMeta::new_readonly(self.token_owner_program, false),
+            AccountMeta::new_readonly(token_owner_program_buffer, false),
+            AccountMeta::new_readonly(self.item_mint, false),
+            AccountMeta::new(self.payer, true),
+            AccountMeta::new_readonly(program_signer, false),
+            AccountMeta::new_readonly(collection_metadata, false),
+            AccountMeta::new_readonly(delegate_record, false),
+            AccountMeta::new(token_record, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(solana_program::sysvar::instructions::ID, false),
+            AccountMeta::new_readonly(SPL_TOKEN_ID, false),
+            AccountMeta::new_readonly(MPL_TOKEN_AUTH_RULES_ID, false),
+            AccountMeta::new_readonly(self.auth_rule_set, false),
+            AccountMeta::new(migration_state, false),
+            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            AccountMeta::new_readonly(owner_program_list, false),
+            AccountMeta::new(bounty_vault, false),
+            AccountMeta::new(payer_record, false),
+        ])
+    }
+
+    /// Instruction data with the bumps of the item's metadata and edition and of the
+    /// payer record.
+    pub fn args(&self) -> MigrateItemArgs {
+        let (_, metadata_bump) = find_metadata_account(&self.item_mint);
+        let (_, edition_bump) = find_master_edition_account(&self.item_mint);
+        let (_, payer_record_bump) = find_payer_record_pda(&self.collection_mint, &self.payer);
+
+        MigrateItemArgs {
+            metadata_bump,
+            edition_bump,
+            payer_record_bump,
+        }
+    }
+
+    /// Builds a `Migrate` instruction.
+    pub fn instruction(&self) -> Result<Instruction, MigrationError> {
+        Ok(Instruction {
+            program_id: crate::ID,
+            accounts: self.to_account_metas()?,
+            data: MigrationInstruction::Migrate(self.args())
+                .try_to_vec()
+                .unwrap(),
+        })
+    }
+
+    /// Builds a `MigrateCollectionParent` instruction; the item mint must be
+    /// the collection mint.
+    pub fn collection_parent_instruction(&self) -> Result<Instruction, MigrationError> {
+        Ok(Instruction {
+            program_id: crate::ID,
+            accounts: self.to_account_metas()?,
+            data: MigrationInstruction::MigrateCollectionParent(self.args())
+                .try_to_vec()
+                .unwrap(),
+        })
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn migrate_item(
     payer: Pubkey,
@@ -192,36 +575,138 @@ pub fn migrate_item(
     collection_mint: Pubkey,
     auth_rule_set: Pubkey,
 ) -> Instruction {
-    let (item_metadata, _) = find_metadata_account(&item_mint);
-    let (item_edition, _) = find_master_edition_account(&item_mint);
-    let (collection_metadata, _) = find_metadata_account(&collection_mint);
-    let (delegate_record, _) = find_delegate_record_pda(&collection_mint);
-    let (token_record, _) = find_token_record_account(&item_mint, &item_token);
+    MigrateAccounts {
+        payer,
+        item_mint,
+        item_token,
+        token_owner,
+        token_owner_program,
+        token_owner_program_buffer: token_owner_program_buffer.into(),
+        collection_mint,
+        auth_rule_set,
+        legacy_signer: false,
+    }
+    .instruction()
+    .unwrap()
+}
+
+pub fn migrate_collection_parent(
+    payer: Pubkey,
+    collection_mint: Pubkey,
+    collection_token: Pubkey,
+    token_owner: Pubkey,
+    token_owner_program: Pubkey,
+    token_owner_program_buffer: Option<Pubkey>,
+    auth_rule_set: Pubkey,
+) -> Instruction {
+    MigrateAccounts {
+        payer,
+        item_mint: collection_mint,
+        item_token: collection_token,
+        token_owner,
+        token_owner_program,
+        token_owner_program_buffer: token_owner_program_buffer.into(),
+        collection_mint,
+        auth_rule_set,
+        legacy_signer: false,
+    }
+    .collection_parent_instruction()
+    .unwrap()
+}
+
+pub fn set_owner_program_list(
+    payer: Pubkey,
+    authority: Pubkey,
+    collection_mint: Pubkey,
+    args: SetOwnerProgramListArgs,
+) -> Instruction {
     let (migration_state, _) = find_migration_state_pda(&collection_mint);
+    let (owner_program_list, _) = find_owner_program_list_pda(&collection_mint);
 
-    let data = MigrationInstruction::Migrate.try_to_vec().unwrap();
+    let data = MigrationInstruction::SetOwnerProgramList(args)
+        .try_to_vec()
+        .unwrap();
     Instruction {
         program_id: crate::ID,
         accounts: vec![
-            AccountMeta::new(item_metadata, false),
-            AccountMeta::new(item_edition, false),
-            AccountMeta::new(item_token, false),
-            AccountMeta::new_readonly(token_owner, false),
-            AccountMeta::new_readonly(token_owner_program, false),
-            AccountMeta::new_readonly(token_owner_program_buffer.unwrap_or(crate::ID), false),
-            AccountMeta::new_readonly(item_mint, false),
             AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(PROGRAM_SIGNER, false),
-            AccountMeta::new_readonly(collection_metadata, false),
-            AccountMeta::new_readonly(delegate_record, false),
-            AccountMeta::new(token_record, false),
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(migration_state, false),
+            AccountMeta::new(owner_program_list, false),
             AccountMeta::new_readonly(solana_program::system_program::ID, false),
-            AccountMeta::new_readonly(solana_program::sysvar::instructions::ID, false),
-            AccountMeta::new_readonly(SPL_TOKEN_ID, false),
-            AccountMeta::new_readonly(MPL_TOKEN_AUTH_RULES_ID, false),
-            AccountMeta::new_readonly(auth_rule_set, false),
+        ],
+        data,
+    }
+}
+
+pub fn upgrade_state(payer: Pubkey, collection_mint: Pubkey) -> Instruction {
+    let (migration_state, _) = find_migration_state_pda(&collection_mint);
+
+    let data = MigrationInstruction::UpgradeState.try_to_vec().unwrap();
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
             AccountMeta::new(migration_state, false),
-            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn fund_bounty(
+    authority: Pubkey,
+    collection_mint: Pubkey,
+    args: FundBountyArgs,
+) -> Instruction {
+    let (migration_state, _) = find_migration_state_pda(&collection_mint);
+    let (bounty_vault, _) = find_bounty_vault_pda(&collection_mint);
+
+    let data = MigrationInstruction::FundBounty(args).try_to_vec().unwrap();
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new(migration_state, false),
+            AccountMeta::new(bounty_vault, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+        ],
+        data,
+    }
+}
+
+pub fn finalize_bounty(authority: Pubkey, collection_mint: Pubkey) -> Instruction {
+    let (migration_state, _) = find_migration_state_pda(&collection_mint);
+    let (bounty_vault, _) = find_bounty_vault_pda(&collection_mint);
+    let (collection_metadata, _) = find_metadata_account(&collection_mint);
+
+    let data = MigrationInstruction::FinalizeBounty.try_to_vec().unwrap();
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(authority, true),
+            AccountMeta::new_readonly(migration_state, false),
+            AccountMeta::new(bounty_vault, false),
+            AccountMeta::new_readonly(solana_program::system_program::ID, false),
+            AccountMeta::new_readonly(collection_metadata, false),
+        ],
+        data,
+    }
+}
+
+pub fn close_payer_record(payer: Pubkey, collection_mint: Pubkey) -> Instruction {
+    let (migration_state, _) = find_migration_state_pda(&collection_mint);
+    let (payer_record, _) = find_payer_record_pda(&collection_mint, &payer);
+    let (collection_metadata, _) = find_metadata_account(&collection_mint);
+
+    let data = MigrationInstruction::ClosePayerRecord.try_to_vec().unwrap();
+    Instruction {
+        program_id: crate::ID,
+        accounts: vec![
+            AccountMeta::new(payer, false),
+            AccountMeta::new_readonly(migration_state, false),
+            AccountMeta::new(payer_record, false),
+            AccountMeta::new_readonly(collection_metadata, false),
         ],
         data,
     }
@@ -0,0 +1,150 @@
+use crate::utils::UNKNOWN_BUMP;
+
+use super::*;
+
+pub fn accept_authority(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Fetch accounts
+    let account_info_iter = &mut accounts.iter();
+    let new_authority_info = next_account_info(account_info_iter)?;
+    let migration_state_info = next_account_info(account_info_iter)?;
+
+    // Validate Accounts
+    assert_signer(new_authority_info)?;
+
+    assert_owned_by(
+        migration_state_info,
+        program_id,
+        MigrationError::IncorrectMigrationStateProgramOwner,
+    )?;
+
+    // Deserialize the migration state
+    let mut migration_state = MigrationState::from_account_info(migration_state_info)?;
+
+    // Only the proposed authority can accept the handoff.
+    if migration_state.pending_authority == Pubkey::default()
+        || migration_state.pending_authority != *new_authority_info.key
+    {
+        return Err(MigrationError::InvalidPendingAuthority.into());
+    }
+
+    if Clock::get()?.unix_timestamp > migration_state.pending_authority_expiry {
+        return Err(MigrationError::PendingAuthorityExpired.into());
+    }
+
+    // The delegate record is tied to the authority, so it cannot change once items
+    // have been migrated.
+    if migration_state.status.has_migrated() {
+        return Err(MigrationError::MigrationInProgress.into());
+    }
+
+    migration_state.collection_info.authority = *new_authority_info.key;
+    migration_state.pending_authority = Pubkey::default();
+    migration_state.pending_authority_expiry = 0;
+    migration_state.save(migration_state_info)?;
+
+    Ok(())
+}
+
+pub fn sync_authority(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Fetch accounts
+    let account_info_iter = &mut accounts.iter();
+    let payer_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let collection_mint_info = next_account_info(account_info_iter)?;
+    let collection_metadata_info = next_account_info(account_info_iter)?;
+    let delegate_info = next_account_info(account_info_iter)?;
+    let delegate_record_info = next_account_info(account_info_iter)?;
+    let migration_state_info = next_account_info(account_info_iter)?;
+    let spl_token_program_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let token_metadata_program_info = next_account_info(account_info_iter)?;
+
+    // Validate Accounts
+    assert_signer(payer_info)?;
+
+    assert_owned_by(
+        collection_metadata_info,
+        &mpl_token_metadata::ID,
+        MigrationError::IncorrectMetadataProgramOwner,
+    )?;
+
+    assert_owned_by(
+        migration_state_info,
+        program_id,
+        MigrationError::IncorrectMigrationStateProgramOwner,
+    )?;
+
+    // Check program ids
+    if spl_token_program_info.key != &SPL_TOKEN_ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if system_program_info.key != &solana_program::system_program::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if token_metadata_program_info.key != &mpl_token_metadata::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Relationship validation
+    metadata_derived_from_mint(collection_metadata_info, collection_mint_info, UNKNOWN_BUMP)?;
+
+    let mut migration_state = MigrationState::from_account_info(migration_state_info)?;
+    migration_state.bump = migration_state_derived_from_mint(
+        migration_state_info,
+        collection_mint_info,
+        migration_state.bump,
+    )?;
+
+    let collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
+
+    incoming_collection_mint_matches_stored(collection_mint_info, &migration_state)?;
+
+    // The collection metadata is the source of truth for the authority, so anyone
+    // can bring the migration state in line with it.
+    let update_authority = collection_metadata.update_authority;
+
+    if migration_state.collection_info.authority != update_authority {
+        migration_state.collection_info.authority = update_authority;
+        migration_state.pending_authority = Pubkey::default();
+        migration_state.pending_authority_expiry = 0;
+    }
+
+    // Approving a delegate needs the update authority's signature, so the delegate
+    // record is only re-created when the update authority signs as well.
+    if authority_info.is_signer {
+        update_authority_matches(&collection_metadata, authority_info.key)?;
+
+        migration_state.collection_signer_bump = collection_signer_derived_from_mint(
+            delegate_info,
+            &migration_state.collection_info.mint,
+            migration_state.collection_signer_bump,
+        )?;
+
+        let delegate_record_bump = delegate_record_derived_from_mint(
+            delegate_record_info,
+            &migration_state.collection_info.mint,
+            delegate_info.key,
+            stored_delegate_record_bump(&migration_state, delegate_record_info),
+        )?;
+
+        refresh_delegate_record(
+            payer_info,
+            authority_info,
+            collection_mint_info,
+            collection_metadata_info,
+            delegate_info,
+            delegate_record_info,
+            spl_token_program_info,
+            system_program_info,
+        )?;
+
+        migration_state.collection_info.delegate_record = *delegate_record_info.key;
+        migration_state.delegate_record_bump = delegate_record_bump;
+    }
+
+    migration_state.save(migration_state_info)?;
+
+    Ok(())
+}
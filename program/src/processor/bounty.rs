@@ -0,0 +1,200 @@
+use solana_program::{msg, program::invoke, rent::Rent, system_instruction};
+
+use crate::{
+    instruction::FundBountyArgs, state::BOUNTY_RECLAIM_PERIOD, utils::close_program_account,
+};
+
+use super::*;
+
+pub fn fund_bounty<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: FundBountyArgs,
+) -> ProgramResult {
+    let FundBountyArgs { lamports, reward } = args;
+
+    // Fetch accounts
+    let account_info_iter = &mut accounts.iter();
+    let authority_info = next_account_info(account_info_iter)?;
+    let migration_state_info = next_account_info(account_info_iter)?;
+    let bounty_vault_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    // Validate Accounts
+    assert_signer(authority_info)?;
+
+    assert_owned_by(
+        migration_state_info,
+        program_id,
+        MigrationError::IncorrectMigrationStateProgramOwner,
+    )?;
+
+    if system_program_info.key != &solana_program::system_program::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Deserialize the migration state
+    let mut migration_state = MigrationState::from_account_info(migration_state_info)?;
+
+    // Ensure the authority matches
+    incoming_collection_authority_matches_stored(authority_info, &migration_state)?;
+
+    // The bounty vault must be derived from the stored collection mint.
+    let bump = bounty_vault_derived_from_mint(
+        bounty_vault_info,
+        &migration_state.collection_info.mint,
+        migration_state.bounty_vault_bump,
+    )?;
+
+    // The vault is a data-less account owned by this program so rewards can be
+    // paid out without a CPI.
+    if bounty_vault_info.owner == &solana_program::system_program::ID {
+        mpl_utils::create_or_allocate_account_raw(
+            *program_id,
+            bounty_vault_info,
+            system_program_info,
+            authority_info,
+            0,
+            &[
+                b"bounty",
+                migration_state.collection_info.mint.as_ref(),
+                &[bump],
+            ],
+        )?;
+    } else {
+        assert_owned_by(
+            bounty_vault_info,
+            program_id,
+            MigrationError::IncorrectBountyVaultProgramOwner,
+        )?;
+    }
+
+    if lamports > 0 {
+        invoke(
+            &system_instruction::transfer(authority_info.key, bounty_vault_info.key, lamports),
+            &[
+                authority_info.clone(),
+                bounty_vault_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    }
+
+    if let Some(reward) = reward {
+        migration_state.bounty_reward = reward;
+    }
+
+    // Record the bump so migrations do not have to search for it.
+    migration_state.bounty_vault_bump = bump;
+    migration_state.save(migration_state_info)?;
+
+    Ok(())
+}
+
+pub fn finalize_bounty<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>]) -> ProgramResult {
+    // Fetch accounts
+    let account_info_iter = &mut accounts.iter();
+    let authority_info = next_account_info(account_info_iter)?;
+    let migration_state_info = next_account_info(account_info_iter)?;
+    let bounty_vault_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+    let collection_metadata_info = next_account_info(account_info_iter)?;
+
+    // Validate Accounts
+    assert_signer(authority_info)?;
+
+    assert_owned_by(
+        migration_state_info,
+        program_id,
+        MigrationError::IncorrectMigrationStateProgramOwner,
+    )?;
+
+    assert_owned_by(
+        bounty_vault_info,
+        program_id,
+        MigrationError::IncorrectBountyVaultProgramOwner,
+    )?;
+
+    if system_program_info.key != &solana_program::system_program::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Deserialize the migration state
+    let migration_state = MigrationState::from_account_info(migration_state_info)?;
+
+    // Ensure the authority matches
+    incoming_collection_authority_matches_stored(authority_info, &migration_state)?;
+
+    bounty_vault_derived_from_mint(
+        bounty_vault_info,
+        &migration_state.collection_info.mint,
+        migration_state.bounty_vault_bump,
+    )?;
+
+    let collection_metadata =
+        collection_metadata_matches_stored(collection_metadata_info, &migration_state)?;
+
+    // A migration that never completes would lock the bounty, so the authority can
+    // reclaim it once no item has been migrated for the reclaim period.
+    let last_activity = migration_state
+        .status
+        .unlock_time
+        .max(migration_state.status.last_migration_timestamp);
+    let reclaimable_at = last_activity.saturating_add(BOUNTY_RECLAIM_PERIOD);
+
+    if Clock::get()?.unix_timestamp < reclaimable_at {
+        // Crankers are owed the bounty until every item has been migrated. An empty
+        // collection would count as complete before anything was migrated.
+        let collection_size =
+            collection_size(&collection_metadata, migration_state.collection_info.size)?;
+        if collection_size == 0 {
+            return Err(MigrationError::EmptyCollection.into());
+        }
+
+        if migration_state.status.items_migrated < collection_size {
+            return Err(MigrationError::MigrationNotComplete.into());
+        }
+    }
+
+    close_program_account(bounty_vault_info, authority_info)?;
+
+    Ok(())
+}
+
+/// Pays the configured reward from the bounty vault to the payer, if the vault
+/// has been created and can cover it. A vault that runs dry does not block the
+/// migration, so the skipped reward is only logged.
+pub(crate) fn pay_bounty(
+    bounty_vault_info: &AccountInfo,
+    payer_info: &AccountInfo,
+    program_id: &Pubkey,
+    reward: u64,
+) -> ProgramResult {
+    if reward == 0 || bounty_vault_info.owner != program_id {
+        return Ok(());
+    }
+
+    let available = bounty_vault_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+
+    if available < reward {
+        msg!(
+            "Bounty vault cannot cover the {} lamport reward, {} lamports available",
+            reward,
+            available
+        );
+        return Ok(());
+    }
+
+    **bounty_vault_info.lamports.borrow_mut() = bounty_vault_info
+        .lamports()
+        .checked_sub(reward)
+        .ok_or(MigrationError::Overflow)?;
+    **payer_info.lamports.borrow_mut() = payer_info
+        .lamports()
+        .checked_add(reward)
+        .ok_or(MigrationError::Overflow)?;
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use crate::utils::close_program_account;
+use crate::{
+    state::PayerRecord,
+    utils::{assert_derivation_with_bump, close_program_account},
+};
 
 use super::*;
 
@@ -26,10 +29,11 @@ pub fn close_migration_state(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
     // Deserialize the migration state
     let migration_state = MigrationState::from_account_info(migration_state_info)?;
 
-    assert_derivation(
+    assert_derivation_with_bump(
         program_id,
         migration_state_info,
         &[b"migration", migration_state.collection_info.mint.as_ref()],
+        migration_state.bump,
         MigrationError::InvalidMigrationStateDerivation,
     )?;
 
@@ -42,7 +46,7 @@ pub fn close_migration_state(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
     }
 
     // Do not allow closing after the migration is complete.
-    if migration_state.status.items_migrated > 0 {
+    if migration_state.status.has_migrated() {
         return Err(MigrationError::MigrationAlreadyCompleted.into());
     }
 
@@ -50,3 +54,57 @@ pub fn close_migration_state(program_id: &Pubkey, accounts: &[AccountInfo]) -> P
 
     Ok(())
 }
+
+pub fn close_payer_record(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    // Fetch accounts
+    let account_info_iter = &mut accounts.iter();
+    let payer_info = next_account_info(account_info_iter)?;
+    let migration_state_info = next_account_info(account_info_iter)?;
+    let payer_record_info = next_account_info(account_info_iter)?;
+    let collection_metadata_info = next_account_info(account_info_iter)?;
+
+    // Validate Accounts
+    assert_owned_by(
+        migration_state_info,
+        program_id,
+        MigrationError::IncorrectMigrationStateProgramOwner,
+    )?;
+
+    assert_owned_by(
+        payer_record_info,
+        program_id,
+        MigrationError::IncorrectPayerRecordProgramOwner,
+    )?;
+
+    // Deserialize the migration state and payer record
+    let migration_state = MigrationState::from_account_info(migration_state_info)?;
+    let payer_record = PayerRecord::from_account_info(payer_record_info)?;
+
+    // The rent can only be returned to the payer the record was created for.
+    assert_derivation_with_bump(
+        program_id,
+        payer_record_info,
+        &[
+            b"payer",
+            migration_state.collection_info.mint.as_ref(),
+            payer_info.key.as_ref(),
+        ],
+        payer_record.bump,
+        MigrationError::InvalidPayerRecordDerivation,
+    )?;
+
+    let collection_metadata =
+        collection_metadata_matches_stored(collection_metadata_info, &migration_state)?;
+
+    // Payer records mark payers as counted, so they are kept until every item has been
+    // migrated. A payer migrating the collection parent afterwards is counted again.
+    if migration_state.status.items_migrated
+        < collection_size(&collection_metadata, migration_state.collection_info.size)?
+    {
+        return Err(MigrationError::MigrationNotComplete.into());
+    }
+
+    close_program_account(payer_record_info, payer_info)?;
+
+    Ok(())
+}
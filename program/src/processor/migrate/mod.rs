@@ -3,6 +3,8 @@ mod validate;
 
 pub use processor::*;
 use spl_token::state::{Account, Mint};
+
+use crate::{state::OwnerProgramList, view::MigrationStateView};
 use validate::*;
 
 use super::*;
@@ -24,12 +26,16 @@ pub(crate) struct AccountContext<'a> {
     pub(crate) sysvar_instructions_info: &'a AccountInfo<'a>,
     pub(crate) token_metadata_program_info: &'a AccountInfo<'a>,
     pub(crate) spl_token_program_info: &'a AccountInfo<'a>,
+    pub(crate) owner_program_list_info: &'a AccountInfo<'a>,
+    pub(crate) bounty_vault_info: &'a AccountInfo<'a>,
+    pub(crate) payer_record_info: &'a AccountInfo<'a>,
 }
 
 pub(crate) struct DataContext<'a> {
     pub(crate) metadata: &'a Metadata,
     pub(crate) collection_metadata: &'a Metadata,
-    pub(crate) migration_state: &'a MigrationState,
+    pub(crate) migration_state: &'a MigrationStateView<&'a [u8]>,
     pub(crate) mint: &'a Mint,
     pub(crate) token: &'a Account,
+    pub(crate) owner_program_list: Option<&'a OwnerProgramList>,
 }
@@ -1,11 +1,35 @@
 use solana_program::program_pack::Pack;
 use spl_token::state::Mint;
 
-use crate::errors::MigrationError;
+use crate::{
+    errors::MigrationError, processor::bounty::pay_bounty, state::PayerRecord,
+    view::MigrationStateView, PROGRAM_SIGNER,
+};
 
 use super::*;
 
-pub fn migrate_item<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo<'a>]) -> ProgramResult {
+pub fn migrate_item<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: MigrateItemArgs,
+) -> ProgramResult {
+    migrate(program_id, accounts, args, false)
+}
+
+pub fn migrate_collection_parent<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: MigrateItemArgs,
+) -> ProgramResult {
+    migrate(program_id, accounts, args, true)
+}
+
+fn migrate<'a>(
+    program_id: &'a Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: MigrateItemArgs,
+    is_collection_parent: bool,
+) -> ProgramResult {
     // Fetch accounts
     let account_info_iter = &mut accounts.iter();
     let metadata_info = next_account_info(account_info_iter)?;
@@ -27,6 +51,9 @@ pub fn migrate_item<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo<'a>])
     let auth_rule_set_info = next_account_info(account_info_iter)?;
     let migration_state_info = next_account_info(account_info_iter)?;
     let token_metadata_program_info = next_account_info(account_info_iter)?;
+    let owner_program_list_info = next_account_info(account_info_iter)?;
+    let bounty_vault_info = next_account_info(account_info_iter)?;
+    let payer_record_info = next_account_info(account_info_iter)?;
 
     let ctx = AccountContext {
         program_id,
@@ -45,6 +72,9 @@ pub fn migrate_item<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo<'a>])
         sysvar_instructions_info,
         spl_token_program_info,
         token_metadata_program_info,
+        owner_program_list_info,
+        bounty_vault_info,
+        payer_record_info,
     };
 
     // Validate Accounts
@@ -57,13 +87,43 @@ pub fn migrate_item<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo<'a>])
     let collection_metadata = Metadata::from_account_info(collection_metadata_info)
         .map_err(|_| MigrationError::InvalidMetadata)?;
 
-    let mut migration_state = MigrationState::from_account_info(ctx.migration_state_info)?;
+    // The migration state is read in place rather than deserialized, which is why only
+    // upgraded states can be migrated from.
+    let migration_state_data = ctx.migration_state_info.try_borrow_data()?;
+    let migration_state = MigrationStateView::new(&migration_state_data[..])?;
 
     let mint = Mint::unpack(&ctx.mint_info.data.borrow())?;
     let token = Account::unpack(&token_info.data.borrow())?;
 
-    let program_signer = ProgramSigner::from_account_info(ctx.program_signer_info)?;
-    let signers_seeds: &[&[u8]] = &[b"signer", &[program_signer.bump]];
+    // The owner program list is optional and only enforced once the authority has created it.
+    let owner_program_list = if ctx.owner_program_list_info.data_is_empty() {
+        None
+    } else {
+        Some(OwnerProgramList::from_account_info(
+            ctx.owner_program_list_info,
+        )?)
+    };
+
+    // Migrations started before collections had their own signer keep using the
+    // global program signer until they complete.
+    let collection_mint = migration_state.mint();
+    let bounty_reward = migration_state.bounty_reward();
+    let is_legacy_signer = ctx.program_signer_info.key == &PROGRAM_SIGNER;
+    let signer_bump = if is_legacy_signer {
+        ProgramSigner::from_account_info(ctx.program_signer_info)?.bump
+    } else {
+        collection_signer_derived_from_mint(
+            ctx.program_signer_info,
+            &collection_mint,
+            migration_state.collection_signer_bump(),
+        )?
+    };
+    let bump_seed = [signer_bump];
+    let signers_seeds: &[&[u8]] = if is_legacy_signer {
+        &[b"signer", &bump_seed]
+    } else {
+        &[b"signer", collection_mint.as_ref(), &bump_seed]
+    };
 
     let data_context = DataContext {
         metadata: &metadata,
@@ -71,27 +131,35 @@ pub fn migrate_item<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo<'a>])
         migration_state: &migration_state,
         mint: &mint,
         token: &token,
+        owner_program_list: owner_program_list.as_ref(),
     };
 
     // Validate relatonships between accounts
-    validate_relationships(&ctx, &data_context)?;
+    validate_relationships(&ctx, &data_context, &args)?;
+
+    // Validate the collection parent is migrated in its own step and in the configured order.
+    validate_step(&data_context, is_collection_parent)?;
 
     // Validate the delegate record is correct.
-    validate_delegate(&ctx, &data_context)?;
+    validate_delegate(&ctx, &data_context, signer_bump)?;
 
     // Validate this item passes all eligibility rules.
     validate_eligibility(&ctx, &data_context)?;
 
     // Migrate the item by CPI'ing into Token Metadata.
-    let args = MigrateArgs::V1 {
+    let rule_set = migration_state.rule_set();
+    let migrate_args = MigrateArgs::V1 {
         migration_type: MigrationType::ProgrammableV1,
-        rule_set: if migration_state.collection_info.rule_set == Pubkey::default() {
+        rule_set: if rule_set == Pubkey::default() {
             None
         } else {
-            Some(migration_state.collection_info.rule_set)
+            Some(rule_set)
         },
     };
 
+    // Release the migration state so its counters can be written below.
+    drop(migration_state_data);
+
     let account_infos = vec![
         metadata_info.clone(),
         edition_info.clone(),
@@ -122,22 +190,105 @@ pub fn migrate_item<'a>(program_id: &'a Pubkey, accounts: &'a [AccountInfo<'a>])
         .collection_metadata(*collection_metadata_info.key)
         .delegate_record(*delegate_record_info.key)
         .token_record(*token_record_info.key)
-        .build(args)
+        .build(migrate_args)
         .map_err(|_| MigrationError::InvalidInstruction)?;
 
     let instruction = migrate.instruction();
 
+    let token_record_lamports = token_record_info.lamports();
+
     invoke_signed(&instruction, &account_infos, &[signers_seeds]).unwrap();
 
-    // Increment the number of items migrated
-    migration_state.status.items_migrated = migration_state
-        .status
-        .items_migrated
-        .checked_add(1)
-        .ok_or(MigrationError::Overflow)?;
+    // Account for the token record rent paid by the payer.
+    let token_record_rent = token_record_info
+        .lamports()
+        .saturating_sub(token_record_lamports);
+
+    // Count the payer the first time they pay for a migration of this collection. The
+    // bump was checked to be canonical with the other accounts.
+    let is_new_payer = payer_record_info.data_is_empty();
+    if is_new_payer {
+        let bump = payer_record_derived_from_mint(
+            payer_record_info,
+            &collection_mint,
+            payer_info.key,
+            args.payer_record_bump,
+        )?;
+        let payer_record = PayerRecord {
+            key: Key::PayerRecord,
+            bump,
+        };
+        let serialized_data = payer_record.try_to_vec()?;
+        let data_len = serialized_data.len();
+
+        mpl_utils::create_or_allocate_account_raw(
+            *program_id,
+            payer_record_info,
+            system_program_info,
+            payer_info,
+            data_len,
+            &[
+                b"payer",
+                collection_mint.as_ref(),
+                payer_info.key.as_ref(),
+                &[bump],
+            ],
+        )?;
+
+        sol_memcpy(
+            &mut payer_record_info.data.borrow_mut(),
+            serialized_data.as_slice(),
+            data_len,
+        );
+    }
+
+    // Only the status changes, so it is written in place rather than re-serializing
+    // the whole migration state.
+    {
+        let mut data = migration_state_info.try_borrow_mut_data()?;
+        let mut state = MigrationStateView::new(&mut **data)?;
+
+        state.set_token_record_rent_paid(
+            state
+                .token_record_rent_paid()
+                .checked_add(token_record_rent)
+                .ok_or(MigrationError::Overflow)?,
+        );
+
+        if is_new_payer {
+            state.set_unique_payers(
+                state
+                    .unique_payers()
+                    .checked_add(1)
+                    .ok_or(MigrationError::Overflow)?,
+            );
+            state.set_payer_record_rent_paid(
+                state
+                    .payer_record_rent_paid()
+                    .checked_add(payer_record_info.lamports())
+                    .ok_or(MigrationError::Overflow)?,
+            );
+        }
+
+        let clock = Clock::get()?;
+        state.record_migration_time(clock.slot, clock.unix_timestamp)?;
+
+        // Record the collection parent step, or increment the number of items migrated.
+        // The collection parent is not counted as an item.
+        if is_collection_parent {
+            state.set_collection_parent_migrated(true);
+        } else {
+            state.set_items_migrated(
+                state
+                    .items_migrated()
+                    .checked_add(1)
+                    .ok_or(MigrationError::Overflow)?,
+            );
+        }
+    }
 
-    // Serialize the migration state
-    migration_state.save(migration_state_info)?;
+    // Reward the payer for cranking the migration.
+    pay_bounty(bounty_vault_info, payer_info, program_id, bounty_reward)?;
 
     Ok(())
 }
@@ -1,8 +1,11 @@
-use mpl_token_metadata::{state::TokenStandard, utils::is_master_edition};
+use mpl_token_metadata::utils::is_master_edition;
 use mpl_utils::token::{get_mint_decimals, get_mint_supply};
 use solana_program::bpf_loader_upgradeable::UpgradeableLoaderState;
 
-use crate::utils::assert_valid_delegate;
+use crate::{
+    state::{CollectionParentOrder, UpdateAuthorityMode},
+    utils::assert_valid_delegate,
+};
 
 use super::*;
 
@@ -32,6 +35,20 @@ pub(crate) fn validate_accounts(ctx: &AccountContext) -> Result<(), ProgramError
         ctx.program_id,
         MigrationError::IncorrectMigrationStateProgramOwner,
     )?;
+    if !ctx.owner_program_list_info.data_is_empty() {
+        assert_owned_by(
+            ctx.owner_program_list_info,
+            ctx.program_id,
+            MigrationError::IncorrectOwnerProgramListProgramOwner,
+        )?;
+    }
+    if !ctx.payer_record_info.data_is_empty() {
+        assert_owned_by(
+            ctx.payer_record_info,
+            ctx.program_id,
+            MigrationError::IncorrectPayerRecordProgramOwner,
+        )?;
+    }
 
     // Programs
     if ctx.token_metadata_program_info.key != &mpl_token_metadata::ID {
@@ -53,6 +70,7 @@ pub(crate) fn validate_accounts(ctx: &AccountContext) -> Result<(), ProgramError
 pub(crate) fn validate_relationships(
     ctx: &AccountContext,
     data: &DataContext,
+    args: &MigrateItemArgs,
 ) -> Result<(), ProgramError> {
     // User provided
     let item_metadata = &data.metadata;
@@ -61,8 +79,8 @@ pub(crate) fn validate_relationships(
     let collection_mint_pubkey = &collection_metadata.mint;
 
     // Migration State
-    let stored_collection_mint_pubkey = &data.migration_state.collection_info.mint;
-    let stored_collection_authority_pubkey = &data.migration_state.collection_info.authority;
+    let stored_collection_mint_pubkey = &data.migration_state.mint();
+    let stored_collection_authority_pubkey = &data.migration_state.authority();
 
     // Collection NFT
     // The provided collection metadata must match the collection mint and update authority
@@ -71,11 +89,15 @@ pub(crate) fn validate_relationships(
     update_authority_matches(collection_metadata, stored_collection_authority_pubkey)?;
 
     // Migration Item
-    // The item's metadata and mint must match.
+    // The item's metadata must be derived from its mint, and its mint must match.
+    metadata_derived_from_mint(ctx.metadata_info, ctx.mint_info, args.metadata_bump)?;
     metadata_belongs_to_mint(item_metadata, mint_pubkey)?;
 
-    // The item's update authority must match that of the collection.
-    update_authority_matches(item_metadata, stored_collection_authority_pubkey)?;
+    // The item's update authority must match that of the collection, unless items are
+    // authorized through their verified collection membership and the collection delegate.
+    if data.migration_state.update_authority_mode()? == UpdateAuthorityMode::Match {
+        update_authority_matches(item_metadata, stored_collection_authority_pubkey)?;
+    }
 
     // The item must actually be a verified member of the collection or must
     // be the collection NFT itself.
@@ -84,15 +106,56 @@ pub(crate) fn validate_relationships(
     }
 
     // The passed in auth_rules account must match the one on the migration state.
-    incoming_auth_rules_matches_stored(ctx.auth_rule_set_info, data.migration_state)?;
+    incoming_auth_rules_matches_stored(ctx.auth_rule_set_info, &data.migration_state.rule_set())?;
+
+    // The owner program list must be derived from the stored collection mint.
+    owner_program_list_derived_from_mint(
+        ctx.owner_program_list_info,
+        stored_collection_mint_pubkey,
+        data.migration_state.owner_program_list_bump(),
+    )?;
+
+    // The bounty vault must be derived from the stored collection mint.
+    bounty_vault_derived_from_mint(
+        ctx.bounty_vault_info,
+        stored_collection_mint_pubkey,
+        data.migration_state.bounty_vault_bump(),
+    )?;
+
+    // The payer record must be derived from the stored collection mint and the payer. The
+    // given bump is trusted for existing records, while new ones must be canonical.
+    if ctx.payer_record_info.data_is_empty() {
+        new_payer_record_derived_from_mint(
+            ctx.payer_record_info,
+            stored_collection_mint_pubkey,
+            ctx.payer_info.key,
+            args.payer_record_bump,
+        )?;
+    } else {
+        payer_record_derived_from_mint(
+            ctx.payer_record_info,
+            stored_collection_mint_pubkey,
+            ctx.payer_info.key,
+            args.payer_record_bump,
+        )?;
+    }
 
     // The item's edition must be derived from the item's mint.
-    edition_derived_from_mint(ctx.edition_info, ctx.mint_info)?;
+    edition_derived_from_mint(ctx.edition_info, ctx.mint_info, args.edition_bump)?;
+
+    // The item's edition must be owned by Token Metadata.
+    assert_owned_by(
+        ctx.edition_info,
+        &mpl_token_metadata::ID,
+        MigrationError::IncorrectEditionProgramOwner,
+    )?;
 
     // The item's edition must be a master edition.
     let mint_decimals = get_mint_decimals(ctx.mint_info)?;
     let mint_supply = get_mint_supply(ctx.mint_info)?;
-    is_master_edition(ctx.edition_info, mint_decimals, mint_supply);
+    if !is_master_edition(ctx.edition_info, mint_decimals, mint_supply) {
+        return Err(MigrationError::NotMasterEdition.into());
+    }
 
     // The token must belong to the mint
     token_belongs_to_mint(data.token, mint_pubkey)?;
@@ -113,18 +176,10 @@ pub(crate) fn validate_relationships(
     let state_opt: Option<UpgradeableLoaderState> =
         bincode::deserialize(&ctx.token_owner_program_info.data.borrow()).ok();
 
-    if let Some(state) = state_opt {
-        match state {
-            UpgradeableLoaderState::Program {
-                programdata_address,
-            } => {
-                if programdata_address != *ctx.token_owner_program_buffer_info.key {
-                    return Err(MigrationError::IncorrectTokenOwnerProgramBuffer.into());
-                }
-            }
-            _ => return Err(MigrationError::IncorrectTokenOwnerProgramOwner.into()),
-        }
-    }
+    token_owner_program_buffer_matches(
+        state_opt.as_ref(),
+        ctx.token_owner_program_buffer_info.key,
+    )?;
 
     Ok(())
 }
@@ -133,48 +188,83 @@ pub(crate) fn validate_eligibility(
     ctx: &AccountContext,
     data: &DataContext,
 ) -> Result<(), ProgramError> {
-    // The Token Metadata edition PDA must have the freeze authority on the item.
-    if data.mint.freeze_authority != COption::Some(*ctx.edition_info.key) {
-        return Err(MigrationError::IncorrectFreezeAuthority.into());
-    }
+    let mint_decimals = get_mint_decimals(ctx.mint_info)?;
+    let mint_supply = get_mint_supply(ctx.mint_info)?;
 
-    // The item metadata must be mutable.
-    if !data.metadata.is_mutable {
-        return Err(MigrationError::ImmutableMetadata.into());
-    }
+    // token owner program buffer defaults to crate ID if not provided,
+    // so skip this check if that's the case.
+    let buffer_state: Option<UpgradeableLoaderState> =
+        if ctx.token_owner_program_buffer_info.key != &crate::ID {
+            Some(
+                bincode::deserialize(&ctx.token_owner_program_buffer_info.data.borrow())
+                    .map_err(|_| MigrationError::IncorrectTokenOwnerProgramBuffer)?,
+            )
+        } else {
+            None
+        };
+
+    let report = check_eligibility(&EligibilityContext {
+        metadata: data.metadata,
+        mint: data.mint,
+        edition: ctx.edition_info.key,
+        is_master_edition: is_master_edition(ctx.edition_info, mint_decimals, mint_supply),
+        token: data.token,
+        token_owner_program: ctx.token_owner_program_info.key,
+        token_owner_program_buffer: buffer_state.as_ref(),
+        delegate_handling: data.migration_state.delegate_handling()?,
+        owner_program_list: data.owner_program_list,
+    });
+
+    report.result()?;
+
+    Ok(())
+}
+
+pub(crate) fn validate_step(
+    data: &DataContext,
+    is_collection_parent: bool,
+) -> Result<(), ProgramError> {
+    let state = data.migration_state;
+    let collection_size = collection_size(data.collection_metadata, state.size())?;
+    let collection_parent_migrated = state.collection_parent_migrated()?;
+    let collection_parent_released = state.collection_parent_released()?;
+    let is_parent_mint = data.metadata.mint == state.mint();
 
-    if let Some(token_standard) = data.metadata.token_standard {
-        if token_standard != TokenStandard::NonFungible {
-            return Err(MigrationError::IncorrectTokenStandard.into());
+    if is_collection_parent {
+        if !is_parent_mint {
+            return Err(MigrationError::NotCollectionParent.into());
         }
-    } else {
-        let mint_decimals = get_mint_decimals(ctx.mint_info)?;
-        let mint_supply = get_mint_supply(ctx.mint_info)?;
 
-        if !is_master_edition(ctx.edition_info, mint_decimals, mint_supply) {
-            return Err(MigrationError::IncorrectTokenStandard.into());
+        if collection_parent_migrated {
+            return Err(MigrationError::CollectionParentAlreadyMigrated.into());
         }
-    }
 
-    // token owner program buffer defaults to crate ID if not provided,
-    // so skip this check if that's the case.
-    if ctx.token_owner_program_buffer_info.key != &crate::ID {
-        // Do not migrate items owned by immutable programs.
-        let state: UpgradeableLoaderState =
-            bincode::deserialize(&ctx.token_owner_program_buffer_info.data.borrow())
-                .map_err(|_| MigrationError::IncorrectTokenOwnerProgramBuffer)?;
-
-        match state {
-            UpgradeableLoaderState::ProgramData {
-                slot: _,
-                upgrade_authority_address,
-            } => {
-                if upgrade_authority_address.is_none() {
-                    return Err(MigrationError::ImmutableProgramOwner.into());
+        // When migrated last, every item must have been migrated first, unless the
+        // authority released the collection parent.
+        if state.collection_parent_order()? == CollectionParentOrder::Last
+            && !collection_parent_released
+            && state.items_migrated() < collection_size
+        {
+            return Err(MigrationError::CollectionItemsNotMigrated.into());
+        }
+    } else {
+        // The collection parent cannot be migrated in the middle of the run.
+        if is_parent_mint {
+            return Err(MigrationError::UnexpectedCollectionParent.into());
+        }
+
+        match state.collection_parent_order()? {
+            CollectionParentOrder::First => {
+                if !collection_parent_migrated {
+                    return Err(MigrationError::CollectionParentNotMigrated.into());
+                }
+            }
+            // Items left over when a released collection parent migrated can still follow it.
+            CollectionParentOrder::Last => {
+                if collection_parent_migrated && !collection_parent_released {
+                    return Err(MigrationError::CollectionParentAlreadyMigrated.into());
                 }
             }
-            // If this isn't a ProgramData variant something is wrong.
-            _ => return Err(MigrationError::IncorrectTokenOwnerProgramBuffer.into()),
         }
     }
 
@@ -184,11 +274,13 @@ pub(crate) fn validate_eligibility(
 pub(crate) fn validate_delegate(
     ctx: &AccountContext,
     data: &DataContext,
+    signer_bump: u8,
 ) -> Result<(), ProgramError> {
     // Validate that the delegate is the program signer for the correct
     // mint and update authority.
     assert_valid_delegate(
         ctx.program_signer_info.key,
+        signer_bump,
         ctx.delegate_record_info,
         data.collection_metadata,
         data.migration_state,
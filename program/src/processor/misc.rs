@@ -23,7 +23,10 @@ pub fn init_signer(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResu
         return Err(MigrationError::AlreadyInitialized.into());
     }
 
-    let signer = ProgramSigner { bump };
+    let signer = ProgramSigner {
+        key: Key::ProgramSigner,
+        bump,
+    };
 
     let serialized_data = signer.try_to_vec()?;
     let data_len = serialized_data.len();
@@ -1,12 +1,13 @@
 use crate::{
     errors::MigrationError,
-    instruction::{MigrationInstruction, UpdateArgs},
-    state::{MigrationState, ProgramSigner, UnlockMethod, SPL_TOKEN_ID},
+    instruction::{MigrateItemArgs, MigrationInstruction, UpdateArgs},
+    state::{Key, MigrationState, ProgramSigner, UnlockMethod, SPL_TOKEN_ID},
+    validation::*,
 };
 use borsh::{BorshDeserialize, BorshSerialize};
 use mpl_token_metadata::{
     instruction::{builders::MigrateBuilder, InstructionBuilder, MigrateArgs},
-    state::{Metadata, MigrationType, TokenMetadataAccount, EDITION, PREFIX},
+    state::{CollectionDetails, Metadata, MigrationType, TokenMetadataAccount, EDITION, PREFIX},
 };
 use mpl_utils::{assert_derivation, assert_owned_by, assert_signer};
 use solana_program::{
@@ -16,26 +17,31 @@ use solana_program::{
     program::invoke_signed,
     program_error::ProgramError,
     program_memory::sol_memcpy,
-    program_option::COption,
     pubkey::Pubkey,
     sysvar::{self, Sysvar},
 };
 
-use spl_token::state::Account as TokenAccount;
-
+mod authority;
+mod bounty;
 mod close;
 mod initialize;
 mod migrate;
 mod misc;
+mod owner_program_list;
 mod start;
 mod update;
+mod upgrade;
 mod validators;
 
-use close::close_migration_state;
-use migrate::migrate_item;
+use authority::{accept_authority, sync_authority};
+use bounty::{finalize_bounty, fund_bounty};
+use close::{close_migration_state, close_payer_record};
+use migrate::{migrate_collection_parent, migrate_item};
 use misc::init_signer;
-use start::start_migration;
+use owner_program_list::set_owner_program_list;
+use start::{refresh_delegate_record, start_migration, stored_delegate_record_bump};
 use update::update_state;
+use upgrade::upgrade_state;
 use validators::*;
 
 pub struct Processor;
@@ -55,8 +61,20 @@ impl Processor {
             MigrationInstruction::Update(args) => update_state(program_id, accounts, args),
             MigrationInstruction::Close => close_migration_state(program_id, accounts),
             MigrationInstruction::Start => start_migration(program_id, accounts),
-            MigrationInstruction::Migrate => migrate_item(program_id, accounts),
+            MigrationInstruction::Migrate(args) => migrate_item(program_id, accounts, args),
+            MigrationInstruction::MigrateCollectionParent(args) => {
+                migrate_collection_parent(program_id, accounts, args)
+            }
+            MigrationInstruction::UpgradeState => upgrade_state(program_id, accounts),
+            MigrationInstruction::FundBounty(args) => fund_bounty(program_id, accounts, args),
+            MigrationInstruction::FinalizeBounty => finalize_bounty(program_id, accounts),
+            MigrationInstruction::AcceptAuthority => accept_authority(program_id, accounts),
+            MigrationInstruction::SyncAuthority => sync_authority(program_id, accounts),
             MigrationInstruction::InitSigner => init_signer(program_id, accounts),
+            MigrationInstruction::SetOwnerProgramList(args) => {
+                set_owner_program_list(program_id, accounts, args)
+            }
+            MigrationInstruction::ClosePayerRecord => close_payer_record(program_id, accounts),
         }
     }
 }
@@ -0,0 +1,97 @@
+use crate::{
+    instruction::SetOwnerProgramListArgs, state::OwnerProgramList, utils::resize_program_account,
+};
+
+use super::*;
+
+pub fn set_owner_program_list<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetOwnerProgramListArgs,
+) -> ProgramResult {
+    let SetOwnerProgramListArgs {
+        list_type,
+        programs,
+    } = args;
+
+    // Fetch accounts
+    let account_info_iter = &mut accounts.iter();
+    let payer_info = next_account_info(account_info_iter)?;
+    let authority_info = next_account_info(account_info_iter)?;
+    let migration_state_info = next_account_info(account_info_iter)?;
+    let owner_program_list_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    // Validate Accounts
+    assert_signer(payer_info)?;
+    assert_signer(authority_info)?;
+
+    assert_owned_by(
+        migration_state_info,
+        program_id,
+        MigrationError::IncorrectMigrationStateProgramOwner,
+    )?;
+
+    if system_program_info.key != &solana_program::system_program::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Deserialize the migration state
+    let mut migration_state = MigrationState::from_account_info(migration_state_info)?;
+
+    // Ensure the authority matches
+    incoming_collection_authority_matches_stored(authority_info, &migration_state)?;
+
+    // The owner program list must be derived from the stored collection mint.
+    let bump = owner_program_list_derived_from_mint(
+        owner_program_list_info,
+        &migration_state.collection_info.mint,
+        migration_state.owner_program_list_bump,
+    )?;
+
+    let owner_program_list = OwnerProgramList {
+        key: Key::OwnerProgramList,
+        list_type,
+        programs,
+    };
+    let data_len = owner_program_list.try_to_vec()?.len();
+
+    // Create the list on first use, otherwise resize it to fit the new programs.
+    if owner_program_list_info.data_is_empty() {
+        mpl_utils::create_or_allocate_account_raw(
+            *program_id,
+            owner_program_list_info,
+            system_program_info,
+            payer_info,
+            data_len,
+            &[
+                b"owner_program_list",
+                migration_state.collection_info.mint.as_ref(),
+                &[bump],
+            ],
+        )?;
+    } else {
+        assert_owned_by(
+            owner_program_list_info,
+            program_id,
+            MigrationError::IncorrectOwnerProgramListProgramOwner,
+        )?;
+
+        resize_program_account(
+            owner_program_list_info,
+            payer_info,
+            system_program_info,
+            data_len,
+        )?;
+    }
+
+    owner_program_list.save(owner_program_list_info)?;
+
+    // Record the bump so migrations do not have to search for it.
+    if migration_state.owner_program_list_bump != bump {
+        migration_state.owner_program_list_bump = bump;
+        migration_state.save(migration_state_info)?;
+    }
+
+    Ok(())
+}
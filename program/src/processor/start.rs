@@ -1,6 +1,6 @@
 use mpl_token_metadata::state::CollectionAuthorityRecord;
 
-use crate::PROGRAM_SIGNER;
+use crate::utils::UNKNOWN_BUMP;
 
 use super::*;
 
@@ -55,15 +55,32 @@ pub fn start_migration(program_id: &Pubkey, accounts: &[AccountInfo]) -> Program
 
     // Relationship validation
 
-    metadata_derived_from_mint(collection_metadata_info, collection_mint_info)?;
-    migration_state_derived_from_mint(migration_state_info, collection_mint_info)?;
+    metadata_derived_from_mint(collection_metadata_info, collection_mint_info, UNKNOWN_BUMP)?;
 
     // Deserialize needed account states.
     let mut migration_state = MigrationState::from_account_info(migration_state_info)?;
+
+    // The stored bump is checked, and recorded for states that do not have one yet.
+    migration_state.bump = migration_state_derived_from_mint(
+        migration_state_info,
+        collection_mint_info,
+        migration_state.bump,
+    )?;
+
     let collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
 
     incoming_collection_mint_matches_stored(collection_mint_info, &migration_state)?;
     incoming_collection_authority_matches_stored(authority_info, &migration_state)?;
+
+    // The delegate must be the program signer for this collection, so a revoked or
+    // compromised delegate record only ever affects a single collection. Its bump is
+    // recorded so migrations do not have to search for it.
+    migration_state.collection_signer_bump = collection_signer_derived_from_mint(
+        delegate_info,
+        &migration_state.collection_info.mint,
+        migration_state.collection_signer_bump,
+    )?;
+
     // Update authority on collection metadata matches the authority stored in the migration state.
     update_authority_matches(
         &collection_metadata,
@@ -72,20 +89,76 @@ pub fn start_migration(program_id: &Pubkey, accounts: &[AccountInfo]) -> Program
 
     // The delegate record must match the correct derivation
     // with the mint from the migration state account and the
-    // program signer as the delegate.
-    assert_derivation(
-        &mpl_token_metadata::ID,
+    // collection program signer as the delegate.
+    let delegate_record_bump = delegate_record_derived_from_mint(
+        delegate_record_info,
+        &migration_state.collection_info.mint,
+        delegate_info.key,
+        stored_delegate_record_bump(&migration_state, delegate_record_info),
+    )?;
+
+    refresh_delegate_record(
+        payer_info,
+        authority_info,
+        collection_mint_info,
+        collection_metadata_info,
+        delegate_info,
         delegate_record_info,
-        &[
-            mpl_token_metadata::state::PREFIX.as_bytes(),
-            mpl_token_metadata::ID.as_ref(),
-            migration_state.collection_info.mint.as_ref(),
-            mpl_token_metadata::pda::COLLECTION_AUTHORITY.as_bytes(),
-            PROGRAM_SIGNER.as_ref(),
-        ],
-        MigrationError::InvalidDelegateRecordDerivation,
+        spl_token_program_info,
+        system_program_info,
     )?;
 
+    // Migration must be unlocked
+    if migration_state.status.is_locked {
+        return Err(MigrationError::MigrationLocked.into());
+    }
+
+    // Migration can be enabled, set to "in progress", as long as no items have been
+    // migrated yet. This allows people to reset the delegate record if they
+    // change their update authority.
+    if migration_state.status.has_migrated() {
+        return Err(MigrationError::MigrationInProgress.into());
+    }
+
+    // Sized collections track their own size on-chain, so it overrides any manual size.
+    if let Some(size) = sized_collection_size(&collection_metadata)? {
+        migration_state.collection_info.size = size;
+        migration_state.collection_info.is_sized = true;
+    }
+
+    migration_state.collection_info.delegate_record = *delegate_record_info.key;
+    migration_state.delegate_record_bump = delegate_record_bump;
+    migration_state.status.in_progress = true;
+    migration_state.save(migration_state_info)?;
+
+    Ok(())
+}
+
+/// The stored delegate record bump, if it belongs to the given delegate record.
+pub(crate) fn stored_delegate_record_bump(
+    migration_state: &MigrationState,
+    delegate_record_info: &AccountInfo,
+) -> u8 {
+    if migration_state.collection_info.delegate_record == *delegate_record_info.key {
+        migration_state.delegate_record_bump
+    } else {
+        UNKNOWN_BUMP
+    }
+}
+
+/// Revokes the delegate record if it was approved by a different update authority and
+/// approves it again with the given authority, which must sign.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn refresh_delegate_record<'a>(
+    payer_info: &AccountInfo<'a>,
+    authority_info: &AccountInfo<'a>,
+    collection_mint_info: &AccountInfo<'a>,
+    collection_metadata_info: &AccountInfo<'a>,
+    delegate_info: &AccountInfo<'a>,
+    delegate_record_info: &AccountInfo<'a>,
+    spl_token_program_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+) -> ProgramResult {
     if !delegate_record_info.data_is_empty() {
         // Check that the authority matches for the cases where we don't create the record.
         let authority_record = CollectionAuthorityRecord::from_account_info(delegate_record_info)?;
@@ -97,7 +170,7 @@ pub fn start_migration(program_id: &Pubkey, accounts: &[AccountInfo]) -> Program
             let instruction = mpl_token_metadata::instruction::revoke_collection_authority(
                 mpl_token_metadata::ID,
                 *delegate_record_info.key,
-                PROGRAM_SIGNER,
+                *delegate_info.key,
                 *authority_info.key,
                 *collection_metadata_info.key,
                 *collection_mint_info.key,
@@ -123,7 +196,7 @@ pub fn start_migration(program_id: &Pubkey, accounts: &[AccountInfo]) -> Program
         let instruction = mpl_token_metadata::instruction::approve_collection_authority(
             mpl_token_metadata::ID,
             *delegate_record_info.key,
-            PROGRAM_SIGNER,
+            *delegate_info.key,
             *authority_info.key,
             *payer_info.key,
             *collection_metadata_info.key,
@@ -143,21 +216,5 @@ pub fn start_migration(program_id: &Pubkey, accounts: &[AccountInfo]) -> Program
         invoke_signed(&instruction, &account_infos, &[]).unwrap();
     }
 
-    // Migration must be unlocked
-    if migration_state.status.is_locked {
-        return Err(MigrationError::MigrationLocked.into());
-    }
-
-    // Migration can be enabled, set to "in progress", as long as no items have been
-    // migrated yet. This allows people to reset the delegate record if they
-    // change their update authority.
-    if migration_state.status.items_migrated > 0 {
-        return Err(MigrationError::MigrationInProgress.into());
-    }
-
-    migration_state.collection_info.delegate_record = *delegate_record_info.key;
-    migration_state.status.in_progress = true;
-    migration_state.save(migration_state_info)?;
-
     Ok(())
 }
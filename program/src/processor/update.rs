@@ -1,3 +1,5 @@
+use crate::state::AUTHORITY_HANDOFF_PERIOD;
+
 use super::*;
 
 pub fn update_state(
@@ -7,14 +9,19 @@ pub fn update_state(
 ) -> ProgramResult {
     let UpdateArgs {
         rule_set,
-        collection_size,
+        collection_size: new_collection_size,
         new_update_authority,
+        delegate_handling,
+        collection_parent_order,
+        update_authority_mode,
+        release_collection_parent,
     } = args;
 
     // Fetch accounts
     let account_info_iter = &mut accounts.iter();
     let authority_info = next_account_info(account_info_iter)?;
     let migration_state_info = next_account_info(account_info_iter)?;
+    let collection_metadata_info = next_account_info(account_info_iter)?;
 
     // Validate Accounts
     assert_signer(authority_info)?;
@@ -31,25 +38,74 @@ pub fn update_state(
     // Ensure the authority matches
     incoming_collection_authority_matches_stored(authority_info, &migration_state)?;
 
-    // If given a collection_size, update the state.
-    if let Some(collection_size) = collection_size {
-        migration_state.collection_info.size = collection_size;
+    let collection_metadata =
+        collection_metadata_matches_stored(collection_metadata_info, &migration_state)?;
+
+    // If given a collection_size, update the state. Sized collections read their size
+    // from the collection metadata, even before `Start` records them as sized, so a
+    // conflicting manual size is refused.
+    if let Some(new_collection_size) = new_collection_size {
+        if sized_collection_size(&collection_metadata)?.is_some()
+            && new_collection_size
+                != collection_size(&collection_metadata, migration_state.collection_info.size)?
+        {
+            return Err(MigrationError::CollectionSizeMismatch.into());
+        }
+        migration_state.collection_info.size = new_collection_size;
     }
 
-    // Only allow updating rule set or update authority if no items have been migrated yet.
+    // Only allow updating rule set, update authority, delegate handling, collection
+    // parent order or update authority mode if no items have been migrated yet.
 
     if let Some(rule_set) = rule_set {
-        if migration_state.status.items_migrated > 0 {
+        if migration_state.status.has_migrated() {
             return Err(MigrationError::MigrationInProgress.into());
         }
         migration_state.collection_info.rule_set = rule_set;
     }
 
+    // The new authority only takes over once it accepts the handoff, so a mistyped key
+    // cannot lock the migration state. Proposing the default pubkey cancels a handoff.
     if let Some(new_update_authority) = new_update_authority {
-        if migration_state.status.items_migrated > 0 {
+        if migration_state.status.has_migrated() {
             return Err(MigrationError::MigrationInProgress.into());
         }
-        migration_state.collection_info.authority = new_update_authority;
+        migration_state.pending_authority = new_update_authority;
+        migration_state.pending_authority_expiry = if new_update_authority == Pubkey::default() {
+            0
+        } else {
+            Clock::get()?
+                .unix_timestamp
+                .checked_add(AUTHORITY_HANDOFF_PERIOD)
+                .ok_or(MigrationError::Overflow)?
+        };
+    }
+
+    if let Some(delegate_handling) = delegate_handling {
+        if migration_state.status.has_migrated() {
+            return Err(MigrationError::MigrationInProgress.into());
+        }
+        migration_state.delegate_handling = delegate_handling;
+    }
+
+    if let Some(collection_parent_order) = collection_parent_order {
+        if migration_state.status.has_migrated() {
+            return Err(MigrationError::MigrationInProgress.into());
+        }
+        migration_state.collection_parent_order = collection_parent_order;
+    }
+
+    if let Some(update_authority_mode) = update_authority_mode {
+        if migration_state.status.has_migrated() {
+            return Err(MigrationError::MigrationInProgress.into());
+        }
+        migration_state.update_authority_mode = update_authority_mode;
+    }
+
+    // Items that can never be migrated would otherwise hold back a collection parent
+    // ordered last, so it can be released at any point of the migration.
+    if let Some(release_collection_parent) = release_collection_parent {
+        migration_state.collection_parent_released = release_collection_parent;
     }
 
     // Perform a time check to check eligibility for migration
@@ -0,0 +1,48 @@
+use crate::{state::MIGRATION_STATE_ACCOUNT_LEN, utils::resize_program_account};
+
+use super::*;
+
+pub fn upgrade_state<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>]) -> ProgramResult {
+    // Fetch accounts
+    let account_info_iter = &mut accounts.iter();
+    let payer_info = next_account_info(account_info_iter)?;
+    let migration_state_info = next_account_info(account_info_iter)?;
+    let system_program_info = next_account_info(account_info_iter)?;
+
+    // Validate Accounts
+    assert_signer(payer_info)?;
+
+    if system_program_info.key != &solana_program::system_program::ID {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    assert_owned_by(
+        migration_state_info,
+        program_id,
+        MigrationError::IncorrectMigrationStateProgramOwner,
+    )?;
+
+    // Deserialize the migration state, converting legacy layouts to the current one.
+    let mut migration_state = MigrationState::from_account_info(migration_state_info)?;
+
+    // Legacy layouts have no bump, so it is recorded when upgrading.
+    migration_state.bump = assert_derivation(
+        program_id,
+        migration_state_info,
+        &[b"migration", migration_state.collection_info.mint.as_ref()],
+        MigrationError::InvalidMigrationStateDerivation,
+    )?;
+
+    // Resize the account to the current layout plus the reserved region, with the payer
+    // covering any extra rent.
+    resize_program_account(
+        migration_state_info,
+        payer_info,
+        system_program_info,
+        MIGRATION_STATE_ACCOUNT_LEN,
+    )?;
+
+    migration_state.save(migration_state_info)?;
+
+    Ok(())
+}
@@ -1,46 +1,36 @@
+use mpl_token_metadata::pda::COLLECTION_AUTHORITY;
+
+use crate::utils::{assert_derivation_with_bump, UNKNOWN_BUMP};
+
 use super::*;
 
-pub(crate) fn metadata_belongs_to_mint(
-    metadata: &Metadata,
-    mint: &Pubkey,
-) -> Result<(), ProgramError> {
-    if metadata.mint != *mint {
-        return Err(MigrationError::MetadataMintMistmatch.into());
+/// Returns the size stored on a sized collection's metadata, if it has one.
+pub(crate) fn sized_collection_size(
+    collection_metadata: &Metadata,
+) -> Result<Option<u32>, ProgramError> {
+    match collection_metadata.collection_details {
+        Some(CollectionDetails::V1 { size }) => Ok(Some(
+            u32::try_from(size).map_err(|_| MigrationError::Overflow)?,
+        )),
+        None => Ok(None),
     }
-    Ok(())
 }
 
-pub(crate) fn update_authority_matches(
-    metadata: &Metadata,
-    authority: &Pubkey,
-) -> Result<(), ProgramError> {
-    if metadata.update_authority != *authority {
-        return Err(MigrationError::InvalidAuthority.into());
-    }
-    Ok(())
-}
-
-pub(crate) fn verified_collection_member(
-    item_metadata: &Metadata,
-    collection_mint_pubkey: &Pubkey,
-) -> Result<(), ProgramError> {
-    if item_metadata.collection.is_none() {
-        return Err(MigrationError::CollectionNotFound.into());
-    }
-
-    let collection = item_metadata.collection.as_ref().unwrap();
-
-    if !collection.verified || collection.key != *collection_mint_pubkey {
-        return Err(MigrationError::NotCollectionMember.into());
-    }
-    Ok(())
+/// Returns the collection size used to decide completion: the on-chain size for sized
+/// collections and the manually set size stored on the migration state otherwise.
+pub(crate) fn collection_size(
+    collection_metadata: &Metadata,
+    stored_size: u32,
+) -> Result<u32, ProgramError> {
+    Ok(sized_collection_size(collection_metadata)?.unwrap_or(stored_size))
 }
 
 pub(crate) fn metadata_derived_from_mint(
     metadata_info: &AccountInfo,
     mint_info: &AccountInfo,
+    bump: u8,
 ) -> Result<(), ProgramError> {
-    assert_derivation(
+    assert_derivation_with_bump(
         &mpl_token_metadata::ID,
         metadata_info,
         &[
@@ -48,6 +38,7 @@ pub(crate) fn metadata_derived_from_mint(
             mpl_token_metadata::ID.as_ref(),
             mint_info.key.as_ref(),
         ],
+        bump,
         MigrationError::MetadataMintMistmatch,
     )?;
     Ok(())
@@ -56,8 +47,9 @@ pub(crate) fn metadata_derived_from_mint(
 pub(crate) fn edition_derived_from_mint(
     edition_info: &AccountInfo,
     mint_info: &AccountInfo,
+    bump: u8,
 ) -> Result<(), ProgramError> {
-    assert_derivation(
+    assert_derivation_with_bump(
         &mpl_token_metadata::ID,
         edition_info,
         &[
@@ -66,6 +58,7 @@ pub(crate) fn edition_derived_from_mint(
             mint_info.key.as_ref(),
             EDITION.as_bytes(),
         ],
+        bump,
         MigrationError::InvalidEditionDerivation,
     )?;
     Ok(())
@@ -74,24 +67,110 @@ pub(crate) fn edition_derived_from_mint(
 pub(crate) fn migration_state_derived_from_mint(
     migration_state_info: &AccountInfo,
     mint_info: &AccountInfo,
-) -> Result<(), ProgramError> {
-    assert_derivation(
+    bump: u8,
+) -> Result<u8, ProgramError> {
+    assert_derivation_with_bump(
         &crate::ID,
         migration_state_info,
         &[b"migration", mint_info.key.as_ref()],
+        bump,
         MigrationError::InvalidMigrationStateDerivation,
-    )?;
-    Ok(())
+    )
 }
 
-pub(crate) fn token_belongs_to_mint(
-    token: &TokenAccount,
+pub(crate) fn delegate_record_derived_from_mint(
+    delegate_record_info: &AccountInfo,
     mint_pubkey: &Pubkey,
-) -> Result<(), ProgramError> {
-    if token.mint != *mint_pubkey {
-        return Err(MigrationError::TokenMintMismatch.into());
+    delegate_pubkey: &Pubkey,
+    bump: u8,
+) -> Result<u8, ProgramError> {
+    assert_derivation_with_bump(
+        &mpl_token_metadata::ID,
+        delegate_record_info,
+        &[
+            PREFIX.as_bytes(),
+            mpl_token_metadata::ID.as_ref(),
+            mint_pubkey.as_ref(),
+            COLLECTION_AUTHORITY.as_bytes(),
+            delegate_pubkey.as_ref(),
+        ],
+        bump,
+        MigrationError::InvalidDelegateRecordDerivation,
+    )
+}
+
+pub(crate) fn collection_signer_derived_from_mint(
+    program_signer_info: &AccountInfo,
+    mint_pubkey: &Pubkey,
+    bump: u8,
+) -> Result<u8, ProgramError> {
+    assert_derivation_with_bump(
+        &crate::ID,
+        program_signer_info,
+        &[b"signer", mint_pubkey.as_ref()],
+        bump,
+        MigrationError::InvalidSignerDerivation,
+    )
+}
+
+pub(crate) fn owner_program_list_derived_from_mint(
+    owner_program_list_info: &AccountInfo,
+    mint_pubkey: &Pubkey,
+    bump: u8,
+) -> Result<u8, ProgramError> {
+    assert_derivation_with_bump(
+        &crate::ID,
+        owner_program_list_info,
+        &[b"owner_program_list", mint_pubkey.as_ref()],
+        bump,
+        MigrationError::InvalidOwnerProgramListDerivation,
+    )
+}
+
+pub(crate) fn bounty_vault_derived_from_mint(
+    bounty_vault_info: &AccountInfo,
+    mint_pubkey: &Pubkey,
+    bump: u8,
+) -> Result<u8, ProgramError> {
+    assert_derivation_with_bump(
+        &crate::ID,
+        bounty_vault_info,
+        &[b"bounty", mint_pubkey.as_ref()],
+        bump,
+        MigrationError::InvalidBountyVaultDerivation,
+    )
+}
+
+pub(crate) fn payer_record_derived_from_mint(
+    payer_record_info: &AccountInfo,
+    mint_pubkey: &Pubkey,
+    payer_pubkey: &Pubkey,
+    bump: u8,
+) -> Result<u8, ProgramError> {
+    assert_derivation_with_bump(
+        &crate::ID,
+        payer_record_info,
+        &[b"payer", mint_pubkey.as_ref(), payer_pubkey.as_ref()],
+        bump,
+        MigrationError::InvalidPayerRecordDerivation,
+    )
+}
+
+/// New payer records are only created at the canonical address, so each payer has a single
+/// record per collection. Returns the canonical bump.
+pub(crate) fn new_payer_record_derived_from_mint(
+    payer_record_info: &AccountInfo,
+    mint_pubkey: &Pubkey,
+    payer_pubkey: &Pubkey,
+    bump: u8,
+) -> Result<u8, ProgramError> {
+    let canonical_bump =
+        payer_record_derived_from_mint(payer_record_info, mint_pubkey, payer_pubkey, UNKNOWN_BUMP)?;
+
+    if bump != UNKNOWN_BUMP && bump != canonical_bump {
+        return Err(MigrationError::InvalidPayerRecordDerivation.into());
     }
-    Ok(())
+    Ok(canonical_bump)
 }
 
 pub(crate) fn incoming_collection_mint_matches_stored(
@@ -114,19 +193,32 @@ pub(crate) fn incoming_collection_authority_matches_stored(
     Ok(())
 }
 
-pub(crate) fn incoming_auth_rules_matches_stored(
-    auth_rules_info: &AccountInfo,
+/// Loads the collection parent's metadata, which must belong to the stored collection mint.
+pub(crate) fn collection_metadata_matches_stored(
+    collection_metadata_info: &AccountInfo,
     migration_state: &MigrationState,
-) -> Result<(), ProgramError> {
-    if migration_state.collection_info.rule_set != *auth_rules_info.key {
-        return Err(MigrationError::InvalidRuleSet.into());
+) -> Result<Metadata, ProgramError> {
+    assert_owned_by(
+        collection_metadata_info,
+        &mpl_token_metadata::ID,
+        MigrationError::IncorrectMetadataProgramOwner,
+    )?;
+
+    let collection_metadata = Metadata::from_account_info(collection_metadata_info)?;
+
+    if collection_metadata.mint != migration_state.collection_info.mint {
+        return Err(MigrationError::MetadataMintMistmatch.into());
     }
-    Ok(())
+
+    Ok(collection_metadata)
 }
 
-pub(crate) fn token_owned_by(token: &TokenAccount, owner: &Pubkey) -> Result<(), ProgramError> {
-    if token.owner != *owner {
-        return Err(MigrationError::TokenOwnerMismatch.into());
+pub(crate) fn incoming_auth_rules_matches_stored(
+    auth_rules_info: &AccountInfo,
+    stored_rule_set: &Pubkey,
+) -> Result<(), ProgramError> {
+    if stored_rule_set != auth_rules_info.key {
+        return Err(MigrationError::InvalidRuleSet.into());
     }
     Ok(())
 }
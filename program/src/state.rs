@@ -4,7 +4,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankAccount;
 use solana_program::{
     account_info::AccountInfo, program_error::ProgramError, program_memory::sol_memcpy, pubkey,
-    pubkey::Pubkey,
+    pubkey::Pubkey, system_program,
 };
 
 #[cfg(feature = "serde-feature")]
@@ -17,13 +17,105 @@ use crate::errors::MigrationError;
 
 pub(crate) const SPL_TOKEN_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
 
+/// Size of the original, unversioned migration state layout.
+pub const MIGRATION_STATE_V0_LEN: usize = 147;
+
+/// Size of the current migration state layout.
+pub const MIGRATION_STATE_LEN: usize = 259;
+
+/// Allocated size of a migration state account. The bytes after the current layout are
+/// reserved and kept zeroed, so fields appended later read as zero from existing accounts
+/// instead of needing a new `Key` version and a resize.
+pub const MIGRATION_STATE_ACCOUNT_LEN: usize = 320;
+
+const _: () = assert!(MIGRATION_STATE_LEN <= MIGRATION_STATE_ACCOUNT_LEN);
+
+/// Size of the original, unversioned program signer layout.
+pub const PROGRAM_SIGNER_V0_LEN: usize = 1;
+
+pub const PROGRAM_SIGNER_LEN: usize = 2;
+
+/// Seconds a proposed authority has to accept the handoff.
+pub const AUTHORITY_HANDOFF_PERIOD: i64 = 60 * 60 * 24 * 7;
+
+/// Seconds without a migration after which the authority can reclaim the bounty of a
+/// migration that has not completed.
+pub const BOUNTY_RECLAIM_PERIOD: i64 = 60 * 60 * 24 * 30;
+
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug)]
+pub enum Key {
+    Uninitialized,
+    MigrationStateV1,
+    ProgramSigner,
+    OwnerProgramList,
+    PayerRecord,
+}
+
+/// Accounts owned by this program, identified by a leading `Key` discriminator.
+pub trait MigrationAccount: BorshDeserialize {
+    fn key() -> Key;
+
+    /// Error returned when the account data has a different key.
+    fn key_error() -> MigrationError;
+
+    /// Error returned when the account data fails to deserialize.
+    fn deserialize_error() -> MigrationError;
+
+    fn safe_deserialize(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.first() != Some(&(Self::key() as u8)) {
+            return Err(Self::key_error().into());
+        }
+
+        let account = Self::deserialize(&mut &data[..]).map_err(|_| Self::deserialize_error())?;
+
+        Ok(account)
+    }
+}
+
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
 // Seeds: [b"migration", collection_mint.as_ref()]
+// New fields are appended at the end, taking bytes from the reserved region, and must treat
+// zero as their default.
 pub struct MigrationState {
+    pub key: Key,
     pub collection_info: CollectionInfo,
     pub unlock_method: UnlockMethod,
     pub status: MigrationStatus,
+    pub delegate_handling: DelegateHandling,
+    pub collection_parent_order: CollectionParentOrder,
+
+    /// Lamports paid from the bounty vault to the payer of each migration.
+    pub bounty_reward: u64,
+
+    /// Authority proposed by the current authority, or the default pubkey if none.
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<DisplayFromStr>"))]
+    pub pending_authority: Pubkey,
+    /// Unix timestamp after which the pending authority can no longer accept.
+    pub pending_authority_expiry: i64,
+
+    pub update_authority_mode: UpdateAuthorityMode,
+
+    /// Bump of the migration state PDA, or 0 if not yet recorded.
+    pub bump: u8,
+    /// Bump of the delegate record set up by `Start`, or 0 if not yet recorded.
+    pub delegate_record_bump: u8,
+
+    /// Whether the authority let the collection parent migrate before every item has been
+    /// migrated, when it is ordered last.
+    pub collection_parent_released: bool,
+
+    /// Total lamports paid by payers for the rent of their payer records, which
+    /// `ClosePayerRecord` returns once every item has been migrated.
+    pub payer_record_rent_paid: u64,
+
+    /// Bump of the collection signer PDA, or 0 if not yet recorded.
+    pub collection_signer_bump: u8,
+    /// Bump of the owner program list PDA, or 0 if not yet recorded.
+    pub owner_program_list_bump: u8,
+    /// Bump of the bounty vault PDA, or 0 if not yet recorded.
+    pub bounty_vault_bump: u8,
 }
 
 /// See if a slice contains all zeroes.  Useful for checking an account's data.
@@ -42,16 +134,29 @@ impl MigrationState {
     pub fn from_account_info(a: &AccountInfo) -> Result<Self, ProgramError> {
         let data = a.try_borrow_data()?;
 
+        Self::from_data(&data)
+    }
+
+    /// Deserializes the migration state from raw account data, converting legacy layouts.
+    pub fn from_data(data: &[u8]) -> Result<Self, ProgramError> {
         if data.is_empty() {
             return Err(MigrationError::EmptyMigrationState.into());
         }
 
-        if is_zeroed_chunks(&data) {
+        if is_zeroed_chunks(data) {
             return Err(MigrationError::ZeroedMigrationState.into());
         }
 
-        let ua = <Self as BorshDeserialize>::deserialize(&mut data.as_ref())
-            .map_err(|_| MigrationError::InvalidMigrationState)?;
+        // Accounts created before the layout was versioned have no key, so they
+        // are identified by their size and converted to the current layout.
+        if data.len() == MIGRATION_STATE_V0_LEN {
+            let legacy = MigrationStateV0::deserialize(&mut &data[..])
+                .map_err(|_| MigrationError::InvalidMigrationState)?;
+
+            return Ok(legacy.into());
+        }
+
+        let ua = Self::safe_deserialize(data)?;
 
         Ok(ua)
     }
@@ -60,6 +165,11 @@ impl MigrationState {
         let serialized_data = self.try_to_vec()?;
         let data_len = serialized_data.len();
 
+        // Legacy accounts must be reallocated with UpgradeState before they can be written.
+        if data_len > a.data_len() {
+            return Err(MigrationError::MigrationStateNotUpgraded.into());
+        }
+
         sol_memcpy(
             &mut a.data.borrow_mut(),
             serialized_data.as_slice(),
@@ -70,12 +180,40 @@ impl MigrationState {
     }
 }
 
+impl MigrationAccount for MigrationState {
+    fn key() -> Key {
+        Key::MigrationStateV1
+    }
+
+    fn key_error() -> MigrationError {
+        MigrationError::InvalidMigrationStateKey
+    }
+
+    fn deserialize_error() -> MigrationError {
+        MigrationError::InvalidMigrationState
+    }
+}
+
 impl Default for MigrationState {
     fn default() -> Self {
         Self {
+            key: Key::MigrationStateV1,
             collection_info: CollectionInfo::default(),
             unlock_method: UnlockMethod::Timed,
             status: MigrationStatus::default(),
+            delegate_handling: DelegateHandling::CarryOver,
+            collection_parent_order: CollectionParentOrder::Last,
+            bounty_reward: 0,
+            pending_authority: Pubkey::default(),
+            pending_authority_expiry: 0,
+            update_authority_mode: UpdateAuthorityMode::Match,
+            bump: 0,
+            delegate_record_bump: 0,
+            collection_parent_released: false,
+            payer_record_rent_paid: 0,
+            collection_signer_bump: 0,
+            owner_program_list_bump: 0,
+            bounty_vault_bump: 0,
         }
     }
 }
@@ -96,6 +234,9 @@ pub struct CollectionInfo {
     pub delegate_record: Pubkey,
 
     pub size: u32,
+
+    /// Whether the size is read from the collection's on-chain `CollectionDetails`.
+    pub is_sized: bool,
 }
 
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
@@ -105,6 +246,38 @@ pub struct MigrationStatus {
     pub is_locked: bool,
     pub in_progress: bool,
     pub items_migrated: u32,
+    pub collection_parent_migrated: bool,
+
+    /// Total lamports paid by payers for the rent of created token records.
+    pub token_record_rent_paid: u64,
+    /// Number of distinct payers that have migrated an item.
+    ///
+    /// Payers are counted when their payer record is created. A payer whose record was
+    /// closed by `ClosePayerRecord` is counted again if they pay for another migration,
+    /// such as the collection parent when it is migrated last, so this can over-count.
+    pub unique_payers: u32,
+    pub first_migration_slot: u64,
+    pub last_migration_slot: u64,
+    pub first_migration_timestamp: i64,
+    pub last_migration_timestamp: i64,
+}
+
+impl MigrationStatus {
+    /// Whether any item, including the collection parent, has been migrated.
+    pub fn has_migrated(&self) -> bool {
+        self.items_migrated > 0 || self.collection_parent_migrated
+    }
+
+    /// Records the slot and timestamp of a migration.
+    pub fn record_migration_time(&mut self, slot: u64, timestamp: i64) {
+        if !self.has_migrated() {
+            self.first_migration_slot = slot;
+            self.first_migration_timestamp = timestamp;
+        }
+
+        self.last_migration_slot = slot;
+        self.last_migration_timestamp = timestamp;
+    }
 }
 
 #[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
@@ -114,6 +287,38 @@ pub enum UnlockMethod {
     Vote,
 }
 
+/// How items with an SPL token delegate or a frozen token account are migrated.
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug)]
+pub enum DelegateHandling {
+    /// Carry the SPL token delegate over to a Migration delegate on the token record.
+    CarryOver,
+    /// Reject items that have an SPL token delegate or are frozen.
+    Reject,
+}
+
+/// When the collection parent NFT is migrated relative to its items.
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug)]
+pub enum CollectionParentOrder {
+    /// The collection parent must be migrated before any item.
+    First,
+    /// The collection parent can only be migrated once every item has been migrated, or
+    /// once the authority releases it.
+    Last,
+}
+
+/// Which items' update authorities are accepted for migration.
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug)]
+pub enum UpdateAuthorityMode {
+    /// Items must have the same update authority as the collection.
+    Match,
+    /// Items with any update authority are migrated through their verified collection
+    /// and the collection's delegate.
+    VerifiedCollection,
+}
+
 impl FromStr for UnlockMethod {
     type Err = MigrationError;
 
@@ -126,8 +331,79 @@ impl FromStr for UnlockMethod {
     }
 }
 
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+// Seeds: [b"owner_program_list", collection_mint.as_ref()]
+pub struct OwnerProgramList {
+    pub key: Key,
+    pub list_type: ListType,
+
+    #[cfg_attr(feature = "serde-feature", serde(with = "As::<Vec<DisplayFromStr>>"))]
+    pub programs: Vec<Pubkey>,
+}
+
+impl OwnerProgramList {
+    pub fn from_account_info(a: &AccountInfo) -> Result<Self, ProgramError> {
+        let data = a.try_borrow_data()?;
+
+        let list = Self::safe_deserialize(&data)?;
+
+        Ok(list)
+    }
+
+    pub fn save(&self, a: &AccountInfo) -> Result<(), ProgramError> {
+        let serialized_data = self.try_to_vec()?;
+        let data_len = serialized_data.len();
+
+        sol_memcpy(
+            &mut a.data.borrow_mut(),
+            serialized_data.as_slice(),
+            data_len,
+        );
+
+        Ok(())
+    }
+
+    /// Whether items held by accounts owned by `program` may be migrated.
+    pub fn allows(&self, program: &Pubkey) -> bool {
+        let listed = self.programs.contains(program);
+
+        match self.list_type {
+            // Allow lists restrict which programs can hold items, so wallets are always allowed.
+            ListType::Allow => listed || program == &system_program::ID,
+            ListType::Deny => !listed,
+        }
+    }
+}
+
+impl MigrationAccount for OwnerProgramList {
+    fn key() -> Key {
+        Key::OwnerProgramList
+    }
+
+    fn key_error() -> MigrationError {
+        MigrationError::InvalidOwnerProgramListKey
+    }
+
+    fn deserialize_error() -> MigrationError {
+        MigrationError::InvalidOwnerProgramList
+    }
+}
+
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug)]
+pub enum ListType {
+    /// Only items held by wallets, owned by the System Program, or by the listed programs
+    /// are migrated.
+    Allow,
+    /// Items held by the listed programs are not migrated. Listing the System Program
+    /// excludes items held by wallets.
+    Deny,
+}
+
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
 pub struct ProgramSigner {
+    pub key: Key,
     pub bump: u8,
 }
 
@@ -139,9 +415,136 @@ impl ProgramSigner {
             return Err(MigrationError::EmptyProgramSigner.into());
         }
 
-        let ua = Self::deserialize(&mut data.as_ref())
-            .map_err(|_| MigrationError::InvalidProgramSigner)?;
+        // The program signer was originally created with only its bump.
+        if data.len() == PROGRAM_SIGNER_V0_LEN {
+            return Ok(Self {
+                key: Key::ProgramSigner,
+                bump: data[0],
+            });
+        }
+
+        if data.len() != PROGRAM_SIGNER_LEN {
+            return Err(MigrationError::InvalidProgramSigner.into());
+        }
+
+        let ua = Self::safe_deserialize(&data)?;
 
         Ok(ua)
     }
 }
+
+impl MigrationAccount for ProgramSigner {
+    fn key() -> Key {
+        Key::ProgramSigner
+    }
+
+    fn key_error() -> MigrationError {
+        MigrationError::InvalidProgramSignerKey
+    }
+
+    fn deserialize_error() -> MigrationError {
+        MigrationError::InvalidProgramSigner
+    }
+}
+
+#[cfg_attr(feature = "serde-feature", derive(Serialize, Deserialize))]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+// Seeds: [b"payer", collection_mint.as_ref(), payer.as_ref()]
+pub struct PayerRecord {
+    pub key: Key,
+    pub bump: u8,
+}
+
+impl PayerRecord {
+    pub fn from_account_info(a: &AccountInfo) -> Result<Self, ProgramError> {
+        let data = a.try_borrow_data()?;
+
+        let record = Self::safe_deserialize(&data)?;
+
+        Ok(record)
+    }
+}
+
+impl MigrationAccount for PayerRecord {
+    fn key() -> Key {
+        Key::PayerRecord
+    }
+
+    fn key_error() -> MigrationError {
+        MigrationError::InvalidPayerRecordKey
+    }
+
+    fn deserialize_error() -> MigrationError {
+        MigrationError::InvalidPayerRecord
+    }
+}
+
+// Legacy layouts
+
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct MigrationStateV0 {
+    pub collection_info: CollectionInfoV0,
+    pub unlock_method: UnlockMethod,
+    pub status: MigrationStatusV0,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct CollectionInfoV0 {
+    pub authority: Pubkey,
+    pub mint: Pubkey,
+    pub rule_set: Pubkey,
+    pub delegate_record: Pubkey,
+    pub size: u32,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct MigrationStatusV0 {
+    pub unlock_time: i64,
+    pub is_locked: bool,
+    pub in_progress: bool,
+    pub items_migrated: u32,
+}
+
+impl From<MigrationStateV0> for MigrationState {
+    fn from(legacy: MigrationStateV0) -> Self {
+        let MigrationStateV0 {
+            collection_info,
+            unlock_method,
+            status,
+        } = legacy;
+
+        Self {
+            key: Key::MigrationStateV1,
+            collection_info: CollectionInfo {
+                authority: collection_info.authority,
+                mint: collection_info.mint,
+                rule_set: collection_info.rule_set,
+                delegate_record: collection_info.delegate_record,
+                size: collection_info.size,
+                is_sized: false,
+            },
+            unlock_method,
+            status: MigrationStatus {
+                unlock_time: status.unlock_time,
+                is_locked: status.is_locked,
+                in_progress: status.in_progress,
+                items_migrated: status.items_migrated,
+                collection_parent_migrated: false,
+                ..MigrationStatus::default()
+            },
+            delegate_handling: DelegateHandling::CarryOver,
+            collection_parent_order: CollectionParentOrder::Last,
+            bounty_reward: 0,
+            pending_authority: Pubkey::default(),
+            pending_authority_expiry: 0,
+            update_authority_mode: UpdateAuthorityMode::Match,
+            bump: 0,
+            delegate_record_bump: 0,
+            collection_parent_released: false,
+            payer_record_rent_paid: 0,
+            collection_signer_bump: 0,
+            owner_program_list_bump: 0,
+            bounty_vault_bump: 0,
+        }
+    }
+}
@@ -1,34 +1,103 @@
 use mpl_token_metadata::{
-    assertions::collection::assert_is_collection_delegated_authority,
-    state::{CollectionAuthorityRecord, Metadata},
+    pda::COLLECTION_AUTHORITY,
+    state::{CollectionAuthorityRecord, Metadata, PREFIX},
 };
+use mpl_utils::assert_derivation;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
-    pubkey::Pubkey, system_program,
+    account_info::AccountInfo, entrypoint::ProgramResult, program::invoke,
+    program_error::ProgramError, pubkey::Pubkey, rent::Rent, system_instruction, system_program,
+    sysvar::Sysvar,
 };
 
-use crate::{errors::MigrationError, state::MigrationState, PROGRAM_SIGNER};
+use crate::{errors::MigrationError, view::MigrationStateView, PROGRAM_SIGNER};
 
-pub fn assert_valid_delegate(
+/// Bump value meaning the bump is not known, so it is searched for.
+pub const UNKNOWN_BUMP: u8 = 0;
+
+/// Like `assert_derivation`, but checks the address against a known bump with
+/// `create_program_address` instead of searching for the bump. Returns the bump.
+pub fn assert_derivation_with_bump(
+    program_id: &Pubkey,
+    account_info: &AccountInfo,
+    seeds: &[&[u8]],
+    bump: u8,
+    error: MigrationError,
+) -> Result<u8, ProgramError> {
+    if bump == UNKNOWN_BUMP {
+        return assert_derivation(program_id, account_info, seeds, error);
+    }
+
+    assert_key_derivation(program_id, account_info.key, seeds, bump, error)
+}
+
+/// Checks that `key` is the address derived from the seeds and the given bump.
+pub fn assert_key_derivation(
+    program_id: &Pubkey,
+    key: &Pubkey,
+    seeds: &[&[u8]],
+    bump: u8,
+    error: MigrationError,
+) -> Result<u8, ProgramError> {
+    let bump_seed = [bump];
+    let mut seeds = seeds.to_vec();
+    seeds.push(&bump_seed);
+
+    match Pubkey::create_program_address(&seeds, program_id) {
+        Ok(derived) if derived == *key => Ok(bump),
+        _ => Err(error.into()),
+    }
+}
+
+/// Checks the delegate and its record. `signer_bump` is the bump of the collection
+/// signer, which is not searched for again.
+pub fn assert_valid_delegate<T: AsRef<[u8]>>(
     delegate_pubkey: &Pubkey,
+    signer_bump: u8,
     delegate_record_info: &AccountInfo,
     collection_metadata: &Metadata,
-    migration_state: &MigrationState,
+    migration_state: &MigrationStateView<T>,
 ) -> Result<(), ProgramError> {
-    let info = &migration_state.collection_info;
+    let mint = migration_state.mint();
+
+    // The delegate is either the collection's own signer or, for migrations started
+    // before collections had one, the global program signer.
+    if delegate_pubkey != &PROGRAM_SIGNER {
+        assert_key_derivation(
+            &crate::ID,
+            delegate_pubkey,
+            &[b"signer", mint.as_ref()],
+            signer_bump,
+            MigrationError::InvalidSignerDerivation,
+        )?;
+    }
+
+    // Only the delegate record set up when the migration was started can be used.
+    if delegate_record_info.key != &migration_state.delegate_record() {
+        return Err(MigrationError::InvalidDelegate.into());
+    }
+
     // Mint is the correct one for the metadata account.
-    if collection_metadata.mint != info.mint {
+    if collection_metadata.mint != mint {
         return Err(MigrationError::MetadataMintMistmatch.into());
     }
 
-    if collection_metadata.update_authority != info.authority {
+    if collection_metadata.update_authority != migration_state.authority() {
         return Err(MigrationError::InvalidAuthority.into());
     }
 
-    let bump = assert_is_collection_delegated_authority(
+    // The bump recorded by Start saves searching for it on every migration.
+    let bump = assert_derivation_with_bump(
+        &mpl_token_metadata::ID,
         delegate_record_info,
-        delegate_pubkey,
-        &info.mint,
+        &[
+            PREFIX.as_bytes(),
+            mpl_token_metadata::ID.as_ref(),
+            mint.as_ref(),
+            COLLECTION_AUTHORITY.as_bytes(),
+            delegate_pubkey.as_ref(),
+        ],
+        migration_state.delegate_record_bump(),
+        MigrationError::InvalidDelegate,
     )?;
 
     let data = delegate_record_info.try_borrow_data()?;
@@ -72,18 +141,89 @@ pub fn close_program_account<'a>(
     Ok(())
 }
 
+pub fn resize_program_account<'a>(
+    account_info: &AccountInfo<'a>,
+    payer_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    new_len: usize,
+) -> ProgramResult {
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let current_lamports = account_info.lamports();
+
+    // Top up the account from the payer if it needs more rent, otherwise
+    // return the excess to the payer.
+    if required_lamports > current_lamports {
+        invoke(
+            &system_instruction::transfer(
+                payer_info.key,
+                account_info.key,
+                required_lamports - current_lamports,
+            ),
+            &[
+                payer_info.clone(),
+                account_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    } else if current_lamports > required_lamports {
+        let excess_lamports = current_lamports - required_lamports;
+        **account_info.lamports.borrow_mut() = required_lamports;
+        **payer_info.lamports.borrow_mut() = payer_info
+            .lamports()
+            .checked_add(excess_lamports)
+            .ok_or(MigrationError::Overflow)?;
+    }
+
+    // Zero any bytes that were in use before a shrink, so reserved regions stay zeroed.
+    account_info.realloc(new_len, true)?;
+
+    Ok(())
+}
+
 pub fn find_migration_state_pda(mint: &Pubkey) -> (Pubkey, u8) {
     let seeds = &[b"migration", mint.as_ref()];
     Pubkey::find_program_address(seeds, &crate::ID)
 }
 
+/// Finds the program signer delegated to migrate items of the collection.
+pub fn find_collection_signer_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    let seeds = &[b"signer", mint.as_ref()];
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
 pub fn find_delegate_record_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    let (collection_signer, _) = find_collection_signer_pda(mint);
+    find_collection_delegate_record_pda(mint, &collection_signer)
+}
+
+/// Finds the delegate record of the global `PROGRAM_SIGNER` used before
+/// collections had their own signer.
+pub fn find_legacy_delegate_record_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    find_collection_delegate_record_pda(mint, &PROGRAM_SIGNER)
+}
+
+fn find_collection_delegate_record_pda(mint: &Pubkey, delegate: &Pubkey) -> (Pubkey, u8) {
     let seeds = &[
         mpl_token_metadata::state::PREFIX.as_bytes(),
         mpl_token_metadata::ID.as_ref(),
         mint.as_ref(),
         mpl_token_metadata::pda::COLLECTION_AUTHORITY.as_bytes(),
-        PROGRAM_SIGNER.as_ref(),
+        delegate.as_ref(),
     ];
     Pubkey::find_program_address(seeds, &mpl_token_metadata::ID)
 }
+
+pub fn find_owner_program_list_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    let seeds = &[b"owner_program_list", mint.as_ref()];
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+pub fn find_bounty_vault_pda(mint: &Pubkey) -> (Pubkey, u8) {
+    let seeds = &[b"bounty", mint.as_ref()];
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
+
+pub fn find_payer_record_pda(mint: &Pubkey, payer: &Pubkey) -> (Pubkey, u8) {
+    let seeds = &[b"payer", mint.as_ref(), payer.as_ref()];
+    Pubkey::find_program_address(seeds, &crate::ID)
+}
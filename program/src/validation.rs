@@ -0,0 +1,235 @@
+//! Checks on decoded accounts, shared by the program and off-chain tooling.
+//!
+//! Every check returns the same `MigrationError` the program fails with, so clients can
+//! predict the outcome of a migration without sending it.
+
+use mpl_token_metadata::state::{Metadata, TokenStandard};
+use solana_program::{
+    bpf_loader_upgradeable::UpgradeableLoaderState, program_option::COption, pubkey::Pubkey,
+};
+use spl_token::state::{Account, AccountState, Mint};
+
+use crate::{
+    errors::MigrationError,
+    state::{DelegateHandling, OwnerProgramList},
+};
+
+pub fn metadata_belongs_to_mint(metadata: &Metadata, mint: &Pubkey) -> Result<(), MigrationError> {
+    if metadata.mint != *mint {
+        return Err(MigrationError::MetadataMintMistmatch);
+    }
+    Ok(())
+}
+
+pub fn update_authority_matches(
+    metadata: &Metadata,
+    authority: &Pubkey,
+) -> Result<(), MigrationError> {
+    if metadata.update_authority != *authority {
+        return Err(MigrationError::InvalidAuthority);
+    }
+    Ok(())
+}
+
+pub fn verified_collection_member(
+    item_metadata: &Metadata,
+    collection_mint_pubkey: &Pubkey,
+) -> Result<(), MigrationError> {
+    let collection = item_metadata
+        .collection
+        .as_ref()
+        .ok_or(MigrationError::CollectionNotFound)?;
+
+    if !collection.verified || collection.key != *collection_mint_pubkey {
+        return Err(MigrationError::NotCollectionMember);
+    }
+    Ok(())
+}
+
+pub fn token_belongs_to_mint(token: &Account, mint_pubkey: &Pubkey) -> Result<(), MigrationError> {
+    if token.mint != *mint_pubkey {
+        return Err(MigrationError::TokenMintMismatch);
+    }
+    Ok(())
+}
+
+pub fn token_owned_by(token: &Account, owner: &Pubkey) -> Result<(), MigrationError> {
+    if token.owner != *owner {
+        return Err(MigrationError::TokenOwnerMismatch);
+    }
+    Ok(())
+}
+
+/// The token owner program buffer must be the program data of an upgradeable token owner
+/// program. `program_state` is the token owner program's data, if it is a loader state.
+pub fn token_owner_program_buffer_matches(
+    program_state: Option<&UpgradeableLoaderState>,
+    buffer: &Pubkey,
+) -> Result<(), MigrationError> {
+    match program_state {
+        Some(UpgradeableLoaderState::Program {
+            programdata_address,
+        }) => {
+            if programdata_address != buffer {
+                return Err(MigrationError::IncorrectTokenOwnerProgramBuffer);
+            }
+        }
+        Some(_) => return Err(MigrationError::IncorrectTokenOwnerProgramOwner),
+        None => {}
+    }
+    Ok(())
+}
+
+/// The Token Metadata edition PDA must have the freeze authority on the item.
+pub fn freeze_authority_is_edition(mint: &Mint, edition: &Pubkey) -> Result<(), MigrationError> {
+    if mint.freeze_authority != COption::Some(*edition) {
+        return Err(MigrationError::IncorrectFreezeAuthority);
+    }
+    Ok(())
+}
+
+pub fn metadata_is_mutable(metadata: &Metadata) -> Result<(), MigrationError> {
+    if !metadata.is_mutable {
+        return Err(MigrationError::ImmutableMetadata);
+    }
+    Ok(())
+}
+
+/// Only non-fungibles are migrated. Items without a token standard must have a master edition.
+pub fn token_standard_is_non_fungible(
+    metadata: &Metadata,
+    is_master_edition: bool,
+) -> Result<(), MigrationError> {
+    let is_non_fungible = match metadata.token_standard {
+        Some(token_standard) => token_standard == TokenStandard::NonFungible,
+        None => is_master_edition,
+    };
+
+    if !is_non_fungible {
+        return Err(MigrationError::IncorrectTokenStandard);
+    }
+    Ok(())
+}
+
+/// Legacy SPL token delegates and frozen token accounts are either rejected or carried over
+/// to the token record, depending on the migration setting.
+pub fn token_state_is_migratable(
+    token: &Account,
+    delegate_handling: DelegateHandling,
+) -> Result<(), MigrationError> {
+    match delegate_handling {
+        DelegateHandling::Reject => {
+            if token.delegate.is_some() || token.delegated_amount > 0 {
+                return Err(MigrationError::TokenDelegateNotAllowed);
+            }
+            if token.state == AccountState::Frozen {
+                return Err(MigrationError::FrozenTokenNotAllowed);
+            }
+        }
+        DelegateHandling::CarryOver => {
+            // The token record delegate only matches the SPL delegate if it
+            // is delegated the full amount.
+            if token.delegate.is_some() && token.delegated_amount != token.amount {
+                return Err(MigrationError::InvalidTokenDelegate);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn owner_program_allowed(
+    owner_program_list: Option<&OwnerProgramList>,
+    token_owner_program: &Pubkey,
+) -> Result<(), MigrationError> {
+    if let Some(owner_program_list) = owner_program_list {
+        if !owner_program_list.allows(token_owner_program) {
+            return Err(MigrationError::TokenOwnerProgramNotAllowed);
+        }
+    }
+    Ok(())
+}
+
+/// Items owned by programs that can no longer be upgraded are not migrated, as the program
+/// could never be updated to handle programmable NFTs.
+pub fn owner_program_is_upgradeable(
+    buffer_state: &UpgradeableLoaderState,
+) -> Result<(), MigrationError> {
+    match buffer_state {
+        UpgradeableLoaderState::ProgramData {
+            upgrade_authority_address,
+            ..
+        } => {
+            if upgrade_authority_address.is_none() {
+                return Err(MigrationError::ImmutableProgramOwner);
+            }
+        }
+        // If this isn't a ProgramData variant something is wrong.
+        _ => return Err(MigrationError::IncorrectTokenOwnerProgramBuffer),
+    }
+    Ok(())
+}
+
+/// Decoded accounts of an item, as needed by the eligibility checks.
+pub struct EligibilityContext<'a> {
+    pub metadata: &'a Metadata,
+    pub mint: &'a Mint,
+    pub edition: &'a Pubkey,
+    /// Whether the edition is a master edition of a mint with no decimals and a supply of one.
+    pub is_master_edition: bool,
+    pub token: &'a Account,
+    pub token_owner_program: &'a Pubkey,
+    /// Program data of the token owner program, if it is an upgradeable program.
+    pub token_owner_program_buffer: Option<&'a UpgradeableLoaderState>,
+    /// How the collection's migration handles delegated and frozen tokens.
+    pub delegate_handling: DelegateHandling,
+    pub owner_program_list: Option<&'a OwnerProgramList>,
+}
+
+/// The outcome of every eligibility check of an item.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EligibilityReport {
+    /// Failed checks, in the order the program runs them.
+    pub failures: Vec<MigrationError>,
+}
+
+impl EligibilityReport {
+    pub fn is_eligible(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// The error the program fails with, if any.
+    pub fn result(&self) -> Result<(), MigrationError> {
+        match self.failures.first() {
+            Some(error) => Err(*error),
+            None => Ok(()),
+        }
+    }
+
+    fn check(&mut self, result: Result<(), MigrationError>) {
+        if let Err(error) = result {
+            self.failures.push(error);
+        }
+    }
+}
+
+/// Runs every eligibility check on the item, recording each failure.
+pub fn check_eligibility(ctx: &EligibilityContext) -> EligibilityReport {
+    let mut report = EligibilityReport::default();
+
+    report.check(freeze_authority_is_edition(ctx.mint, ctx.edition));
+    report.check(metadata_is_mutable(ctx.metadata));
+    report.check(token_standard_is_non_fungible(
+        ctx.metadata,
+        ctx.is_master_edition,
+    ));
+    report.check(token_state_is_migratable(ctx.token, ctx.delegate_handling));
+    report.check(owner_program_allowed(
+        ctx.owner_program_list,
+        ctx.token_owner_program,
+    ));
+    if let Some(buffer_state) = ctx.token_owner_program_buffer {
+        report.check(owner_program_is_upgradeable(buffer_state));
+    }
+
+    report
+}
@@ -0,0 +1,277 @@
+//! Zero-copy access to the migration state.
+//!
+//! Every field of `MigrationState` has a fixed size, so its Borsh encoding has a fixed
+//! layout. `MigrationStateView` reads and writes fields at their offsets in the account
+//! data, which saves deserializing and re-serializing the whole state on the migrate path.
+
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{
+    errors::MigrationError,
+    state::{
+        CollectionParentOrder, DelegateHandling, Key, UnlockMethod, UpdateAuthorityMode,
+        MIGRATION_STATE_LEN,
+    },
+};
+
+// Offsets of the fields in the Borsh encoding of `MigrationState`.
+const KEY: usize = 0;
+const AUTHORITY: usize = KEY + 1;
+const MINT: usize = AUTHORITY + 32;
+const RULE_SET: usize = MINT + 32;
+const DELEGATE_RECORD: usize = RULE_SET + 32;
+const SIZE: usize = DELEGATE_RECORD + 32;
+const IS_SIZED: usize = SIZE + 4;
+const UNLOCK_METHOD: usize = IS_SIZED + 1;
+const UNLOCK_TIME: usize = UNLOCK_METHOD + 1;
+const IS_LOCKED: usize = UNLOCK_TIME + 8;
+const IN_PROGRESS: usize = IS_LOCKED + 1;
+const ITEMS_MIGRATED: usize = IN_PROGRESS + 1;
+const COLLECTION_PARENT_MIGRATED: usize = ITEMS_MIGRATED + 4;
+const TOKEN_RECORD_RENT_PAID: usize = COLLECTION_PARENT_MIGRATED + 1;
+const UNIQUE_PAYERS: usize = TOKEN_RECORD_RENT_PAID + 8;
+const FIRST_MIGRATION_SLOT: usize = UNIQUE_PAYERS + 4;
+const LAST_MIGRATION_SLOT: usize = FIRST_MIGRATION_SLOT + 8;
+const FIRST_MIGRATION_TIMESTAMP: usize = LAST_MIGRATION_SLOT + 8;
+const LAST_MIGRATION_TIMESTAMP: usize = FIRST_MIGRATION_TIMESTAMP + 8;
+const DELEGATE_HANDLING: usize = LAST_MIGRATION_TIMESTAMP + 8;
+const COLLECTION_PARENT_ORDER: usize = DELEGATE_HANDLING + 1;
+const BOUNTY_REWARD: usize = COLLECTION_PARENT_ORDER + 1;
+const PENDING_AUTHORITY: usize = BOUNTY_REWARD + 8;
+const PENDING_AUTHORITY_EXPIRY: usize = PENDING_AUTHORITY + 32;
+const UPDATE_AUTHORITY_MODE: usize = PENDING_AUTHORITY_EXPIRY + 8;
+const BUMP: usize = UPDATE_AUTHORITY_MODE + 1;
+const DELEGATE_RECORD_BUMP: usize = BUMP + 1;
+const COLLECTION_PARENT_RELEASED: usize = DELEGATE_RECORD_BUMP + 1;
+const PAYER_RECORD_RENT_PAID: usize = COLLECTION_PARENT_RELEASED + 1;
+const COLLECTION_SIGNER_BUMP: usize = PAYER_RECORD_RENT_PAID + 8;
+const OWNER_PROGRAM_LIST_BUMP: usize = COLLECTION_SIGNER_BUMP + 1;
+const BOUNTY_VAULT_BUMP: usize = OWNER_PROGRAM_LIST_BUMP + 1;
+
+const _: () = assert!(BOUNTY_VAULT_BUMP + 1 == MIGRATION_STATE_LEN);
+
+/// A fixed-layout view over the data of a migration state account.
+///
+/// Only accounts with the current layout can be viewed: legacy accounts must be upgraded
+/// with `UpgradeState` first.
+pub struct MigrationStateView<T> {
+    data: T,
+}
+
+impl<T: AsRef<[u8]>> MigrationStateView<T> {
+    pub fn new(data: T) -> Result<Self, ProgramError> {
+        let bytes = data.as_ref();
+
+        if bytes.len() < MIGRATION_STATE_LEN {
+            return Err(MigrationError::MigrationStateNotUpgraded.into());
+        }
+
+        if bytes[KEY] != Key::MigrationStateV1 as u8 {
+            return Err(MigrationError::InvalidMigrationStateKey.into());
+        }
+
+        Ok(Self { data })
+    }
+
+    pub fn authority(&self) -> Pubkey {
+        self.read_pubkey(AUTHORITY)
+    }
+
+    pub fn mint(&self) -> Pubkey {
+        self.read_pubkey(MINT)
+    }
+
+    pub fn rule_set(&self) -> Pubkey {
+        self.read_pubkey(RULE_SET)
+    }
+
+    pub fn delegate_record(&self) -> Pubkey {
+        self.read_pubkey(DELEGATE_RECORD)
+    }
+
+    pub fn size(&self) -> u32 {
+        u32::from_le_bytes(self.read(SIZE))
+    }
+
+    pub fn is_sized(&self) -> Result<bool, ProgramError> {
+        self.read_bool(IS_SIZED)
+    }
+
+    pub fn unlock_method(&self) -> Result<UnlockMethod, ProgramError> {
+        match self.read::<1>(UNLOCK_METHOD)[0] {
+            0 => Ok(UnlockMethod::Timed),
+            1 => Ok(UnlockMethod::Vote),
+            _ => Err(MigrationError::InvalidMigrationState.into()),
+        }
+    }
+
+    pub fn unlock_time(&self) -> i64 {
+        i64::from_le_bytes(self.read(UNLOCK_TIME))
+    }
+
+    pub fn is_locked(&self) -> Result<bool, ProgramError> {
+        self.read_bool(IS_LOCKED)
+    }
+
+    pub fn in_progress(&self) -> Result<bool, ProgramError> {
+        self.read_bool(IN_PROGRESS)
+    }
+
+    pub fn items_migrated(&self) -> u32 {
+        u32::from_le_bytes(self.read(ITEMS_MIGRATED))
+    }
+
+    pub fn collection_parent_migrated(&self) -> Result<bool, ProgramError> {
+        self.read_bool(COLLECTION_PARENT_MIGRATED)
+    }
+
+    pub fn token_record_rent_paid(&self) -> u64 {
+        u64::from_le_bytes(self.read(TOKEN_RECORD_RENT_PAID))
+    }
+
+    pub fn unique_payers(&self) -> u32 {
+        u32::from_le_bytes(self.read(UNIQUE_PAYERS))
+    }
+
+    pub fn first_migration_slot(&self) -> u64 {
+        u64::from_le_bytes(self.read(FIRST_MIGRATION_SLOT))
+    }
+
+    pub fn last_migration_slot(&self) -> u64 {
+        u64::from_le_bytes(self.read(LAST_MIGRATION_SLOT))
+    }
+
+    pub fn first_migration_timestamp(&self) -> i64 {
+        i64::from_le_bytes(self.read(FIRST_MIGRATION_TIMESTAMP))
+    }
+
+    pub fn last_migration_timestamp(&self) -> i64 {
+        i64::from_le_bytes(self.read(LAST_MIGRATION_TIMESTAMP))
+    }
+
+    pub fn delegate_handling(&self) -> Result<DelegateHandling, ProgramError> {
+        match self.read::<1>(DELEGATE_HANDLING)[0] {
+            0 => Ok(DelegateHandling::CarryOver),
+            1 => Ok(DelegateHandling::Reject),
+            _ => Err(MigrationError::InvalidMigrationState.into()),
+        }
+    }
+
+    pub fn collection_parent_order(&self) -> Result<CollectionParentOrder, ProgramError> {
+        match self.read::<1>(COLLECTION_PARENT_ORDER)[0] {
+            0 => Ok(CollectionParentOrder::First),
+            1 => Ok(CollectionParentOrder::Last),
+            _ => Err(MigrationError::InvalidMigrationState.into()),
+        }
+    }
+
+    pub fn bounty_reward(&self) -> u64 {
+        u64::from_le_bytes(self.read(BOUNTY_REWARD))
+    }
+
+    pub fn pending_authority(&self) -> Pubkey {
+        self.read_pubkey(PENDING_AUTHORITY)
+    }
+
+    pub fn pending_authority_expiry(&self) -> i64 {
+        i64::from_le_bytes(self.read(PENDING_AUTHORITY_EXPIRY))
+    }
+
+    pub fn update_authority_mode(&self) -> Result<UpdateAuthorityMode, ProgramError> {
+        match self.read::<1>(UPDATE_AUTHORITY_MODE)[0] {
+            0 => Ok(UpdateAuthorityMode::Match),
+            1 => Ok(UpdateAuthorityMode::VerifiedCollection),
+            _ => Err(MigrationError::InvalidMigrationState.into()),
+        }
+    }
+
+    pub fn bump(&self) -> u8 {
+        self.read::<1>(BUMP)[0]
+    }
+
+    pub fn delegate_record_bump(&self) -> u8 {
+        self.read::<1>(DELEGATE_RECORD_BUMP)[0]
+    }
+
+    pub fn collection_parent_released(&self) -> Result<bool, ProgramError> {
+        self.read_bool(COLLECTION_PARENT_RELEASED)
+    }
+
+    pub fn payer_record_rent_paid(&self) -> u64 {
+        u64::from_le_bytes(self.read(PAYER_RECORD_RENT_PAID))
+    }
+
+    pub fn collection_signer_bump(&self) -> u8 {
+        self.read::<1>(COLLECTION_SIGNER_BUMP)[0]
+    }
+
+    pub fn owner_program_list_bump(&self) -> u8 {
+        self.read::<1>(OWNER_PROGRAM_LIST_BUMP)[0]
+    }
+
+    pub fn bounty_vault_bump(&self) -> u8 {
+        self.read::<1>(BOUNTY_VAULT_BUMP)[0]
+    }
+
+    /// Whether any item, including the collection parent, has been migrated.
+    pub fn has_migrated(&self) -> Result<bool, ProgramError> {
+        Ok(self.items_migrated() > 0 || self.collection_parent_migrated()?)
+    }
+
+    fn read<const N: usize>(&self, offset: usize) -> [u8; N] {
+        self.data.as_ref()[offset..offset + N].try_into().unwrap()
+    }
+
+    fn read_pubkey(&self, offset: usize) -> Pubkey {
+        Pubkey::new_from_array(self.read(offset))
+    }
+
+    // Borsh only accepts 0 and 1 for booleans.
+    fn read_bool(&self, offset: usize) -> Result<bool, ProgramError> {
+        match self.read::<1>(offset)[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(MigrationError::InvalidMigrationState.into()),
+        }
+    }
+}
+
+/// Setters for the fields changed by each migration.
+impl<T: AsRef<[u8]> + AsMut<[u8]>> MigrationStateView<T> {
+    pub fn set_items_migrated(&mut self, items_migrated: u32) {
+        self.write(ITEMS_MIGRATED, &items_migrated.to_le_bytes());
+    }
+
+    pub fn set_collection_parent_migrated(&mut self, migrated: bool) {
+        self.write(COLLECTION_PARENT_MIGRATED, &[migrated as u8]);
+    }
+
+    pub fn set_token_record_rent_paid(&mut self, lamports: u64) {
+        self.write(TOKEN_RECORD_RENT_PAID, &lamports.to_le_bytes());
+    }
+
+    pub fn set_unique_payers(&mut self, unique_payers: u32) {
+        self.write(UNIQUE_PAYERS, &unique_payers.to_le_bytes());
+    }
+
+    pub fn set_payer_record_rent_paid(&mut self, lamports: u64) {
+        self.write(PAYER_RECORD_RENT_PAID, &lamports.to_le_bytes());
+    }
+
+    /// Records the slot and timestamp of a migration, as `MigrationStatus::record_migration_time`.
+    pub fn record_migration_time(&mut self, slot: u64, timestamp: i64) -> Result<(), ProgramError> {
+        if !self.has_migrated()? {
+            self.write(FIRST_MIGRATION_SLOT, &slot.to_le_bytes());
+            self.write(FIRST_MIGRATION_TIMESTAMP, &timestamp.to_le_bytes());
+        }
+
+        self.write(LAST_MIGRATION_SLOT, &slot.to_le_bytes());
+        self.write(LAST_MIGRATION_TIMESTAMP, &timestamp.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn write(&mut self, offset: usize, bytes: &[u8]) {
+        self.data.as_mut()[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+}
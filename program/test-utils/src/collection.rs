@@ -0,0 +1,219 @@
+use mpl_migration_validator::state::{
+    CollectionInfo, CollectionParentOrder, DelegateHandling, MigrationState, MigrationStatus,
+    UpdateAuthorityMode,
+};
+use mpl_token_metadata::processor::AuthorizationData;
+use solana_program::clock::Clock;
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+use super::*;
+
+/// A collection parent with verified items, a rule set and a migration state, all under the
+/// collection authority.
+pub struct TestCollection {
+    pub authority: Keypair,
+    pub parent: NfTest,
+    pub items: Vec<NfTest>,
+    pub rule_set: Pubkey,
+    pub auth_data: AuthorizationData,
+    pub migratorr: Migratorr,
+}
+
+impl TestCollection {
+    pub fn builder() -> TestCollectionBuilder {
+        TestCollectionBuilder::new()
+    }
+
+    /// Unlocks the migration state and starts the migration, paid for by the context payer.
+    pub async fn start(
+        &mut self,
+        context: &mut ProgramTestContext,
+    ) -> Result<(), BanksClientError> {
+        let payer = context.payer.dirty_clone();
+
+        self.migratorr
+            .unlock_collection(context, &self.authority)
+            .await;
+
+        self.migratorr
+            .start(context, &payer, &self.authority, &self.parent)
+            .await
+    }
+}
+
+pub struct TestCollectionBuilder {
+    items: usize,
+    authority: Option<Keypair>,
+    sized: bool,
+    collection_size: Option<u32>,
+    unlock_time: Option<i64>,
+    delegate_handling: Option<DelegateHandling>,
+    collection_parent_order: Option<CollectionParentOrder>,
+    update_authority_mode: Option<UpdateAuthorityMode>,
+}
+
+impl TestCollectionBuilder {
+    pub fn new() -> Self {
+        TestCollectionBuilder {
+            items: 0,
+            authority: None,
+            sized: false,
+            collection_size: None,
+            unlock_time: None,
+            delegate_handling: None,
+            collection_parent_order: None,
+            update_authority_mode: None,
+        }
+    }
+
+    /// Number of verified items to mint, which is also the collection size.
+    pub fn items(mut self, items: usize) -> Self {
+        self.items = items;
+        self
+    }
+
+    /// Update authority of the parent and the items. Defaults to the context payer. It pays
+    /// for its own mints, so it must already be funded.
+    pub fn authority(mut self, authority: Keypair) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Mints the parent as a sized collection, whose size is counted by Token Metadata.
+    pub fn sized(mut self) -> Self {
+        self.sized = true;
+        self
+    }
+
+    /// Collection size stored in the migration state, if it differs from the item count.
+    pub fn collection_size(mut self, collection_size: u32) -> Self {
+        self.collection_size = Some(collection_size);
+        self
+    }
+
+    /// Time at which the migration state can be unlocked. Defaults to the current clock time,
+    /// so the next `Update` unlocks it.
+    pub fn unlock_time(mut self, unlock_time: i64) -> Self {
+        self.unlock_time = Some(unlock_time);
+        self
+    }
+
+    pub fn delegate_handling(mut self, delegate_handling: DelegateHandling) -> Self {
+        self.delegate_handling = Some(delegate_handling);
+        self
+    }
+
+    pub fn collection_parent_order(
+        mut self,
+        collection_parent_order: CollectionParentOrder,
+    ) -> Self {
+        self.collection_parent_order = Some(collection_parent_order);
+        self
+    }
+
+    pub fn update_authority_mode(mut self, update_authority_mode: UpdateAuthorityMode) -> Self {
+        self.update_authority_mode = Some(update_authority_mode);
+        self
+    }
+
+    pub async fn build(
+        self,
+        context: &mut ProgramTestContext,
+    ) -> Result<TestCollection, BanksClientError> {
+        let authority = self
+            .authority
+            .unwrap_or_else(|| context.payer.dirty_clone());
+
+        let mut parent = NfTest::new();
+        if self.sized {
+            parent
+                .mint_sized_collection(context, Some(authority.dirty_clone()), 0)
+                .await?;
+        } else {
+            parent
+                .mint_default(context, Some(authority.dirty_clone()))
+                .await?;
+        }
+
+        let mut items = Vec::with_capacity(self.items);
+        for _ in 0..self.items {
+            let mut item = NfTest::new();
+            item.mint_default(context, Some(authority.dirty_clone()))
+                .await?;
+
+            let args = SetAndVerifyCollectionArgs {
+                collection_metadata: parent.metadata_pubkey(),
+                collection_authority: authority.dirty_clone(),
+                nft_update_authority: authority.pubkey(),
+                collection_mint: parent.mint_pubkey(),
+                collection_master_edition_account: parent.edition_pubkey().unwrap(),
+                collection_authority_record: None,
+            };
+            if self.sized {
+                item.set_and_verify_sized_collection_item(context, args)
+                    .await?;
+            } else {
+                item.set_and_verify_collection(context, args).await?;
+            }
+            items.push(item);
+        }
+
+        let payer = context.payer.dirty_clone();
+        let (rule_set, auth_data) = create_default_metaplex_rule_set(context, payer).await;
+
+        let unlock_time = match self.unlock_time {
+            Some(unlock_time) => unlock_time,
+            None => {
+                let clock: Clock = context.banks_client.get_sysvar().await?;
+                clock.unix_timestamp
+            }
+        };
+
+        // Initialize is deprecated, so the locked migration state is created directly.
+        let mut migratorr = Migratorr::new(parent.mint_pubkey());
+        let defaults = MigrationState::default();
+        let state = MigrationState {
+            collection_info: CollectionInfo {
+                authority: authority.pubkey(),
+                mint: parent.mint_pubkey(),
+                rule_set,
+                size: self.collection_size.unwrap_or(self.items as u32),
+                ..CollectionInfo::default()
+            },
+            status: MigrationStatus {
+                unlock_time,
+                is_locked: true,
+                ..MigrationStatus::default()
+            },
+            delegate_handling: self.delegate_handling.unwrap_or(defaults.delegate_handling),
+            collection_parent_order: self
+                .collection_parent_order
+                .unwrap_or(defaults.collection_parent_order),
+            update_authority_mode: self
+                .update_authority_mode
+                .unwrap_or(defaults.update_authority_mode),
+            bump: find_migrate_state_pda(&parent.mint_pubkey()).1,
+            ..defaults
+        };
+        migratorr.create_state(context, state).await;
+
+        Ok(TestCollection {
+            authority,
+            parent,
+            items,
+            rule_set,
+            auth_data,
+            migratorr,
+        })
+    }
+}
+
+impl Default for TestCollectionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
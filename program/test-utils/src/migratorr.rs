@@ -1,19 +1,23 @@
 use borsh::BorshSerialize;
 use mpl_migration_validator::{
     instruction::{
-        initialize, migrate_item, start, update, InitializeArgs, MigrationInstruction, UpdateArgs,
+        accept_authority, close_payer_record, finalize_bounty, fund_bounty, initialize,
+        set_owner_program_list, start, sync_authority, update, upgrade_state, FundBountyArgs,
+        InitializeArgs, MigrateAccounts, MigrationInstruction, SetOwnerProgramListArgs, UpdateArgs,
     },
-    state::{MigrationState, UnlockMethod},
+    state::{
+        MigrationState, MigrationStateV0, OwnerProgramList, UnlockMethod,
+        MIGRATION_STATE_ACCOUNT_LEN,
+    },
+    utils::find_owner_program_list_pda,
 };
 use solana_program::{
     borsh::try_from_slice_unchecked,
-    bpf_loader_upgradeable::UpgradeableLoaderState,
     instruction::{AccountMeta, Instruction},
 };
 use solana_program_test::{BanksClientError, ProgramTestContext};
 use solana_sdk::{
-    account_utils::StateMut, pubkey::Pubkey, signature::Signer, signer::keypair::Keypair,
-    transaction::Transaction,
+    pubkey::Pubkey, signature::Signer, signer::keypair::Keypair, transaction::Transaction,
 };
 use spl_associated_token_account::get_associated_token_address;
 
@@ -22,6 +26,7 @@ use super::*;
 #[derive(Debug)]
 pub struct Migratorr {
     pubkey: Pubkey,
+    collection_mint: Pubkey,
     state: MigrationState,
 }
 
@@ -31,6 +36,7 @@ impl Migratorr {
 
         Migratorr {
             pubkey,
+            collection_mint,
             state: MigrationState::default(),
         }
     }
@@ -112,7 +118,7 @@ impl Migratorr {
         authority: &Keypair,
         args: UpdateArgs,
     ) -> Result<(), BanksClientError> {
-        let instruction = update(authority.pubkey(), self.pubkey, args);
+        let instruction = update(authority.pubkey(), self.collection_mint, args);
 
         let transaction = Transaction::new_signed_with_payer(
             &[instruction],
@@ -124,6 +130,46 @@ impl Migratorr {
         context.banks_client.process_transaction(transaction).await
     }
 
+    pub async fn accept_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        payer: &Keypair,
+        new_authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = accept_authority(new_authority.pubkey(), self.pubkey);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, new_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    pub async fn sync_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        payer: &Keypair,
+        authority: Option<&Keypair>,
+    ) -> Result<(), BanksClientError> {
+        let instruction =
+            sync_authority(payer.pubkey(), authority.map(|a| a.pubkey()), self.mint());
+
+        let mut signers = vec![payer];
+        signers.extend(authority);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &signers,
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
     pub async fn start(
         &self,
         context: &mut ProgramTestContext,
@@ -201,6 +247,99 @@ impl Migratorr {
         context.banks_client.process_transaction(transaction).await
     }
 
+    /// Closes the payer record of `payer`. Anyone can close it, so the transaction is paid
+    /// by `fee_payer`.
+    pub async fn close_payer_record(
+        &self,
+        context: &mut ProgramTestContext,
+        fee_payer: &Keypair,
+        payer: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let instruction = close_payer_record(payer, self.collection_mint);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&fee_payer.pubkey()),
+            &[fee_payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    pub async fn upgrade_state(
+        &self,
+        context: &mut ProgramTestContext,
+        payer: &Keypair,
+        collection_mint: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let instruction = upgrade_state(payer.pubkey(), collection_mint);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    pub async fn fund_bounty(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+        args: FundBountyArgs,
+    ) -> Result<(), BanksClientError> {
+        let instruction = fund_bounty(authority.pubkey(), self.mint(), args);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&authority.pubkey()),
+            &[authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    pub async fn finalize_bounty(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let instruction = finalize_bounty(authority.pubkey(), self.collection_mint);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&authority.pubkey()),
+            &[authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    pub async fn set_owner_program_list(
+        &self,
+        context: &mut ProgramTestContext,
+        payer: &Keypair,
+        authority: &Keypair,
+        args: SetOwnerProgramListArgs,
+    ) -> Result<(), BanksClientError> {
+        let instruction =
+            set_owner_program_list(payer.pubkey(), authority.pubkey(), self.mint(), args);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(transaction).await
+    }
+
     pub async fn migrate_item(
         &mut self,
         context: &mut ProgramTestContext,
@@ -209,43 +348,49 @@ impl Migratorr {
         token_owner: Pubkey,
         nft: &NfTest,
     ) -> Result<(), BanksClientError> {
-        let token_owner_program = context
-            .banks_client
-            .get_account(token_owner)
+        let instruction = self
+            .migrate_accounts(
+                context,
+                payer,
+                nft.mint_pubkey(),
+                nft.token_pubkey(),
+                token_owner,
+                collection_mint,
+            )
             .await
-            .unwrap()
-            .unwrap()
-            .owner;
+            .instruction()
+            .unwrap();
 
-        let bpf_upgradeable_state: Option<UpgradeableLoaderState> = context
-            .banks_client
-            .get_account(token_owner_program)
-            .await
-            .unwrap()
-            .unwrap()
-            .state()
-            .ok();
-
-        let token_owner_program_buffer = if let Some(UpgradeableLoaderState::Program {
-            programdata_address,
-        }) = bpf_upgradeable_state
-        {
-            Some(programdata_address)
-        } else {
-            None
-        };
-
-        let instruction = migrate_item(
-            payer.pubkey(),
-            nft.mint_pubkey(),
-            nft.token_pubkey(),
-            token_owner,
-            token_owner_program,
-            token_owner_program_buffer,
-            collection_mint,
-            self.rule_set(),
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[payer],
+            context.last_blockhash,
         );
 
+        context.banks_client.process_transaction(transaction).await
+    }
+
+    pub async fn migrate_collection_parent(
+        &mut self,
+        context: &mut ProgramTestContext,
+        payer: &Keypair,
+        token_owner: Pubkey,
+        collection_nft: &NfTest,
+    ) -> Result<(), BanksClientError> {
+        let instruction = self
+            .migrate_accounts(
+                context,
+                payer,
+                collection_nft.mint_pubkey(),
+                collection_nft.token_pubkey(),
+                token_owner,
+                collection_nft.mint_pubkey(),
+            )
+            .await
+            .collection_parent_instruction()
+            .unwrap();
+
         let transaction = Transaction::new_signed_with_payer(
             &[instruction],
             Some(&payer.pubkey()),
@@ -265,42 +410,18 @@ impl Migratorr {
         asset: &TestAsset,
     ) -> Result<(), BanksClientError> {
         let token = get_associated_token_address(&token_owner, &asset.mint.pubkey());
-        let token_owner_program = context
-            .banks_client
-            .get_account(token_owner)
+        let instruction = self
+            .migrate_accounts(
+                context,
+                payer,
+                asset.mint.pubkey(),
+                token,
+                token_owner,
+                collection_mint,
+            )
             .await
-            .unwrap()
-            .unwrap()
-            .owner;
-
-        let bpf_upgradeable_state: Option<UpgradeableLoaderState> = context
-            .banks_client
-            .get_account(token_owner_program)
-            .await
-            .unwrap()
-            .unwrap()
-            .state()
-            .ok();
-
-        let token_owner_program_buffer = if let Some(UpgradeableLoaderState::Program {
-            programdata_address,
-        }) = bpf_upgradeable_state
-        {
-            Some(programdata_address)
-        } else {
-            None
-        };
-
-        let instruction = migrate_item(
-            payer.pubkey(),
-            asset.mint.pubkey(),
-            token,
-            token_owner,
-            token_owner_program,
-            token_owner_program_buffer,
-            collection_mint,
-            self.rule_set(),
-        );
+            .instruction()
+            .unwrap();
 
         let transaction = Transaction::new_signed_with_payer(
             &[instruction],
@@ -320,42 +441,18 @@ impl Migratorr {
         token_owner: Pubkey,
         asset: &TestPrintEdition,
     ) -> Result<(), BanksClientError> {
-        let token_owner_program = context
-            .banks_client
-            .get_account(token_owner)
+        let instruction = self
+            .migrate_accounts(
+                context,
+                payer,
+                asset.print_mint.pubkey(),
+                asset.print_token.pubkey(),
+                token_owner,
+                collection_mint,
+            )
             .await
-            .unwrap()
-            .unwrap()
-            .owner;
-
-        let bpf_upgradeable_state: Option<UpgradeableLoaderState> = context
-            .banks_client
-            .get_account(token_owner_program)
-            .await
-            .unwrap()
-            .unwrap()
-            .state()
-            .ok();
-
-        let token_owner_program_buffer = if let Some(UpgradeableLoaderState::Program {
-            programdata_address,
-        }) = bpf_upgradeable_state
-        {
-            Some(programdata_address)
-        } else {
-            None
-        };
-
-        let instruction = migrate_item(
-            payer.pubkey(),
-            asset.print_mint.pubkey(),
-            asset.print_token.pubkey(),
-            token_owner,
-            token_owner_program,
-            token_owner_program_buffer,
-            collection_mint,
-            self.rule_set(),
-        );
+            .instruction()
+            .unwrap();
 
         let transaction = Transaction::new_signed_with_payer(
             &[instruction],
@@ -368,6 +465,34 @@ impl Migratorr {
     }
 
     //      *****Utilities*****         //
+    pub async fn migrate_accounts(
+        &self,
+        context: &mut ProgramTestContext,
+        payer: &Keypair,
+        item_mint: Pubkey,
+        item_token: Pubkey,
+        token_owner: Pubkey,
+        collection_mint: Pubkey,
+    ) -> MigrateAccounts {
+        let token_owner_program = get_account(context, &token_owner).await.owner;
+        let program_account = get_account(context, &token_owner_program).await;
+
+        MigrateAccounts::new(
+            payer.pubkey(),
+            item_mint,
+            item_token,
+            token_owner,
+            collection_mint,
+        )
+        .token_owner_program(
+            token_owner_program,
+            &program_account.owner,
+            &program_account.data,
+        )
+        .unwrap()
+        .auth_rule_set(self.rule_set())
+    }
+
     pub async fn refresh_state(
         &mut self,
         context: &mut ProgramTestContext,
@@ -377,6 +502,12 @@ impl Migratorr {
         Ok(())
     }
 
+    pub async fn owner_program_list(&self, context: &mut ProgramTestContext) -> OwnerProgramList {
+        let (owner_program_list_pda, _) = find_owner_program_list_pda(&self.mint());
+        let account = get_account(context, &owner_program_list_pda).await;
+        try_from_slice_unchecked(&account.data).unwrap()
+    }
+
     // Allows injecting a specific state into the on-chain
     // account. This is useful for testing the migration unlock time.
     pub async fn inject_state(&self, context: &mut ProgramTestContext, state: MigrationState) {
@@ -390,7 +521,44 @@ impl Migratorr {
 
         let account = Account {
             lamports,
-            data: state.try_to_vec().unwrap(),
+            data: state_account_data(&state),
+            owner: mpl_migration_validator::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        context.set_account(&self.pubkey(), &account.into())
+    }
+
+    // Creates the migration state account directly, as Initialize is deprecated.
+    pub async fn create_state(&mut self, context: &mut ProgramTestContext, state: MigrationState) {
+        let data = state_account_data(&state);
+        let rent = context.banks_client.get_rent().await.unwrap();
+
+        let account = Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
+            owner: mpl_migration_validator::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        context.set_account(&self.pubkey(), &account.into());
+        self.state = state;
+    }
+
+    // Injects a migration state account with the legacy, unversioned layout.
+    pub async fn inject_legacy_state(
+        &self,
+        context: &mut ProgramTestContext,
+        state: MigrationStateV0,
+    ) {
+        let data = state.try_to_vec().unwrap();
+        let rent = context.banks_client.get_rent().await.unwrap();
+
+        let account = Account {
+            lamports: rent.minimum_balance(data.len()),
+            data,
             owner: mpl_migration_validator::ID,
             executable: false,
             rent_epoch: 0,
@@ -404,6 +572,25 @@ impl Migratorr {
         context: &mut ProgramTestContext,
         authority: &Keypair,
     ) {
+        self.expire_unlock_time(context).await;
+
+        // Update the state account on-chain. This checks the current time
+        // and updates the is_unlocked field if the wait time has passed.s
+        let update_args = UpdateArgs {
+            rule_set: None,
+            collection_size: None, // leave collection size unchanged
+            new_update_authority: None,
+            delegate_handling: None,
+            collection_parent_order: None,
+            update_authority_mode: None,
+            release_collection_parent: None,
+        };
+
+        self.update(context, authority, update_args).await.unwrap();
+    }
+
+    // Moves the unlock time to the past so the next update unlocks the collection.
+    pub async fn expire_unlock_time(&mut self, context: &mut ProgramTestContext) {
         self.refresh_state(context).await.unwrap();
 
         // We need to inject the account with the state set to a timestamp
@@ -421,15 +608,12 @@ impl Migratorr {
 
         // Warp ahead to ensure account is updated.
         warp100(context).await;
-
-        // Update the state account on-chain. This checks the current time
-        // and updates the is_unlocked field if the wait time has passed.s
-        let update_args = UpdateArgs {
-            rule_set: None,
-            collection_size: None, // leave collection size unchanged
-            new_update_authority: None,
-        };
-
-        self.update(context, authority, update_args).await.unwrap();
     }
 }
+
+// Migration state accounts are allocated with a zeroed reserved region after the state.
+fn state_account_data(state: &MigrationState) -> Vec<u8> {
+    let mut data = state.try_to_vec().unwrap();
+    data.resize(MIGRATION_STATE_ACCOUNT_LEN, 0);
+    data
+}
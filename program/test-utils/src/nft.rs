@@ -219,6 +219,30 @@ impl NfTest {
         Ok(())
     }
 
+    pub async fn mint_sized_collection(
+        &mut self,
+        context: &mut ProgramTestContext,
+        authority: Option<Keypair>,
+        size: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut args = MintArgs::default();
+        let authority = authority.unwrap_or_else(|| context.payer.dirty_clone());
+        args.authority = authority.dirty_clone();
+        args.collection_details = Some(CollectionDetails::V1 { size });
+
+        self.mint(context, args).await.unwrap();
+
+        let master_edition = MasterEditionV2::new(self);
+        master_edition
+            .create_v3(context, Some(authority), Some(0))
+            .await
+            .unwrap();
+
+        self.edition = Some(master_edition.pubkey);
+
+        Ok(())
+    }
+
     pub async fn mint_master_with_supply(
         &mut self,
         context: &mut ProgramTestContext,
@@ -266,6 +290,30 @@ impl NfTest {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn set_and_verify_sized_collection_item(
+        &self,
+        context: &mut ProgramTestContext,
+        args: SetAndVerifyCollectionArgs,
+    ) -> Result<(), BanksClientError> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::set_and_verify_sized_collection_item(
+                id(),
+                self.metadata,
+                args.collection_authority.pubkey(),
+                context.payer.pubkey(),
+                args.nft_update_authority,
+                args.collection_mint,
+                args.collection_metadata,
+                args.collection_master_edition_account,
+                args.collection_authority_record,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &args.collection_authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn set_new_update_authority(
         &self,
         context: &mut ProgramTestContext,
@@ -369,6 +417,26 @@ impl NfTest {
         context.set_account(&self.token.pubkey(), &account.into())
     }
 
+    // Overwrites the NFT's edition account with arbitrary data and owner.
+    pub async fn inject_edition(
+        &self,
+        context: &mut ProgramTestContext,
+        owner: Pubkey,
+        data: Vec<u8>,
+    ) {
+        let lamports = get_account(context, &self.edition.unwrap()).await.lamports;
+
+        let account = Account {
+            lamports,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        context.set_account(&self.edition.unwrap(), &account.into())
+    }
+
     pub async fn refresh_accounts(
         &mut self,
         context: &mut ProgramTestContext,
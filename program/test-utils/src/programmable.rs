@@ -9,7 +9,7 @@ use mpl_token_metadata::{
 };
 use rmp_serde::Serializer;
 use serde::Serialize;
-use solana_program::system_program;
+use solana_program::{pubkey, system_program};
 use solana_program_test::ProgramTestContext;
 use solana_sdk::{
     pubkey::Pubkey,
@@ -17,7 +17,10 @@ use solana_sdk::{
     transaction::Transaction,
 };
 
-static PROGRAM_ALLOW_LIST: [Pubkey; 2] = [mpl_token_auth_rules::ID, rooster::ID];
+/// Rooster is only referenced by address, so the crate doesn't pull in its git-only dependency.
+const ROOSTER_PROGRAM_ID: Pubkey = pubkey!("Roostrnex2Z9Y2XZC49sFAdZARP8E4iFpEnZC5QJWdz");
+
+static PROGRAM_ALLOW_LIST: [Pubkey; 2] = [mpl_token_auth_rules::ID, ROOSTER_PROGRAM_ID];
 
 macro_rules! get_primitive_rules {
     (
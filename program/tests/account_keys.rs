@@ -0,0 +1,113 @@
+use borsh::BorshSerialize;
+use mpl_migration_validator::{
+    errors::MigrationError,
+    state::{Key, ListType, MigrationState, OwnerProgramList, PayerRecord, ProgramSigner},
+};
+use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+fn with_account_info<T>(data: &mut [u8], f: impl FnOnce(&AccountInfo) -> T) -> T {
+    let key = Pubkey::new_unique();
+    let owner = mpl_migration_validator::ID;
+    let mut lamports = 0;
+
+    let account_info = AccountInfo::new(&key, false, true, &mut lamports, data, &owner, false, 0);
+
+    f(&account_info)
+}
+
+#[test]
+fn migration_state_is_not_a_program_signer() {
+    let mut data = MigrationState::default().try_to_vec().unwrap();
+
+    let err = with_account_info(&mut data, |a| {
+        ProgramSigner::from_account_info(a).unwrap_err()
+    });
+
+    assert_eq!(
+        err,
+        ProgramError::from(MigrationError::InvalidProgramSigner)
+    );
+}
+
+#[test]
+fn program_signer_is_not_a_migration_state() {
+    let mut data = ProgramSigner {
+        key: Key::ProgramSigner,
+        bump: 255,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let err = with_account_info(&mut data, |a| {
+        MigrationState::from_account_info(a).unwrap_err()
+    });
+
+    assert_eq!(
+        err,
+        ProgramError::from(MigrationError::InvalidMigrationStateKey)
+    );
+}
+
+#[test]
+fn owner_program_list_is_not_a_migration_state() {
+    let mut data = OwnerProgramList {
+        key: Key::OwnerProgramList,
+        list_type: ListType::Deny,
+        programs: vec![Pubkey::new_unique()],
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let err = with_account_info(&mut data, |a| {
+        MigrationState::from_account_info(a).unwrap_err()
+    });
+
+    assert_eq!(
+        err,
+        ProgramError::from(MigrationError::InvalidMigrationStateKey)
+    );
+}
+
+#[test]
+fn legacy_program_signer_is_accepted() {
+    let mut data = vec![254];
+
+    let signer = with_account_info(&mut data, |a| ProgramSigner::from_account_info(a).unwrap());
+
+    assert_eq!(signer.key, Key::ProgramSigner);
+    assert_eq!(signer.bump, 254);
+}
+
+#[test]
+fn program_signer_key_is_checked() {
+    let mut data = vec![Key::MigrationStateV1 as u8, 255];
+
+    let err: ProgramError = with_account_info(&mut data, |a| {
+        ProgramSigner::from_account_info(a).unwrap_err()
+    });
+
+    assert_eq!(
+        err,
+        ProgramError::from(MigrationError::InvalidProgramSignerKey)
+    );
+}
+
+#[test]
+fn payer_record_is_not_a_program_signer() {
+    // Payer records have the same size as the program signer.
+    let mut data = PayerRecord {
+        key: Key::PayerRecord,
+        bump: 255,
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let err = with_account_info(&mut data, |a| {
+        ProgramSigner::from_account_info(a).unwrap_err()
+    });
+
+    assert_eq!(
+        err,
+        ProgramError::from(MigrationError::InvalidProgramSignerKey)
+    );
+}
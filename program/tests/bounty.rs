@@ -0,0 +1,301 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_migration_validator::{
+    errors::MigrationError, instruction::FundBountyArgs, state::BOUNTY_RECLAIM_PERIOD,
+    utils::find_bounty_vault_pda,
+};
+use num_traits::FromPrimitive;
+use solana_program::native_token::LAMPORTS_PER_SOL;
+use solana_program_test::{tokio, BanksClientError};
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use utils::*;
+
+#[tokio::test]
+async fn bounty_paid_per_migration() {
+    let mut context = setup_pnft_context().await;
+
+    // We create a collection with a single NFT in it.
+    let TestCollection {
+        authority: collection_authority,
+        parent: collection_nft,
+        items,
+        mut migratorr,
+        ..
+    } = TestCollection::builder()
+        .items(1)
+        .build(&mut context)
+        .await
+        .unwrap();
+    let [nft]: [NfTest; 1] = items.try_into().unwrap();
+
+    let payer = context.payer.dirty_clone();
+
+    // Fund the vault and set the reward paid to crankers.
+    let reward = LAMPORTS_PER_SOL / 100;
+    let funding = LAMPORTS_PER_SOL;
+
+    migratorr
+        .fund_bounty(
+            &mut context,
+            &collection_authority,
+            FundBountyArgs {
+                lamports: funding,
+                reward: Some(reward),
+            },
+        )
+        .await
+        .unwrap();
+
+    migratorr.refresh_state(&mut context).await.unwrap();
+    assert_eq!(migratorr.state().bounty_reward, reward);
+
+    let (bounty_vault, _) = find_bounty_vault_pda(&collection_nft.mint_pubkey());
+    let vault_balance = get_account(&mut context, &bounty_vault).await.lamports;
+
+    migratorr
+        .unlock_collection(&mut context, &collection_authority)
+        .await;
+
+    migratorr
+        .start(&mut context, &payer, &collection_authority, &collection_nft)
+        .await
+        .unwrap();
+
+    // The bounty cannot be reclaimed while items remain unmigrated.
+    let err = migratorr
+        .finalize_bounty(&mut context, &collection_authority)
+        .await
+        .unwrap_err();
+
+    assert_custom_error_ix!(0, err, MigrationError::MigrationNotComplete);
+
+    // A third party cranks the migration and collects the reward.
+    let cranker = Keypair::new();
+    cranker
+        .airdrop(&mut context, LAMPORTS_PER_SOL)
+        .await
+        .unwrap();
+
+    migratorr
+        .migrate_item(
+            &mut context,
+            &cranker,
+            collection_nft.mint_pubkey(),
+            payer.pubkey(),
+            &nft,
+        )
+        .await
+        .unwrap();
+
+    let account = get_account(&mut context, &bounty_vault).await;
+    assert_eq!(account.lamports, vault_balance - reward);
+
+    // Finalizing returns the leftover bounty and closes the vault.
+    migratorr
+        .finalize_bounty(&mut context, &collection_authority)
+        .await
+        .unwrap();
+
+    let account = context
+        .banks_client
+        .get_account(bounty_vault)
+        .await
+        .unwrap();
+    assert!(account.is_none());
+}
+
+#[tokio::test]
+async fn underfunded_bounty_does_not_block_migration() {
+    let mut context = setup_pnft_context().await;
+
+    let mut collection = TestCollection::builder()
+        .items(1)
+        .build(&mut context)
+        .await
+        .unwrap();
+
+    // The vault only holds half of the reward.
+    let reward = LAMPORTS_PER_SOL / 100;
+
+    collection
+        .migratorr
+        .fund_bounty(
+            &mut context,
+            &collection.authority,
+            FundBountyArgs {
+                lamports: reward / 2,
+                reward: Some(reward),
+            },
+        )
+        .await
+        .unwrap();
+
+    collection.start(&mut context).await.unwrap();
+
+    let TestCollection {
+        parent: collection_nft,
+        items,
+        mut migratorr,
+        ..
+    } = collection;
+    let [nft]: [NfTest; 1] = items.try_into().unwrap();
+
+    let payer = context.payer.dirty_clone();
+
+    let (bounty_vault, _) = find_bounty_vault_pda(&collection_nft.mint_pubkey());
+    let vault_balance = get_account(&mut context, &bounty_vault).await.lamports;
+
+    // The item migrates without a reward.
+    migratorr
+        .migrate_item(
+            &mut context,
+            &payer,
+            collection_nft.mint_pubkey(),
+            payer.pubkey(),
+            &nft,
+        )
+        .await
+        .unwrap();
+
+    let account = get_account(&mut context, &bounty_vault).await;
+    assert_eq!(account.lamports, vault_balance);
+
+    migratorr.refresh_state(&mut context).await.unwrap();
+    assert_eq!(migratorr.state().status.items_migrated, 1);
+}
+
+#[tokio::test]
+async fn empty_collection_bounty_cannot_be_finalized() {
+    let mut context = setup_pnft_context().await;
+
+    // A collection without items would otherwise count as complete right away.
+    let mut collection = TestCollection::builder().build(&mut context).await.unwrap();
+
+    collection
+        .migratorr
+        .fund_bounty(
+            &mut context,
+            &collection.authority,
+            FundBountyArgs {
+                lamports: LAMPORTS_PER_SOL,
+                reward: Some(LAMPORTS_PER_SOL / 100),
+            },
+        )
+        .await
+        .unwrap();
+
+    collection.start(&mut context).await.unwrap();
+
+    let TestCollection {
+        authority,
+        migratorr,
+        ..
+    } = collection;
+
+    let err = migratorr
+        .finalize_bounty(&mut context, &authority)
+        .await
+        .unwrap_err();
+
+    assert_custom_error_ix!(0, err, MigrationError::EmptyCollection);
+}
+
+#[tokio::test]
+async fn stalled_bounty_can_be_reclaimed() {
+    let mut context = setup_pnft_context().await;
+
+    let mut collection = TestCollection::builder()
+        .items(2)
+        .build(&mut context)
+        .await
+        .unwrap();
+
+    collection
+        .migratorr
+        .fund_bounty(
+            &mut context,
+            &collection.authority,
+            FundBountyArgs {
+                lamports: LAMPORTS_PER_SOL,
+                reward: Some(LAMPORTS_PER_SOL / 100),
+            },
+        )
+        .await
+        .unwrap();
+
+    collection.start(&mut context).await.unwrap();
+
+    let TestCollection {
+        authority,
+        parent: collection_nft,
+        items,
+        mut migratorr,
+        ..
+    } = collection;
+    let [nft, _straggler]: [NfTest; 2] = items.try_into().unwrap();
+
+    let payer = context.payer.dirty_clone();
+
+    migratorr
+        .migrate_item(
+            &mut context,
+            &payer,
+            collection_nft.mint_pubkey(),
+            payer.pubkey(),
+            &nft,
+        )
+        .await
+        .unwrap();
+
+    // The last migration is recent, so the bounty is still owed to crankers even
+    // though the unlock time is long past.
+    migratorr.refresh_state(&mut context).await.unwrap();
+    let mut state = migratorr.state().clone();
+    state.status.unlock_time -= BOUNTY_RECLAIM_PERIOD;
+    migratorr.inject_state(&mut context, state.clone()).await;
+
+    let err = migratorr
+        .finalize_bounty(&mut context, &authority)
+        .await
+        .unwrap_err();
+
+    assert_custom_error_ix!(0, err, MigrationError::MigrationNotComplete);
+
+    // Once no item has been migrated for the reclaim period, the authority gets the
+    // leftover bounty back.
+    state.status.last_migration_timestamp -= BOUNTY_RECLAIM_PERIOD;
+    migratorr.inject_state(&mut context, state).await;
+
+    // A new blockhash, so the retried instruction is not deduplicated.
+    warp100(&mut context).await;
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let (bounty_vault, _) = find_bounty_vault_pda(&collection_nft.mint_pubkey());
+    let vault_balance = get_account(&mut context, &bounty_vault).await.lamports;
+    let authority_balance = get_account(&mut context, &authority.pubkey())
+        .await
+        .lamports;
+
+    migratorr
+        .finalize_bounty(&mut context, &authority)
+        .await
+        .unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(bounty_vault)
+        .await
+        .unwrap()
+        .is_none());
+
+    // The authority pays the transaction fee out of the returned bounty.
+    let reclaimed = get_account(&mut context, &authority.pubkey())
+        .await
+        .lamports
+        - authority_balance;
+    assert!(reclaimed > vault_balance - LAMPORTS_PER_SOL / 100);
+}
@@ -0,0 +1,96 @@
+use mpl_migration_validator::{
+    errors::MigrationError,
+    instruction::{migrate_item, MigrateAccounts, TokenOwnerProgramBuffer},
+};
+use solana_program::{
+    bpf_loader, bpf_loader_upgradeable, bpf_loader_upgradeable::UpgradeableLoaderState,
+    pubkey::Pubkey,
+};
+
+// Position of the token owner program buffer in the migrate accounts.
+const TOKEN_OWNER_PROGRAM_BUFFER: usize = 5;
+
+#[test]
+fn migrate_accounts_match_positional_builder() {
+    let key = Pubkey::new_unique;
+    let (payer, mint, token, owner, program, collection_mint, rule_set) =
+        (key(), key(), key(), key(), key(), key(), key());
+
+    let instruction = MigrateAccounts::new(payer, mint, token, owner, collection_mint)
+        .token_owner_program(program, &bpf_loader::ID, &[])
+        .unwrap()
+        .auth_rule_set(rule_set)
+        .instruction()
+        .unwrap();
+
+    assert_eq!(
+        instruction,
+        migrate_item(
+            payer,
+            mint,
+            token,
+            owner,
+            program,
+            None,
+            collection_mint,
+            rule_set
+        )
+    );
+}
+
+#[test]
+fn migrate_accounts_use_programdata_of_upgradeable_owner() {
+    let key = Pubkey::new_unique;
+    let programdata_address = key();
+
+    let program_data = bincode::serialize(&UpgradeableLoaderState::Program {
+        programdata_address,
+    })
+    .unwrap();
+
+    let accounts = MigrateAccounts::new(key(), key(), key(), key(), key())
+        .token_owner_program(key(), &bpf_loader_upgradeable::ID, &program_data)
+        .unwrap();
+    assert_eq!(
+        accounts.token_owner_program_buffer,
+        TokenOwnerProgramBuffer::Buffer(programdata_address)
+    );
+
+    let instruction = accounts.instruction().unwrap();
+    assert_eq!(
+        instruction.accounts[TOKEN_OWNER_PROGRAM_BUFFER].pubkey,
+        programdata_address
+    );
+
+    // Wallets are owned by the system program, which has no programdata.
+    let accounts = MigrateAccounts::new(key(), key(), key(), key(), key()).no_buffer();
+    assert_eq!(
+        accounts.instruction().unwrap().accounts[TOKEN_OWNER_PROGRAM_BUFFER].pubkey,
+        mpl_migration_validator::ID
+    );
+}
+
+#[test]
+fn migrate_accounts_need_a_buffer() {
+    let key = Pubkey::new_unique;
+
+    // The buffer must be picked before building the instruction.
+    let accounts = MigrateAccounts::new(key(), key(), key(), key(), key());
+    assert_eq!(
+        accounts.token_owner_program_buffer,
+        TokenOwnerProgramBuffer::Unset
+    );
+    assert_eq!(
+        accounts.instruction(),
+        Err(MigrationError::IncorrectTokenOwnerProgramBuffer)
+    );
+
+    // Upgradeable programs must decode to find their programdata.
+    let program_data = bincode::serialize(&UpgradeableLoaderState::Uninitialized).unwrap();
+    assert_eq!(
+        accounts
+            .token_owner_program(key(), &bpf_loader_upgradeable::ID, &program_data)
+            .unwrap_err(),
+        MigrationError::IncorrectTokenOwnerProgramBuffer
+    );
+}
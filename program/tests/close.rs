@@ -1,11 +1,7 @@
 #![cfg(feature = "test-bpf")]
 pub mod utils;
 
-use mpl_migration_validator::{
-    errors::MigrationError,
-    instruction::{InitializeArgs, UpdateArgs},
-    state::UnlockMethod,
-};
+use mpl_migration_validator::{errors::MigrationError, utils::find_payer_record_pda};
 use num_traits::FromPrimitive;
 use solana_program_test::{tokio, BanksClientError};
 use solana_sdk::{
@@ -16,39 +12,22 @@ use utils::*;
 
 #[tokio::test]
 async fn close_successfully() {
-    let mut context = setup_context().await;
-
-    // Create a default NFT to use as a collection.
-    let mut nft = NfTest::new();
-    nft.mint_default(&mut context, None).await.unwrap();
-
-    // Create our migration state manager.
-    let mut migratorr = Migratorr::new(nft.mint_pubkey());
-
-    // Set up our initialize args
-    let unlock_method = UnlockMethod::Timed;
-
-    let args = InitializeArgs {
-        rule_set: None, // this defaults to the default public key
-        unlock_method,
-        collection_size: 0,
-    };
-
-    let payer = context.payer.dirty_clone();
+    let mut context = setup_pnft_context().await;
 
-    // Initialize the migration state account on-chain
-    migratorr
-        .initialize(&mut context, &payer, &payer, &nft, args)
-        .await
-        .unwrap();
+    let TestCollection {
+        authority,
+        parent,
+        mut migratorr,
+        ..
+    } = TestCollection::builder().build(&mut context).await.unwrap();
 
     // Refresh the migratorr's state from the on-chain account.
     migratorr.refresh_state(&mut context).await.unwrap();
 
-    assert_eq!(migratorr.mint(), nft.mint_pubkey());
-    assert_eq!(migratorr.authority(), payer.pubkey());
+    assert_eq!(migratorr.mint(), parent.mint_pubkey());
+    assert_eq!(migratorr.authority(), authority.pubkey());
 
-    migratorr.close(&mut context, &payer).await.unwrap();
+    migratorr.close(&mut context, &authority).await.unwrap();
 
     // The account should not exist.
     assert!(context
@@ -61,71 +40,18 @@ async fn close_successfully() {
 
 #[tokio::test]
 async fn cannot_close_in_progress_state() {
-    let mut context = setup_context().await;
-
-    // Create a default NFT to use as a collection.
-    let mut nft = NfTest::new();
-    nft.mint_default(&mut context, None).await.unwrap();
-
-    // Create our migration state manager.
-    let mut migratorr = Migratorr::new(nft.mint_pubkey());
-
-    // Set up our initialize args
-    let unlock_method = UnlockMethod::Timed;
-
-    let args = InitializeArgs {
-        rule_set: None, // this defaults to the default public key
-        unlock_method,
-        collection_size: 0,
-    };
-
-    let payer = context.payer.dirty_clone();
-
-    // Initialize the migration state account on-chain
-    migratorr
-        .initialize(&mut context, &payer, &payer, &nft, args)
-        .await
-        .unwrap();
-
-    // Refresh the migratorr's state from the on-chain account.
-    migratorr.refresh_state(&mut context).await.unwrap();
-
-    assert_eq!(migratorr.mint(), nft.mint_pubkey());
-    assert_eq!(migratorr.authority(), payer.pubkey());
-
-    // We need to inject the account with the state set to a timestamp
-    // that allows our migration to start.
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-    let mut state = migratorr.state().clone();
-    state.status.unlock_time = now as i64 - 2;
-
-    // Set the state on the account.
-    migratorr.inject_state(&mut context, state).await;
-
-    // Warp ahead to ensure account is updated.
-    context.warp_to_slot(100).unwrap();
+    let mut context = setup_pnft_context().await;
 
-    // Update the state account on-chain. This checks the current time
-    // and updates the is_unlocked field if the wait time has passed.s
-    let update_args = UpdateArgs {
-        rule_set: None,
-        collection_size: None,
-        new_update_authority: None,
-    };
+    let mut collection = TestCollection::builder().build(&mut context).await.unwrap();
 
-    migratorr
-        .update(&mut context, &payer, update_args)
-        .await
-        .unwrap();
+    // Now we start the migration expecting it to succeed.
+    collection.start(&mut context).await.unwrap();
 
-    // Now we try to start the migration expecting it to succeed.
-    migratorr
-        .start(&mut context, &payer, &payer, &nft)
-        .await
-        .unwrap();
+    let TestCollection {
+        authority,
+        mut migratorr,
+        ..
+    } = collection;
 
     // Refresh the migratorr's state from the on-chain account.
     migratorr.refresh_state(&mut context).await.unwrap();
@@ -135,7 +61,7 @@ async fn cannot_close_in_progress_state() {
     assert!(!migratorr.state().status.is_locked);
 
     // Closing should now fail because the migration is in progress.
-    let err = migratorr.close(&mut context, &payer).await.unwrap_err();
+    let err = migratorr.close(&mut context, &authority).await.unwrap_err();
 
     assert_custom_error_ix!(0, err, MigrationError::MigrationInProgress);
 
@@ -150,58 +76,33 @@ async fn cannot_close_in_progress_state() {
 
 #[tokio::test]
 async fn cannot_close_already_migrated() {
-    let mut context = setup_context().await;
-
-    // Create a default NFT to use as a collection.
-    let mut nft = NfTest::new();
-    nft.mint_default(&mut context, None).await.unwrap();
-
-    // Create our migration state manager.
-    let mut migratorr = Migratorr::new(nft.mint_pubkey());
-
-    // Set up our initialize args
-    let unlock_method = UnlockMethod::Timed;
-
-    let args = InitializeArgs {
-        rule_set: None, // this defaults to the default public key
-        unlock_method,
-        collection_size: 0,
-    };
-
-    let payer = context.payer.dirty_clone();
-
-    // Initialize the migration state account on-chain
-    migratorr
-        .initialize(&mut context, &payer, &payer, &nft, args)
+    let mut context = setup_pnft_context().await;
+
+    let TestCollection {
+        authority,
+        mut migratorr,
+        ..
+    } = TestCollection::builder()
+        .collection_size(10)
+        .build(&mut context)
         .await
         .unwrap();
 
-    // Refresh the migratorr's state from the on-chain account.
-    migratorr.refresh_state(&mut context).await.unwrap();
-
-    assert_eq!(migratorr.mint(), nft.mint_pubkey());
-    assert_eq!(migratorr.authority(), payer.pubkey());
-
     // We need to inject the account with the state set to a completed migration.
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
+    migratorr.refresh_state(&mut context).await.unwrap();
     let mut state = migratorr.state().clone();
-    state.status.unlock_time = now as i64 - 2;
     state.status.in_progress = false;
     state.status.is_locked = false;
     state.status.items_migrated = 10;
-    state.collection_info.size = 10;
 
     // Set the state on the account.
     migratorr.inject_state(&mut context, state).await;
 
     // Warp ahead to ensure account is updated.
-    context.warp_to_slot(100).unwrap();
+    warp100(&mut context).await;
 
     // Closing should now fail because the migration has completed.
-    let err = migratorr.close(&mut context, &payer).await.unwrap_err();
+    let err = migratorr.close(&mut context, &authority).await.unwrap_err();
 
     assert_custom_error_ix!(0, err, MigrationError::MigrationAlreadyCompleted);
 
@@ -218,7 +119,7 @@ async fn cannot_close_already_migrated() {
 async fn authority_must_match() {
     // We can only close accounts for which we are the authority.
 
-    let mut context = setup_context().await;
+    let mut context = setup_pnft_context().await;
 
     let incorrect_authority = Keypair::new();
     incorrect_authority
@@ -226,48 +127,118 @@ async fn authority_must_match() {
         .await
         .unwrap();
 
-    // Create a default NFT to use as a collection.
-    let mut nft = NfTest::new();
-    nft.mint_default(&mut context, None).await.unwrap();
+    let TestCollection { migratorr, .. } =
+        TestCollection::builder().build(&mut context).await.unwrap();
 
-    // Create our migration state manager.
-    let mut migratorr = Migratorr::new(nft.mint_pubkey());
+    let err = migratorr
+        .close(&mut context, &incorrect_authority)
+        .await
+        .unwrap_err();
 
-    // Set up our initialize args
-    let unlock_method = UnlockMethod::Timed;
+    assert_custom_error_ix!(0, err, MigrationError::InvalidAuthority);
 
-    let args = InitializeArgs {
-        rule_set: None, // this defaults to the default public key
-        unlock_method,
-        collection_size: 0,
-    };
+    // The account should still exist.
+    assert!(context
+        .banks_client
+        .get_account(migratorr.pubkey())
+        .await
+        .unwrap()
+        .is_some());
+}
 
-    let payer = context.payer.dirty_clone();
+#[tokio::test]
+async fn close_payer_record_after_migration() {
+    let mut context = setup_pnft_context().await;
 
-    // Initialize the migration state account on-chain
-    migratorr
-        .initialize(&mut context, &payer, &payer, &nft, args)
+    let mut collection = TestCollection::builder()
+        .items(2)
+        .build(&mut context)
         .await
         .unwrap();
 
-    // Refresh the migratorr's state from the on-chain account.
-    migratorr.refresh_state(&mut context).await.unwrap();
+    collection.start(&mut context).await.unwrap();
 
-    assert_eq!(migratorr.mint(), nft.mint_pubkey());
-    assert_eq!(migratorr.authority(), payer.pubkey());
+    let TestCollection {
+        parent: collection_nft,
+        items,
+        mut migratorr,
+        ..
+    } = collection;
+    let [nft1, nft2]: [NfTest; 2] = items.try_into().unwrap();
 
+    // A separate payer, so its balance only changes by its own migrations.
+    let payer = Keypair::new();
+    payer.airdrop(&mut context, 1_000_000_000).await.unwrap();
+    let token_owner = context.payer.pubkey();
+    let fee_payer = context.payer.dirty_clone();
+
+    migratorr
+        .migrate_item(
+            &mut context,
+            &payer,
+            collection_nft.mint_pubkey(),
+            token_owner,
+            &nft1,
+        )
+        .await
+        .unwrap();
+
+    // The record still counts the payer while items remain unmigrated.
     let err = migratorr
-        .close(&mut context, &incorrect_authority)
+        .close_payer_record(&mut context, &fee_payer, payer.pubkey())
         .await
         .unwrap_err();
 
-    assert_custom_error_ix!(0, err, MigrationError::InvalidAuthority);
+    assert_custom_error_ix!(0, err, MigrationError::MigrationNotComplete);
+
+    migratorr
+        .migrate_item(
+            &mut context,
+            &payer,
+            collection_nft.mint_pubkey(),
+            token_owner,
+            &nft2,
+        )
+        .await
+        .unwrap();
+
+    let (payer_record, _) = find_payer_record_pda(&collection_nft.mint_pubkey(), &payer.pubkey());
+    let payer_record_rent = get_account(&mut context, &payer_record).await.lamports;
+
+    migratorr.refresh_state(&mut context).await.unwrap();
+    assert_eq!(migratorr.state().payer_record_rent_paid, payer_record_rent);
+
+    let balance = get_account(&mut context, &payer.pubkey()).await.lamports;
+
+    migratorr
+        .close_payer_record(&mut context, &fee_payer, payer.pubkey())
+        .await
+        .unwrap();
+
+    // The rent is returned to the payer.
+    assert_eq!(
+        get_account(&mut context, &payer.pubkey()).await.lamports,
+        balance + payer_record_rent
+    );
 
-    // The account should not exist.
     assert!(context
         .banks_client
-        .get_account(migratorr.pubkey())
+        .get_account(payer_record)
         .await
         .unwrap()
-        .is_some());
+        .is_none());
+
+    // Migrating the collection parent creates a new payer record, so the same payer
+    // is counted again.
+    migratorr
+        .migrate_collection_parent(&mut context, &payer, token_owner, &collection_nft)
+        .await
+        .unwrap();
+
+    migratorr.refresh_state(&mut context).await.unwrap();
+    assert_eq!(migratorr.state().status.unique_payers, 2);
+    assert_eq!(
+        migratorr.state().payer_record_rent_paid,
+        2 * payer_record_rent
+    );
 }
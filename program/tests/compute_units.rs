@@ -0,0 +1,477 @@
+//! Compute units used by each instruction, checked against `tests/compute_units.txt`.
+//!
+//! The measured units are written to `bench_output.txt` at the repository root. Run with
+//! `UPDATE_COMPUTE_BUDGET=1` to rewrite the budget file from the measured units after an
+//! intended change. Budgets well above the measured units fail too, so the file always
+//! reflects a real run.
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use std::{collections::BTreeMap, fmt::Write, fs, path::PathBuf};
+
+use mpl_migration_validator::{
+    instruction::{
+        accept_authority, close, close_payer_record, finalize_bounty, fund_bounty, init_signer,
+        set_owner_program_list, start, sync_authority, update, upgrade_state, FundBountyArgs,
+        SetOwnerProgramListArgs, UpdateArgs,
+    },
+    state::{CollectionInfoV0, ListType, MigrationStateV0, MigrationStatusV0, UnlockMethod},
+};
+use solana_program::{instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use solana_program_test::{tokio, ProgramTestContext};
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+
+use utils::*;
+
+// Headroom added to the measured units when the budget file is rewritten.
+const BUDGET_HEADROOM_PERCENT: u64 = 10;
+
+// Headroom above which a budget is stale, as it would no longer catch a regression.
+const STALE_HEADROOM_PERCENT: u64 = 2 * BUDGET_HEADROOM_PERCENT;
+
+// Compute units a transaction gets by default for a single instruction.
+const DEFAULT_COMPUTE_UNIT_LIMIT: u64 = 200_000;
+
+fn manifest_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn budget_path() -> PathBuf {
+    manifest_dir().join("tests/compute_units.txt")
+}
+
+// Budget file lines are `<case> <units>`; blank lines and `#` comments are ignored.
+fn load_budget() -> BTreeMap<String, u64> {
+    let budget = fs::read_to_string(budget_path()).expect("missing compute unit budget file");
+
+    budget
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (case, units) = line
+                .split_once(char::is_whitespace)
+                .unwrap_or_else(|| panic!("invalid budget line: {}", line));
+            let units = units
+                .trim()
+                .parse()
+                .unwrap_or_else(|_| panic!("invalid budget line: {}", line));
+            (case.to_string(), units)
+        })
+        .collect()
+}
+
+// Adds the headroom to the measured units, rounded up to the next thousand so small
+// changes do not churn the file.
+fn with_headroom(units: u64, headroom_percent: u64) -> u64 {
+    let units = units * (100 + headroom_percent) / 100;
+    (units + 999) / 1000 * 1000
+}
+
+fn write_budget(measured: &BTreeMap<String, u64>) {
+    let mut budget = String::from(
+        "# Compute unit budget per instruction, checked by tests/compute_units.rs.\n\
+         # Regenerate with UPDATE_COMPUTE_BUDGET=1 after an intended change.\n",
+    );
+    for (case, units) in measured {
+        writeln!(
+            budget,
+            "{} {}",
+            case,
+            with_headroom(*units, BUDGET_HEADROOM_PERCENT)
+        )
+        .unwrap();
+    }
+
+    fs::write(budget_path(), budget).unwrap();
+}
+
+// Records the compute units of each case and executes its instruction.
+#[derive(Default)]
+struct Bench {
+    measured: BTreeMap<String, u64>,
+}
+
+impl Bench {
+    async fn run(
+        &mut self,
+        context: &mut ProgramTestContext,
+        case: &str,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&signers[0].pubkey()),
+            signers,
+            context.last_blockhash,
+        );
+
+        let simulation = context
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+        if let Some(Err(error)) = simulation.result {
+            panic!("{} failed: {:?}", case, error);
+        }
+        let units = simulation
+            .simulation_details
+            .expect("missing simulation details")
+            .units_consumed;
+
+        context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        self.measured.insert(case.to_string(), units);
+    }
+
+    fn report(&self, budget: &BTreeMap<String, u64>) -> String {
+        let mut report = format!(
+            "{:<32} {:>10} {:>10} {:>8}\n",
+            "case", "units", "budget", "limit %"
+        );
+        for (case, units) in &self.measured {
+            let budget = budget
+                .get(case)
+                .map(|budget| budget.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            writeln!(
+                report,
+                "{:<32} {:>10} {:>10} {:>7.1}%",
+                case,
+                units,
+                budget,
+                *units as f64 * 100.0 / DEFAULT_COMPUTE_UNIT_LIMIT as f64
+            )
+            .unwrap();
+        }
+
+        report
+    }
+}
+
+#[tokio::test]
+async fn compute_units_within_budget() {
+    let mut context = setup_pnft_context().await;
+    let mut bench = Bench::default();
+
+    let payer = context.payer.dirty_clone();
+
+    bench
+        .run(
+            &mut context,
+            "init_signer",
+            init_signer(payer.pubkey()),
+            &[&payer],
+        )
+        .await;
+
+    let TestCollection {
+        parent: collection_nft,
+        items,
+        mut migratorr,
+        ..
+    } = TestCollection::builder()
+        .items(3)
+        .build(&mut context)
+        .await
+        .unwrap();
+    let [plain, delegated, frozen]: [NfTest; 3] = items.try_into().unwrap();
+
+    let collection_mint = collection_nft.mint_pubkey();
+
+    bench
+        .run(
+            &mut context,
+            "set_owner_program_list",
+            set_owner_program_list(
+                payer.pubkey(),
+                payer.pubkey(),
+                collection_mint,
+                SetOwnerProgramListArgs {
+                    list_type: ListType::Deny,
+                    programs: vec![Pubkey::new_unique()],
+                },
+            ),
+            &[&payer],
+        )
+        .await;
+
+    bench
+        .run(
+            &mut context,
+            "fund_bounty",
+            fund_bounty(
+                payer.pubkey(),
+                collection_mint,
+                FundBountyArgs {
+                    lamports: LAMPORTS_PER_SOL,
+                    reward: Some(LAMPORTS_PER_SOL / 100),
+                },
+            ),
+            &[&payer],
+        )
+        .await;
+
+    // Warp ahead to ensure the account is updated.
+    warp100(&mut context).await;
+
+    // The update that unlocks the collection.
+    let args = UpdateArgs {
+        rule_set: None,
+        collection_size: None,
+        new_update_authority: None,
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
+    };
+    bench
+        .run(
+            &mut context,
+            "update",
+            update(payer.pubkey(), collection_mint, args.clone()),
+            &[&payer],
+        )
+        .await;
+
+    // Hand the migration state over to a new authority, then bring it back in line
+    // with the collection's update authority.
+    let new_authority = Keypair::new();
+    bench
+        .run(
+            &mut context,
+            "update_propose_authority",
+            update(
+                payer.pubkey(),
+                collection_mint,
+                UpdateArgs {
+                    new_update_authority: Some(new_authority.pubkey()),
+                    ..args
+                },
+            ),
+            &[&payer],
+        )
+        .await;
+
+    bench
+        .run(
+            &mut context,
+            "accept_authority",
+            accept_authority(new_authority.pubkey(), migratorr.pubkey()),
+            &[&payer, &new_authority],
+        )
+        .await;
+
+    bench
+        .run(
+            &mut context,
+            "sync_authority",
+            sync_authority(payer.pubkey(), None, collection_mint),
+            &[&payer],
+        )
+        .await;
+
+    bench
+        .run(
+            &mut context,
+            "start",
+            start(payer.pubkey(), payer.pubkey(), collection_nft.mint_pubkey()),
+            &[&payer],
+        )
+        .await;
+    migratorr.refresh_state(&mut context).await.unwrap();
+
+    let token_owner = payer.pubkey();
+
+    let instruction = migratorr
+        .migrate_accounts(
+            &mut context,
+            &payer,
+            plain.mint_pubkey(),
+            plain.token_pubkey(),
+            token_owner,
+            collection_nft.mint_pubkey(),
+        )
+        .await
+        .instruction()
+        .unwrap();
+    bench
+        .run(&mut context, "migrate_item", instruction, &[&payer])
+        .await;
+
+    // An unfrozen token with an SPL delegate.
+    let delegate = Keypair::new();
+    delegated
+        .spl_delegate(&mut context, &payer, &delegate.pubkey())
+        .await
+        .unwrap();
+    let instruction = migratorr
+        .migrate_accounts(
+            &mut context,
+            &payer,
+            delegated.mint_pubkey(),
+            delegated.token_pubkey(),
+            token_owner,
+            collection_nft.mint_pubkey(),
+        )
+        .await
+        .instruction()
+        .unwrap();
+    bench
+        .run(
+            &mut context,
+            "migrate_item_spl_delegate",
+            instruction,
+            &[&payer],
+        )
+        .await;
+
+    // A token frozen by its SPL delegate.
+    let delegate = Keypair::new();
+    delegate
+        .airdrop(&mut context, LAMPORTS_PER_SOL)
+        .await
+        .unwrap();
+    frozen
+        .spl_delegate(&mut context, &payer, &delegate.pubkey())
+        .await
+        .unwrap();
+    frozen.freeze_token(&mut context, &delegate).await.unwrap();
+    let instruction = migratorr
+        .migrate_accounts(
+            &mut context,
+            &payer,
+            frozen.mint_pubkey(),
+            frozen.token_pubkey(),
+            token_owner,
+            collection_nft.mint_pubkey(),
+        )
+        .await
+        .instruction()
+        .unwrap();
+    bench
+        .run(
+            &mut context,
+            "migrate_item_frozen_delegate",
+            instruction,
+            &[&payer],
+        )
+        .await;
+
+    let instruction = migratorr
+        .migrate_accounts(
+            &mut context,
+            &payer,
+            collection_nft.mint_pubkey(),
+            collection_nft.token_pubkey(),
+            token_owner,
+            collection_nft.mint_pubkey(),
+        )
+        .await
+        .collection_parent_instruction()
+        .unwrap();
+    bench
+        .run(
+            &mut context,
+            "migrate_collection_parent",
+            instruction,
+            &[&payer],
+        )
+        .await;
+
+    // Every item has been migrated, so the bounty and the payer record can be closed.
+    bench
+        .run(
+            &mut context,
+            "finalize_bounty",
+            finalize_bounty(payer.pubkey(), collection_mint),
+            &[&payer],
+        )
+        .await;
+
+    bench
+        .run(
+            &mut context,
+            "close_payer_record",
+            close_payer_record(payer.pubkey(), collection_mint),
+            &[&payer],
+        )
+        .await;
+
+    // A legacy migration state is upgraded, then closed before it is started.
+    let mut legacy_nft = NfTest::new();
+    legacy_nft.mint_default(&mut context, None).await.unwrap();
+    let legacy_migratorr = Migratorr::new(legacy_nft.mint_pubkey());
+    legacy_migratorr
+        .inject_legacy_state(
+            &mut context,
+            MigrationStateV0 {
+                collection_info: CollectionInfoV0 {
+                    authority: payer.pubkey(),
+                    mint: legacy_nft.mint_pubkey(),
+                    rule_set: Pubkey::default(),
+                    delegate_record: Pubkey::default(),
+                    size: 1,
+                },
+                unlock_method: UnlockMethod::Timed,
+                status: MigrationStatusV0 {
+                    unlock_time: 0,
+                    is_locked: true,
+                    in_progress: false,
+                    items_migrated: 0,
+                },
+            },
+        )
+        .await;
+
+    bench
+        .run(
+            &mut context,
+            "upgrade_state",
+            upgrade_state(payer.pubkey(), legacy_nft.mint_pubkey()),
+            &[&payer],
+        )
+        .await;
+
+    bench
+        .run(
+            &mut context,
+            "close",
+            close(payer.pubkey(), legacy_migratorr.pubkey()),
+            &[&payer],
+        )
+        .await;
+
+    if std::env::var_os("UPDATE_COMPUTE_BUDGET").is_some() {
+        write_budget(&bench.measured);
+    }
+
+    let budget = load_budget();
+    let report = bench.report(&budget);
+    println!("{}", report);
+    fs::write(manifest_dir().join("../bench_output.txt"), &report).unwrap();
+
+    let failures: Vec<String> = bench
+        .measured
+        .iter()
+        .filter_map(|(case, units)| match budget.get(case) {
+            Some(budget) if units > budget => Some(format!("{}: {} > {}", case, units, budget)),
+            Some(budget) if *budget > with_headroom(*units, STALE_HEADROOM_PERCENT) => Some(
+                format!("{}: budget {} is stale for {} units", case, budget, units),
+            ),
+            Some(_) => None,
+            None => Some(format!("{}: no budget", case)),
+        })
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "compute units do not match the budget, regenerate it with \
+         UPDATE_COMPUTE_BUDGET=1 if the change is intended:\n{}",
+        failures.join("\n")
+    );
+}
@@ -0,0 +1,334 @@
+use std::collections::BTreeMap;
+
+use borsh::BorshDeserialize;
+use mpl_migration_validator::{
+    errors::MigrationError,
+    instruction::{
+        accept_authority, close, close_payer_record, finalize_bounty, fund_bounty, init_signer,
+        initialize, migrate_collection_parent, migrate_item, set_owner_program_list, start,
+        sync_authority, update, upgrade_state, FundBountyArgs, InitializeArgs,
+        MigrationInstruction, SetOwnerProgramListArgs, UpdateArgs,
+    },
+    state::{ListType, UnlockMethod},
+};
+use num_traits::FromPrimitive;
+use serde_json::Value;
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
+
+const INSTRUCTION_SOURCE: &str = include_str!("../src/instruction.rs");
+/// The committed IDL, which the SDK is generated from. Its instructions and error codes must
+/// match the program, so a stale IDL fails the tests. Error messages are not compared, as
+/// the program does not keep them.
+const IDL: &str = include_str!("../../packages/sdk/idl/mpl_migration_validator.json");
+
+/// Accounts the builders may pass as signers even though the instruction does not require
+/// them to sign, as `(instruction, account)`.
+const OPTIONAL_SIGNERS: &[(&str, &str)] = &[("SyncAuthority", "authority")];
+
+/// An account as declared by a Shank `#[account(...)]` attribute.
+#[derive(Debug)]
+struct IdlAccount {
+    index: usize,
+    name: String,
+    writable: bool,
+    signer: bool,
+    optional: bool,
+}
+
+/// Reads the Shank account attributes of every `MigrationInstruction` variant.
+fn shank_accounts() -> BTreeMap<String, Vec<IdlAccount>> {
+    let start = INSTRUCTION_SOURCE
+        .find("pub enum MigrationInstruction {")
+        .expect("MigrationInstruction enum not found");
+    let body = &INSTRUCTION_SOURCE[start..];
+    let body = &body[..body.find("\n}\n").expect("unterminated enum")];
+
+    let mut variants = BTreeMap::new();
+    let mut accounts = vec![];
+
+    for line in body.lines().skip(1).map(str::trim) {
+        if let Some(attribute) = line.strip_prefix("#[account(") {
+            let attribute = attribute.strip_suffix(")]").expect("malformed attribute");
+            accounts.push(parse_account(attribute));
+        } else if line.starts_with(|c: char| c.is_ascii_uppercase()) {
+            let name = line
+                .split(|c: char| c == '(' || c == ',')
+                .next()
+                .unwrap()
+                .to_string();
+            variants.insert(name, std::mem::take(&mut accounts));
+        }
+    }
+
+    variants
+}
+
+fn parse_account(attribute: &str) -> IdlAccount {
+    let mut account = IdlAccount {
+        index: usize::MAX,
+        name: String::new(),
+        writable: false,
+        signer: false,
+        optional: false,
+    };
+
+    // Split on the commas outside of quoted values.
+    let mut in_quotes = false;
+    let parts = attribute.split(|c: char| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        c == ',' && !in_quotes
+    });
+
+    for (i, part) in parts.map(str::trim).enumerate() {
+        if i == 0 {
+            account.index = part.parse().expect("account index");
+            continue;
+        }
+
+        match part.split_once('=') {
+            Some((key, value)) if key.trim() == "name" => {
+                account.name = value.trim().trim_matches('"').to_string();
+            }
+            Some(_) => {}
+            None if part == "writable" => account.writable = true,
+            None if part == "signer" => account.signer = true,
+            None if part == "optional" => account.optional = true,
+            None => {}
+        }
+    }
+
+    account
+}
+
+fn idl() -> Value {
+    serde_json::from_str(IDL).expect("malformed IDL")
+}
+
+/// Shank names IDL instructions after the variant and accounts in camel case.
+fn camel_case(name: &str) -> String {
+    let mut parts = name.split('_');
+    let mut camel = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            camel.push(first.to_ascii_uppercase());
+            camel.extend(chars);
+        }
+    }
+    camel
+}
+
+fn idl_instruction<'a>(idl: &'a Value, variant: &str) -> &'a Value {
+    idl["instructions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|ix| ix["name"] == variant)
+        .unwrap_or_else(|| panic!("{} missing from the IDL", variant))
+}
+
+fn variant_name(instruction: &Instruction) -> String {
+    let ix = MigrationInstruction::try_from_slice(&instruction.data).unwrap();
+    format!("{:?}", ix)
+        .split(|c: char| c == '(' || c == ' ')
+        .next()
+        .unwrap()
+        .to_string()
+}
+
+fn builder_instructions() -> Vec<Instruction> {
+    let key = Pubkey::new_unique;
+    let mint = key();
+
+    let update_args = UpdateArgs {
+        rule_set: None,
+        collection_size: None,
+        new_update_authority: None,
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
+    };
+
+    vec![
+        initialize(
+            key(),
+            key(),
+            mint,
+            InitializeArgs {
+                rule_set: None,
+                unlock_method: UnlockMethod::Timed,
+                collection_size: 0,
+            },
+        ),
+        close(key(), key()),
+        update(key(), mint, update_args),
+        init_signer(key()),
+        start(key(), key(), mint),
+        migrate_item(key(), key(), key(), key(), key(), None, mint, key()),
+        set_owner_program_list(
+            key(),
+            key(),
+            mint,
+            SetOwnerProgramListArgs {
+                list_type: ListType::Deny,
+                programs: vec![],
+            },
+        ),
+        migrate_collection_parent(key(), mint, key(), key(), key(), None, key()),
+        upgrade_state(key(), mint),
+        fund_bounty(
+            key(),
+            mint,
+            FundBountyArgs {
+                lamports: 0,
+                reward: None,
+            },
+        ),
+        finalize_bounty(key(), mint),
+        accept_authority(key(), key()),
+        sync_authority(key(), Some(key()), mint),
+        close_payer_record(key(), mint),
+    ]
+}
+
+#[test]
+fn every_instruction_has_a_builder() {
+    let variants = shank_accounts();
+    let built: Vec<String> = builder_instructions().iter().map(variant_name).collect();
+
+    for variant in variants.keys() {
+        assert!(built.contains(variant), "no builder for {}", variant);
+    }
+}
+
+#[test]
+fn shank_accounts_match_builders() {
+    let variants = shank_accounts();
+
+    for instruction in builder_instructions() {
+        let variant = variant_name(&instruction);
+        let accounts = &variants[&variant];
+
+        assert_eq!(
+            accounts.len(),
+            instruction.accounts.len(),
+            "{}: account count",
+            variant
+        );
+
+        for (i, (account, meta)) in accounts.iter().zip(&instruction.accounts).enumerate() {
+            assert_eq!(account.index, i, "{}: account order", variant);
+            assert!(
+                !account.name.contains(','),
+                "{}: malformed account name {:?}",
+                variant,
+                account.name
+            );
+            assert_eq!(
+                account.writable, meta.is_writable,
+                "{}: writability of {}",
+                variant, account.name
+            );
+            let optional_signer =
+                OPTIONAL_SIGNERS.contains(&(variant.as_str(), account.name.as_str()));
+            if optional_signer {
+                assert!(
+                    !account.signer,
+                    "{}: {} must not be a signer",
+                    variant, account.name
+                );
+            } else {
+                assert_eq!(
+                    account.signer, meta.is_signer,
+                    "{}: signer of {}",
+                    variant, account.name
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn idl_instructions_match_shank_accounts() {
+    let idl = idl();
+    let variants = shank_accounts();
+    let instructions = idl["instructions"].as_array().unwrap();
+
+    assert_eq!(instructions.len(), variants.len(), "instruction count");
+
+    for instruction in builder_instructions() {
+        let variant = variant_name(&instruction);
+        let ix = idl_instruction(&idl, &variant);
+        assert_eq!(
+            ix["discriminant"]["value"], instruction.data[0],
+            "{}: discriminant",
+            variant
+        );
+
+        let accounts = &variants[&variant];
+        let idl_accounts = ix["accounts"].as_array().unwrap();
+        assert_eq!(
+            accounts.len(),
+            idl_accounts.len(),
+            "{}: account count",
+            variant
+        );
+
+        for (account, idl_account) in accounts.iter().zip(idl_accounts) {
+            assert_eq!(
+                idl_account["name"],
+                camel_case(&account.name),
+                "{}: account order",
+                variant
+            );
+            assert_eq!(
+                idl_account["isMut"], account.writable,
+                "{}: writability of {}",
+                variant, account.name
+            );
+            assert_eq!(
+                idl_account["isSigner"], account.signer,
+                "{}: signer of {}",
+                variant, account.name
+            );
+            assert_eq!(
+                idl_account["optional"].as_bool().unwrap_or_default(),
+                account.optional,
+                "{}: optionality of {}",
+                variant,
+                account.name
+            );
+        }
+    }
+}
+
+#[test]
+fn idl_errors_match_migration_errors() {
+    let idl = idl();
+    let errors = idl["errors"].as_array().unwrap();
+
+    for (i, error) in errors.iter().enumerate() {
+        let code = error["code"].as_u64().unwrap();
+        assert_eq!(code, i as u64, "error order");
+
+        let migration_error = MigrationError::from_u64(code)
+            .unwrap_or_else(|| panic!("no migration error with code {}", code));
+        assert_eq!(
+            error["name"],
+            format!("{:?}", migration_error),
+            "name of {}",
+            code
+        );
+    }
+
+    // The IDL must cover every error, not only a prefix of them.
+    let next_code = errors.len() as u64;
+    assert!(
+        MigrationError::from_u64(next_code).is_none(),
+        "{:?} missing from the IDL",
+        MigrationError::from_u64(next_code)
+    );
+}
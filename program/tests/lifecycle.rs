@@ -0,0 +1,341 @@
+//! Model-based test of the migration lifecycle.
+//!
+//! Random sequences of Update, Start, Migrate, Close and clock warps run against the program,
+//! and every outcome and resulting status is compared to a reference model of the
+//! `MigrationStatus` transitions.
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_migration_validator::{errors::MigrationError, instruction::UpdateArgs};
+use num_traits::FromPrimitive;
+use proptest::{prelude::*, test_runner::TestCaseError};
+use solana_program::clock::Clock;
+use solana_program_test::{tokio, BanksClientError, ProgramTestContext};
+use solana_sdk::{instruction::InstructionError, signer::Signer, transaction::TransactionError};
+
+use utils::*;
+
+// Number of verified items in the collection, not counting the collection parent.
+const ITEMS: usize = 2;
+
+// Seconds between the creation of the migration state and its unlock time.
+const WAIT_PERIOD: i64 = 1_000;
+
+const START_TIME: i64 = 1_700_000_000;
+
+#[derive(Debug, Clone)]
+enum Op {
+    // Updates the state, optionally setting the rule set to its current value.
+    Update { rule_set: bool },
+    Start,
+    Migrate(usize),
+    MigrateCollectionParent,
+    Close,
+    // Moves the clock forward by a number of seconds.
+    Warp(i64),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    // Warps and updates are weighted up so most sequences unlock the migration.
+    prop_oneof![
+        2 => any::<bool>().prop_map(|rule_set| Op::Update { rule_set }),
+        1 => Just(Op::Start),
+        2 => (0..ITEMS).prop_map(Op::Migrate),
+        1 => Just(Op::MigrateCollectionParent),
+        1 => Just(Op::Close),
+        2 => (0..=WAIT_PERIOD / 2).prop_map(Op::Warp),
+    ]
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expected {
+    Ok,
+    Err(MigrationError),
+}
+
+/// Reference model of a timed migration of a collection whose parent is migrated last.
+#[derive(Debug, Clone)]
+struct Model {
+    exists: bool,
+    now: i64,
+    unlock_time: i64,
+    is_locked: bool,
+    in_progress: bool,
+    items_migrated: u32,
+    collection_parent_migrated: bool,
+    migrated: [bool; ITEMS],
+}
+
+impl Model {
+    fn new() -> Self {
+        Self {
+            exists: true,
+            now: START_TIME,
+            unlock_time: START_TIME + WAIT_PERIOD,
+            is_locked: true,
+            in_progress: false,
+            items_migrated: 0,
+            collection_parent_migrated: false,
+            migrated: [false; ITEMS],
+        }
+    }
+
+    fn has_migrated(&self) -> bool {
+        self.items_migrated > 0 || self.collection_parent_migrated
+    }
+
+    fn apply(&mut self, op: &Op) -> Expected {
+        if let Op::Warp(seconds) = op {
+            self.now += seconds;
+            return Expected::Ok;
+        }
+
+        // A closed migration state is an empty account owned by the system program.
+        if !self.exists && !matches!(op, Op::Migrate(_) | Op::MigrateCollectionParent) {
+            return Expected::Err(MigrationError::IncorrectMigrationStateProgramOwner);
+        }
+
+        match *op {
+            Op::Update { rule_set } => {
+                if rule_set && self.has_migrated() {
+                    return Expected::Err(MigrationError::MigrationInProgress);
+                }
+                if self.now >= self.unlock_time {
+                    self.is_locked = false;
+                }
+            }
+            Op::Start => {
+                if self.is_locked {
+                    return Expected::Err(MigrationError::MigrationLocked);
+                }
+                if self.has_migrated() {
+                    return Expected::Err(MigrationError::MigrationInProgress);
+                }
+                self.in_progress = true;
+            }
+            // Items can only be migrated with the delegate record set up by Start, and the
+            // migration order is checked before the item's eligibility.
+            Op::Migrate(item) => {
+                if !self.in_progress {
+                    return Expected::Err(MigrationError::IncorrectDelegateRecordProgramOwner);
+                }
+                if self.collection_parent_migrated {
+                    return Expected::Err(MigrationError::CollectionParentAlreadyMigrated);
+                }
+                if self.migrated[item] {
+                    return Expected::Err(MigrationError::IncorrectTokenStandard);
+                }
+                self.migrated[item] = true;
+                self.items_migrated += 1;
+            }
+            Op::MigrateCollectionParent => {
+                if !self.in_progress {
+                    return Expected::Err(MigrationError::IncorrectDelegateRecordProgramOwner);
+                }
+                if self.collection_parent_migrated {
+                    return Expected::Err(MigrationError::CollectionParentAlreadyMigrated);
+                }
+                if self.items_migrated < ITEMS as u32 {
+                    return Expected::Err(MigrationError::CollectionItemsNotMigrated);
+                }
+                self.collection_parent_migrated = true;
+            }
+            Op::Close => {
+                if self.in_progress {
+                    return Expected::Err(MigrationError::MigrationInProgress);
+                }
+                if self.has_migrated() {
+                    return Expected::Err(MigrationError::MigrationAlreadyCompleted);
+                }
+                self.exists = false;
+            }
+            Op::Warp(_) => unreachable!(),
+        }
+
+        Expected::Ok
+    }
+}
+
+fn migration_error(error: &BanksClientError) -> Option<MigrationError> {
+    match error {
+        BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) => MigrationError::from_u32(*code),
+        _ => None,
+    }
+}
+
+fn outcome_matches(expected: Expected, result: &Result<(), BanksClientError>) -> bool {
+    match (expected, result) {
+        (Expected::Ok, Ok(())) => true,
+        (Expected::Err(expected), Err(error)) => migration_error(error) == Some(expected),
+        _ => false,
+    }
+}
+
+// Moves to a new slot, so repeated instructions are not deduplicated, and sets the clock to
+// the model's time.
+async fn next_slot(context: &mut ProgramTestContext, unix_timestamp: i64) {
+    warp100(context).await;
+    context.last_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = unix_timestamp;
+    context.set_sysvar(&clock);
+}
+
+async fn run(ops: Vec<Op>) -> Result<(), TestCaseError> {
+    let mut context = setup_pnft_context().await;
+    let payer = context.payer.dirty_clone();
+
+    let mut model = Model::new();
+
+    let TestCollection {
+        parent: collection_nft,
+        items,
+        rule_set,
+        mut migratorr,
+        ..
+    } = TestCollection::builder()
+        .items(ITEMS)
+        .unlock_time(model.unlock_time)
+        .build(&mut context)
+        .await
+        .unwrap();
+
+    for (step, op) in ops.iter().enumerate() {
+        let expected = model.apply(op);
+        next_slot(&mut context, model.now).await;
+
+        let result = match *op {
+            Op::Update {
+                rule_set: set_rule_set,
+            } => {
+                let args = UpdateArgs {
+                    rule_set: set_rule_set.then(|| rule_set),
+                    collection_size: None,
+                    new_update_authority: None,
+                    delegate_handling: None,
+                    collection_parent_order: None,
+                    update_authority_mode: None,
+                    release_collection_parent: None,
+                };
+                migratorr.update(&mut context, &payer, args).await
+            }
+            Op::Start => {
+                migratorr
+                    .start(&mut context, &payer, &payer, &collection_nft)
+                    .await
+            }
+            Op::Migrate(item) => {
+                migratorr
+                    .migrate_item(
+                        &mut context,
+                        &payer,
+                        collection_nft.mint_pubkey(),
+                        payer.pubkey(),
+                        &items[item],
+                    )
+                    .await
+            }
+            Op::MigrateCollectionParent => {
+                migratorr
+                    .migrate_collection_parent(
+                        &mut context,
+                        &payer,
+                        payer.pubkey(),
+                        &collection_nft,
+                    )
+                    .await
+            }
+            Op::Close => migratorr.close(&mut context, &payer).await,
+            Op::Warp(_) => Ok(()),
+        };
+
+        prop_assert!(
+            outcome_matches(expected, &result),
+            "step {} {:?}: expected {:?}, got {:?}",
+            step,
+            op,
+            expected,
+            result
+        );
+
+        let account = context
+            .banks_client
+            .get_account(migratorr.pubkey())
+            .await
+            .unwrap();
+        prop_assert_eq!(account.is_some(), model.exists, "step {} {:?}", step, op);
+        if !model.exists {
+            continue;
+        }
+
+        migratorr.refresh_state(&mut context).await.unwrap();
+        let status = &migratorr.state().status;
+        let actual = (
+            status.is_locked,
+            status.in_progress,
+            status.items_migrated,
+            status.collection_parent_migrated,
+        );
+        let modeled = (
+            model.is_locked,
+            model.in_progress,
+            model.items_migrated,
+            model.collection_parent_migrated,
+        );
+        prop_assert_eq!(actual, modeled, "step {} {:?}", step, op);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn close_after_partial_migration_is_rejected() {
+    let ops = vec![
+        Op::Start,
+        Op::Warp(WAIT_PERIOD),
+        Op::Start,
+        Op::Update { rule_set: false },
+        Op::Start,
+        Op::Close,
+        Op::Migrate(0),
+        Op::Update { rule_set: true },
+        Op::Close,
+        Op::MigrateCollectionParent,
+        Op::Migrate(1),
+        Op::MigrateCollectionParent,
+    ];
+
+    run(ops).await.unwrap();
+}
+
+// Each case sets up a fresh bank, so only a few sequences are run unless PROPTEST_CASES
+// asks for more.
+fn config() -> ProptestConfig {
+    let default = ProptestConfig::default();
+    ProptestConfig {
+        cases: if std::env::var_os("PROPTEST_CASES").is_some() {
+            default.cases
+        } else {
+            8
+        },
+        max_shrink_iters: 32,
+        ..default
+    }
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn lifecycle_matches_model(ops in proptest::collection::vec(op(), 1..24)) {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(run(ops))?;
+    }
+}
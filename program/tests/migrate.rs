@@ -1,9 +1,13 @@
 #![cfg(feature = "test-bpf")]
 pub mod utils;
 
-use mpl_migration_validator::{instruction::InitializeArgs, state::UnlockMethod, PROGRAM_SIGNER};
+use mpl_migration_validator::{
+    utils::{find_collection_signer_pda, find_legacy_delegate_record_pda, find_payer_record_pda},
+    PROGRAM_SIGNER,
+};
 use mpl_token_metadata::{
-    pda::find_collection_authority_account,
+    instruction::approve_collection_authority,
+    pda::{find_collection_authority_account, find_token_record_account},
     state::{CollectionAuthorityRecord, TokenDelegateRole, TokenMetadataAccount, TokenState},
 };
 use num_traits::FromPrimitive;
@@ -17,6 +21,11 @@ use solana_sdk::{
 use utils::*;
 
 mod eligible_scenarios {
+    use mpl_migration_validator::{
+        errors::MigrationError, instruction::UpdateArgs, state::UpdateAuthorityMode,
+    };
+    use solana_sdk::transaction::Transaction;
+
     use super::*;
 
     #[tokio::test]
@@ -32,101 +41,26 @@ mod eligible_scenarios {
         // TokenRecord State:    Unlocked
         let mut context = setup_pnft_context().await;
 
-        let collection_authority = context.payer.dirty_clone();
-        let collection_authority_pubkey = collection_authority.pubkey();
-
-        // We create a collection with three NFTs in it.
-        let mut collection_nft = NfTest::new();
-        collection_nft
-            .mint_default(&mut context, None)
+        let mut collection = TestCollection::builder()
+            .items(3)
+            .build(&mut context)
             .await
             .unwrap();
 
-        let mut nft1 = NfTest::new();
-        nft1.mint_default(&mut context, None).await.unwrap();
-        nft1.set_and_verify_collection(
-            &mut context,
-            SetAndVerifyCollectionArgs {
-                collection_metadata: collection_nft.metadata_pubkey(),
-                collection_authority: collection_authority.dirty_clone(),
-                nft_update_authority: collection_authority.pubkey(),
-                collection_mint: collection_nft.mint_pubkey(),
-                collection_master_edition_account: collection_nft.edition_pubkey().unwrap(),
-                collection_authority_record: None,
-            },
-        )
-        .await
-        .unwrap();
-
-        let mut nft2 = NfTest::new();
-        nft2.mint_default(&mut context, None).await.unwrap();
-        nft2.set_and_verify_collection(
-            &mut context,
-            SetAndVerifyCollectionArgs {
-                collection_metadata: collection_nft.metadata_pubkey(),
-                collection_authority: collection_authority.dirty_clone(),
-                nft_update_authority: collection_authority_pubkey,
-                collection_mint: collection_nft.mint_pubkey(),
-                collection_master_edition_account: collection_nft.edition_pubkey().unwrap(),
-                collection_authority_record: None,
-            },
-        )
-        .await
-        .unwrap();
-
-        let mut nft3 = NfTest::new();
-        nft3.mint_default(&mut context, None).await.unwrap();
-        nft3.set_and_verify_collection(
-            &mut context,
-            SetAndVerifyCollectionArgs {
-                collection_metadata: collection_nft.metadata_pubkey(),
-                collection_authority: collection_authority.dirty_clone(),
-                nft_update_authority: collection_authority_pubkey,
-                collection_mint: collection_nft.mint_pubkey(),
-                collection_master_edition_account: collection_nft.edition_pubkey().unwrap(),
-                collection_authority_record: None,
-            },
-        )
-        .await
-        .unwrap();
-
-        let payer = context.payer.dirty_clone();
-
-        // Create default rule set to apply to migrated NFTs.
-        let (rule_set, _auth_rules) = create_default_metaplex_rule_set(&mut context, payer).await;
+        // Unlock the collection and enable migration.
+        collection.start(&mut context).await.unwrap();
 
-        // Create our migration state manager.
-        let mut migratorr = Migratorr::new(collection_nft.mint_pubkey());
+        let TestCollection {
+            parent: collection_nft,
+            items,
+            rule_set,
+            mut migratorr,
+            ..
+        } = collection;
+        let [mut nft1, mut nft2, mut nft3]: [NfTest; 3] = items.try_into().unwrap();
 
         let payer = context.payer.dirty_clone();
 
-        // Initialize the program signer
-        migratorr.init_signer(&mut context, &payer).await.unwrap();
-
-        let args = InitializeArgs {
-            rule_set: Some(rule_set),
-            unlock_method: UnlockMethod::Timed,
-            collection_size: 3,
-        };
-
-        // Initialize the migration state account on-chain
-        migratorr
-            .initialize(&mut context, &payer, &payer, &collection_nft, args)
-            .await
-            .unwrap();
-
-        // Artificially update the timestamp to allow the migration to start
-        // and call update to unlock the collection.
-        migratorr
-            .unlock_collection(&mut context, &collection_authority)
-            .await;
-
-        // Now we try to start the migration expecting it to succeed.
-        migratorr
-            .start(&mut context, &payer, &payer, &collection_nft)
-            .await
-            .unwrap();
-
         // Refresh the migratorr's state from the on-chain account.
         migratorr.refresh_state(&mut context).await.unwrap();
 
@@ -135,9 +69,10 @@ mod eligible_scenarios {
         assert!(!migratorr.state().status.is_locked);
         assert_eq!(migratorr.rule_set(), rule_set);
 
-        // Ensure the collection delegate was created.
+        // Ensure the collection delegate was created for the collection's program signer.
+        let (program_signer, _) = find_collection_signer_pda(&migratorr.mint());
         let (delegate_record_pda, bump) =
-            find_collection_authority_account(&migratorr.mint(), &PROGRAM_SIGNER);
+            find_collection_authority_account(&migratorr.mint(), &program_signer);
 
         // This function call panics if the account doesn't exist.
         let delegate_record_account = get_account(&mut context, &delegate_record_pda).await;
@@ -197,16 +132,35 @@ mod eligible_scenarios {
 
         // Migrate the collection NFT at the end.
         migratorr
-            .migrate_item(
-                &mut context,
-                &payer,
-                collection_nft.mint_pubkey(),
-                token_owner,
-                &collection_nft,
-            )
+            .migrate_collection_parent(&mut context, &payer, token_owner, &collection_nft)
             .await
             .unwrap();
 
+        // The collection parent is recorded separately from the items.
+        migratorr.refresh_state(&mut context).await.unwrap();
+        assert_eq!(migratorr.state().status.items_migrated, 3);
+        assert!(migratorr.state().status.collection_parent_migrated);
+
+        // The token record rent for all four migrations was paid by a single payer.
+        let mut token_record_rent = 0;
+        for nft in [&nft1, &nft2, &nft3, &collection_nft] {
+            let (token_record_pda, _) =
+                find_token_record_account(&nft.mint_pubkey(), &nft.token_pubkey());
+            token_record_rent += get_account(&mut context, &token_record_pda).await.lamports;
+        }
+
+        let status = &migratorr.state().status;
+        assert_eq!(status.token_record_rent_paid, token_record_rent);
+        assert_eq!(status.unique_payers, 1);
+
+        // The payer record rent is tracked separately.
+        let (payer_record, _) =
+            find_payer_record_pda(&collection_nft.mint_pubkey(), &payer.pubkey());
+        let payer_record_rent = get_account(&mut context, &payer_record).await.lamports;
+        assert_eq!(migratorr.state().payer_record_rent_paid, payer_record_rent);
+        assert!(status.first_migration_slot <= status.last_migration_slot);
+        assert!(status.first_migration_timestamp <= status.last_migration_timestamp);
+
         // The rule set should match.
         // NFTs had no SPL token delegate so their delegate and role should be None.
         // The NFTs should be unlocked because they weren't frozen initially.
@@ -257,69 +211,26 @@ mod eligible_scenarios {
         // TokenRecord State:    Unlocked
         let mut context = setup_pnft_context().await;
 
-        let collection_authority = context.payer.dirty_clone();
-        let collection_authority_pubkey = collection_authority.pubkey();
-
-        // We create a collection to contain the NFT.
-        let mut collection_nft = NfTest::new();
-        collection_nft
-            .mint_default(&mut context, None)
+        let mut collection = TestCollection::builder()
+            .items(1)
+            .build(&mut context)
             .await
             .unwrap();
 
-        let mut nft = NfTest::new();
-        nft.mint_default(&mut context, None).await.unwrap();
-        nft.set_and_verify_collection(
-            &mut context,
-            SetAndVerifyCollectionArgs {
-                collection_metadata: collection_nft.metadata_pubkey(),
-                collection_authority: collection_authority.dirty_clone(),
-                nft_update_authority: collection_authority_pubkey,
-                collection_mint: collection_nft.mint_pubkey(),
-                collection_master_edition_account: collection_nft.edition_pubkey().unwrap(),
-                collection_authority_record: None,
-            },
-        )
-        .await
-        .unwrap();
-
-        let payer = context.payer.dirty_clone();
-
-        // Create default rule set to apply to migrated NFTs.
-        let (rule_set, _auth_rules) = create_default_metaplex_rule_set(&mut context, payer).await;
+        // Unlock the collection and enable migration.
+        collection.start(&mut context).await.unwrap();
 
-        // Create our migration state manager.
-        let mut migratorr = Migratorr::new(collection_nft.mint_pubkey());
+        let TestCollection {
+            parent: collection_nft,
+            items,
+            rule_set,
+            mut migratorr,
+            ..
+        } = collection;
+        let [mut nft]: [NfTest; 1] = items.try_into().unwrap();
 
         let payer = context.payer.dirty_clone();
 
-        // Initialize the program signer
-        migratorr.init_signer(&mut context, &payer).await.unwrap();
-
-        let args = InitializeArgs {
-            rule_set: Some(rule_set), // this defaults to the default public key
-            unlock_method: UnlockMethod::Timed,
-            collection_size: 1,
-        };
-
-        // Initialize the migration state account on-chain
-        migratorr
-            .initialize(&mut context, &payer, &payer, &collection_nft, args)
-            .await
-            .unwrap();
-
-        // Artificially update the timestamp to allow the migration to start
-        // and call update to unlock the collection.
-        migratorr
-            .unlock_collection(&mut context, &collection_authority)
-            .await;
-
-        // Enable migration
-        migratorr
-            .start(&mut context, &payer, &payer, &collection_nft)
-            .await
-            .unwrap();
-
         // Assign a spl token delegate to the NFT
         let delegate = Keypair::new();
 
@@ -369,69 +280,26 @@ mod eligible_scenarios {
         // TokenRecord State:    Locked
         let mut context = setup_pnft_context().await;
 
-        let collection_authority = context.payer.dirty_clone();
-        let collection_authority_pubkey = collection_authority.pubkey();
-
-        // We create a collection to contain the NFT.
-        let mut collection_nft = NfTest::new();
-        collection_nft
-            .mint_default(&mut context, None)
+        let mut collection = TestCollection::builder()
+            .items(1)
+            .build(&mut context)
             .await
             .unwrap();
 
-        let mut nft = NfTest::new();
-        nft.mint_default(&mut context, None).await.unwrap();
-        nft.set_and_verify_collection(
-            &mut context,
-            SetAndVerifyCollectionArgs {
-                collection_metadata: collection_nft.metadata_pubkey(),
-                collection_authority: collection_authority.dirty_clone(),
-                nft_update_authority: collection_authority_pubkey,
-                collection_mint: collection_nft.mint_pubkey(),
-                collection_master_edition_account: collection_nft.edition_pubkey().unwrap(),
-                collection_authority_record: None,
-            },
-        )
-        .await
-        .unwrap();
-
-        let payer = context.payer.dirty_clone();
-
-        // Create default rule set to apply to migrated NFTs.
-        let (rule_set, _auth_rules) = create_default_metaplex_rule_set(&mut context, payer).await;
+        // Unlock the collection and enable migration.
+        collection.start(&mut context).await.unwrap();
 
-        // Create our migration state manager.
-        let mut migratorr = Migratorr::new(collection_nft.mint_pubkey());
+        let TestCollection {
+            parent: collection_nft,
+            items,
+            rule_set,
+            mut migratorr,
+            ..
+        } = collection;
+        let [mut nft]: [NfTest; 1] = items.try_into().unwrap();
 
         let payer = context.payer.dirty_clone();
 
-        // Initialize the program signer
-        migratorr.init_signer(&mut context, &payer).await.unwrap();
-
-        let args = InitializeArgs {
-            rule_set: Some(rule_set), // this defaults to the default public key
-            unlock_method: UnlockMethod::Timed,
-            collection_size: 1,
-        };
-
-        // Initialize the migration state account on-chain
-        migratorr
-            .initialize(&mut context, &payer, &payer, &collection_nft, args)
-            .await
-            .unwrap();
-
-        // Artificially update the timestamp to allow the migration to start
-        // and call update to unlock the collection.
-        migratorr
-            .unlock_collection(&mut context, &collection_authority)
-            .await;
-
-        // Enable migration
-        migratorr
-            .start(&mut context, &payer, &payer, &collection_nft)
-            .await
-            .unwrap();
-
         // Assign a spl token delegate to the NFT
         let owner = context.payer.dirty_clone();
         let delegate = Keypair::new();
@@ -487,69 +355,26 @@ mod eligible_scenarios {
         // TokenRecord State:    Unlocked
         let mut context = setup_pnft_context().await;
 
-        let collection_authority = context.payer.dirty_clone();
-        let collection_authority_pubkey = collection_authority.pubkey();
-
-        // We create a collection to contain the NFT.
-        let mut collection_nft = NfTest::new();
-        collection_nft
-            .mint_default(&mut context, None)
+        let mut collection = TestCollection::builder()
+            .items(1)
+            .build(&mut context)
             .await
             .unwrap();
 
-        let mut nft = NfTest::new();
-        nft.mint_default(&mut context, None).await.unwrap();
-        nft.set_and_verify_collection(
-            &mut context,
-            SetAndVerifyCollectionArgs {
-                collection_metadata: collection_nft.metadata_pubkey(),
-                collection_authority: collection_authority.dirty_clone(),
-                nft_update_authority: collection_authority_pubkey,
-                collection_mint: collection_nft.mint_pubkey(),
-                collection_master_edition_account: collection_nft.edition_pubkey().unwrap(),
-                collection_authority_record: None,
-            },
-        )
-        .await
-        .unwrap();
-
-        let payer = context.payer.dirty_clone();
-
-        // Create default rule set to apply to migrated NFTs.
-        let (rule_set, _auth_rules) = create_default_metaplex_rule_set(&mut context, payer).await;
+        // Unlock the collection and enable migration.
+        collection.start(&mut context).await.unwrap();
 
-        // Create our migration state manager.
-        let mut migratorr = Migratorr::new(collection_nft.mint_pubkey());
+        let TestCollection {
+            parent: collection_nft,
+            items,
+            rule_set,
+            mut migratorr,
+            ..
+        } = collection;
+        let [mut nft]: [NfTest; 1] = items.try_into().unwrap();
 
         let payer = context.payer.dirty_clone();
 
-        // Initialize the program signer
-        migratorr.init_signer(&mut context, &payer).await.unwrap();
-
-        let args = InitializeArgs {
-            rule_set: Some(rule_set), // this defaults to the default public key
-            unlock_method: UnlockMethod::Timed,
-            collection_size: 1,
-        };
-
-        // Initialize the migration state account on-chain
-        migratorr
-            .initialize(&mut context, &payer, &payer, &collection_nft, args)
-            .await
-            .unwrap();
-
-        // Artificially update the timestamp to allow the migration to start
-        // and call update to unlock the collection.
-        migratorr
-            .unlock_collection(&mut context, &collection_authority)
-            .await;
-
-        // Enable migration
-        migratorr
-            .start(&mut context, &payer, &payer, &collection_nft)
-            .await
-            .unwrap();
-
         let owner = context.payer.dirty_clone();
 
         // Simulate a frozen NFT with no delegate by directly injecting the frozen state.
@@ -577,143 +402,441 @@ mod eligible_scenarios {
         .await
         .unwrap();
     }
-}
-
-mod ineligible_scenarios {
-    use mpl_migration_validator::errors::MigrationError;
-
-    use super::*;
 
     #[tokio::test]
-    async fn only_nonfungible_can_migrate() {
-        // Attempt to migrate the following asset types:
-        // * Fungible
-        // * FungibleAsset
-        // * NonFungibleEdition
-        // * ProgrammableNonFungible
-        // These should all fail.
+    async fn item_with_different_update_authority() {
+        // Items whose update authority differs from the collection's are only
+        // migrated when the migration authorizes them through their verified collection.
         let mut context = setup_pnft_context().await;
 
-        let collection_authority = context.payer.dirty_clone();
-
-        // We create a collection to contain the various asset types.
-        let mut collection_nft = NfTest::new();
-        collection_nft
-            .mint_default(&mut context, None)
+        let mut collection = TestCollection::builder()
+            .items(1)
+            .build(&mut context)
             .await
             .unwrap();
 
-        let authority = context.payer.dirty_clone();
-
-        // Create NonFungible asset to migrate to a ProgrammableNonFungible
-        // to test a second migration and to print an edition from.
-        let mut nft = NfTest::new();
-        nft.mint_master_with_supply(&mut context, None, 1)
-            .await
-            .unwrap();
-        nft.set_and_verify_collection(
+        collection.start(&mut context).await.unwrap();
+
+        let TestCollection {
+            authority: collection_authority,
+            parent: collection_nft,
+            items,
+            rule_set,
+            mut migratorr,
+            ..
+        } = collection;
+        let [mut nft]: [NfTest; 1] = items.try_into().unwrap();
+
+        // The item is handed over to its creator.
+        let creator = Keypair::new();
+        nft.set_new_update_authority(
             &mut context,
-            SetAndVerifyCollectionArgs {
-                collection_metadata: collection_nft.metadata_pubkey(),
-                collection_authority: collection_authority.dirty_clone(),
-                nft_update_authority: collection_authority.pubkey(),
-                collection_mint: collection_nft.mint_pubkey(),
-                collection_master_edition_account: collection_nft.edition_pubkey().unwrap(),
-                collection_authority_record: None,
+            SetNewUpdateAuthorityArgs {
+                update_authority: collection_authority.dirty_clone(),
+                new_update_authority: creator.pubkey(),
             },
         )
         .await
         .unwrap();
 
-        let print_edition = TestPrintEdition::new(&nft, 1);
-        print_edition.create(&mut context).await.unwrap();
-        print_edition
-            .set_and_verify_collection(
+        let payer = context.payer.dirty_clone();
+
+        let token_owner = context.payer.pubkey();
+
+        let err = migratorr
+            .migrate_item(
                 &mut context,
-                collection_nft.metadata_pubkey(),
-                &collection_authority,
-                collection_authority.pubkey(),
+                &payer,
                 collection_nft.mint_pubkey(),
-                collection_nft.edition_pubkey().unwrap(),
-                None,
+                token_owner,
+                &nft,
             )
             .await
-            .unwrap();
+            .unwrap_err();
 
-        let fungible = TestAsset::new();
-        fungible
-            .mint_default_fungible(&mut context, &authority)
-            .await
-            .unwrap();
+        assert_custom_error_ix!(0, err, MigrationError::InvalidAuthority);
 
-        fungible
-            .set_and_verify_collection(
+        migratorr
+            .update(
                 &mut context,
-                collection_nft.metadata_pubkey(),
                 &collection_authority,
-                collection_authority.pubkey(),
-                collection_nft.mint_pubkey(),
-                collection_nft.edition_pubkey().unwrap(),
-                None,
+                UpdateArgs {
+                    rule_set: None,
+                    collection_size: None,
+                    new_update_authority: None,
+                    delegate_handling: None,
+                    collection_parent_order: None,
+                    update_authority_mode: Some(UpdateAuthorityMode::VerifiedCollection),
+                    release_collection_parent: None,
+                },
             )
             .await
             .unwrap();
 
-        let fungible_asset = TestAsset::new();
-        fungible_asset
-            .mint_default_fungible_asset(&mut context, &authority)
+        let cranker = Keypair::new();
+        cranker
+            .airdrop(&mut context, LAMPORTS_PER_SOL)
             .await
             .unwrap();
 
-        fungible_asset
-            .set_and_verify_collection(
+        migratorr
+            .migrate_item(
                 &mut context,
-                collection_nft.metadata_pubkey(),
-                &collection_authority,
-                collection_authority.pubkey(),
+                &cranker,
                 collection_nft.mint_pubkey(),
-                collection_nft.edition_pubkey().unwrap(),
-                None,
+                token_owner,
+                &nft,
             )
             .await
             .unwrap();
 
-        // Create default rule set to apply to migrated NFTs.
-        let (rule_set, _auth_rules) =
-            create_default_metaplex_rule_set(&mut context, authority).await;
+        nft.assert_pnft_migration(
+            &mut context,
+            Some(rule_set),
+            None,
+            None,
+            TokenState::Unlocked,
+        )
+        .await
+        .unwrap();
+    }
 
-        // Create our migration state manager.
-        let mut migratorr = Migratorr::new(collection_nft.mint_pubkey());
+    #[tokio::test]
+    async fn verified_collection_items_minted_by_other_authorities() {
+        // With the update authority mode set to VerifiedCollection, items minted
+        // under other update authorities migrate through their verified collection,
+        // while unverified items are still refused.
+        let mut context = setup_pnft_context().await;
 
-        let payer = context.payer.dirty_clone();
+        let mut collection = TestCollection::builder()
+            .items(1)
+            .collection_size(2)
+            .update_authority_mode(UpdateAuthorityMode::VerifiedCollection)
+            .build(&mut context)
+            .await
+            .unwrap();
 
-        // Initialize the program signer
-        migratorr.init_signer(&mut context, &payer).await.unwrap();
+        // A creator mints an item that the collection authority verifies, and
+        // another item that stays outside of the collection.
+        let creator = Keypair::new();
+        creator
+            .airdrop(&mut context, LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
 
-        let args = InitializeArgs {
-            rule_set: Some(rule_set), // this defaults to the default public key
-            unlock_method: UnlockMethod::Timed,
-            collection_size: 1,
-        };
+        let mut foreign_nft = NfTest::new();
+        foreign_nft
+            .mint_default(&mut context, Some(creator.dirty_clone()))
+            .await
+            .unwrap();
+        foreign_nft
+            .set_and_verify_collection(
+                &mut context,
+                SetAndVerifyCollectionArgs {
+                    collection_metadata: collection.parent.metadata_pubkey(),
+                    collection_authority: collection.authority.dirty_clone(),
+                    nft_update_authority: creator.pubkey(),
+                    collection_mint: collection.parent.mint_pubkey(),
+                    collection_master_edition_account: collection.parent.edition_pubkey().unwrap(),
+                    collection_authority_record: None,
+                },
+            )
+            .await
+            .unwrap();
 
-        // Initialize the migration state account on-chain
-        migratorr
-            .initialize(&mut context, &payer, &payer, &collection_nft, args)
+        let mut stray_nft = NfTest::new();
+        stray_nft
+            .mint_default(&mut context, Some(creator.dirty_clone()))
             .await
             .unwrap();
 
-        // Artificially update the timestamp to allow the migration to start
-        // and call update to unlock the collection.
+        collection.start(&mut context).await.unwrap();
+
+        let TestCollection {
+            parent: collection_nft,
+            items,
+            rule_set,
+            mut migratorr,
+            ..
+        } = collection;
+        let [mut nft]: [NfTest; 1] = items.try_into().unwrap();
+
+        let payer = context.payer.dirty_clone();
+
+        // Error: CollectionNotFound
+        let err = migratorr
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                creator.pubkey(),
+                &stray_nft,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::CollectionNotFound);
+
         migratorr
-            .unlock_collection(&mut context, &collection_authority)
-            .await;
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                creator.pubkey(),
+                &foreign_nft,
+            )
+            .await
+            .unwrap();
 
-        // Enable migration
+        // Items under the collection's own update authority still migrate.
         migratorr
-            .start(&mut context, &payer, &payer, &collection_nft)
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                payer.pubkey(),
+                &nft,
+            )
+            .await
+            .unwrap();
+
+        for nft in [&mut foreign_nft, &mut nft] {
+            nft.assert_pnft_migration(
+                &mut context,
+                Some(rule_set),
+                None,
+                None,
+                TokenState::Unlocked,
+            )
+            .await
+            .unwrap();
+        }
+
+        migratorr.refresh_state(&mut context).await.unwrap();
+        assert_eq!(migratorr.state().status.items_migrated, 2);
+    }
+
+    #[tokio::test]
+    async fn legacy_program_signer_migration() {
+        // Migrations started before collections had their own signer delegated to
+        // the global program signer, and carry on through it until they complete.
+        let mut context = setup_pnft_context().await;
+
+        let TestCollection {
+            authority,
+            parent: collection_nft,
+            items,
+            rule_set,
+            mut migratorr,
+            ..
+        } = TestCollection::builder()
+            .items(1)
+            .build(&mut context)
+            .await
+            .unwrap();
+        let [mut nft]: [NfTest; 1] = items.try_into().unwrap();
+
+        let payer = context.payer.dirty_clone();
+
+        migratorr.init_signer(&mut context, &payer).await.unwrap();
+
+        // Delegate the collection to the global program signer, as Start used to.
+        let (delegate_record, delegate_record_bump) =
+            find_legacy_delegate_record_pda(&collection_nft.mint_pubkey());
+
+        let instruction = approve_collection_authority(
+            mpl_token_metadata::ID,
+            delegate_record,
+            PROGRAM_SIGNER,
+            authority.pubkey(),
+            payer.pubkey(),
+            collection_nft.metadata_pubkey(),
+            collection_nft.mint_pubkey(),
+        );
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer, &authority],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        // The migration is in progress with the legacy delegate recorded.
+        let mut state = migratorr.state().clone();
+        state.collection_info.delegate_record = delegate_record;
+        state.delegate_record_bump = delegate_record_bump;
+        state.status.is_locked = false;
+        state.status.in_progress = true;
+        migratorr.create_state(&mut context, state).await;
+
+        let instruction = migratorr
+            .migrate_accounts(
+                &mut context,
+                &payer,
+                nft.mint_pubkey(),
+                nft.token_pubkey(),
+                payer.pubkey(),
+                collection_nft.mint_pubkey(),
+            )
+            .await
+            .legacy_signer()
+            .instruction()
+            .unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        migratorr.refresh_state(&mut context).await.unwrap();
+        assert_eq!(migratorr.state().status.items_migrated, 1);
+
+        nft.assert_pnft_migration(
+            &mut context,
+            Some(rule_set),
+            None,
+            None,
+            TokenState::Unlocked,
+        )
+        .await
+        .unwrap();
+    }
+}
+
+mod ineligible_scenarios {
+    use borsh::BorshSerialize;
+    use mpl_migration_validator::{
+        errors::MigrationError,
+        instruction::{
+            FundBountyArgs, MigrateItemArgs, MigrationInstruction, SetOwnerProgramListArgs,
+            UpdateArgs,
+        },
+        state::{CollectionParentOrder, DelegateHandling, ListType, MigrationAccount, PayerRecord},
+        utils::{find_bounty_vault_pda, find_delegate_record_pda, find_owner_program_list_pda},
+    };
+    use mpl_token_metadata::state::{Edition, Key};
+    use solana_program::{instruction::Instruction, pubkey::Pubkey};
+    use solana_sdk::transaction::Transaction;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn only_nonfungible_can_migrate() {
+        // Attempt to migrate the following asset types:
+        // * Fungible
+        // * FungibleAsset
+        // * NonFungibleEdition
+        // * ProgrammableNonFungible
+        // These should all fail.
+        let mut context = setup_pnft_context().await;
+
+        // We create a collection to contain the various asset types.
+        let mut collection = TestCollection::builder()
+            .collection_size(1)
+            .build(&mut context)
             .await
             .unwrap();
 
+        let collection_authority = collection.authority.dirty_clone();
+        let collection_nft = &collection.parent;
+
+        let authority = context.payer.dirty_clone();
+
+        // Create NonFungible asset to migrate to a ProgrammableNonFungible
+        // to test a second migration and to print an edition from.
+        let mut nft = NfTest::new();
+        nft.mint_master_with_supply(&mut context, None, 1)
+            .await
+            .unwrap();
+        nft.set_and_verify_collection(
+            &mut context,
+            SetAndVerifyCollectionArgs {
+                collection_metadata: collection_nft.metadata_pubkey(),
+                collection_authority: collection_authority.dirty_clone(),
+                nft_update_authority: collection_authority.pubkey(),
+                collection_mint: collection_nft.mint_pubkey(),
+                collection_master_edition_account: collection_nft.edition_pubkey().unwrap(),
+                collection_authority_record: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let print_edition = TestPrintEdition::new(&nft, 1);
+        print_edition.create(&mut context).await.unwrap();
+        print_edition
+            .set_and_verify_collection(
+                &mut context,
+                collection_nft.metadata_pubkey(),
+                &collection_authority,
+                collection_authority.pubkey(),
+                collection_nft.mint_pubkey(),
+                collection_nft.edition_pubkey().unwrap(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let fungible = TestAsset::new();
+        fungible
+            .mint_default_fungible(&mut context, &authority)
+            .await
+            .unwrap();
+
+        fungible
+            .set_and_verify_collection(
+                &mut context,
+                collection_nft.metadata_pubkey(),
+                &collection_authority,
+                collection_authority.pubkey(),
+                collection_nft.mint_pubkey(),
+                collection_nft.edition_pubkey().unwrap(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let fungible_asset = TestAsset::new();
+        fungible_asset
+            .mint_default_fungible_asset(&mut context, &authority)
+            .await
+            .unwrap();
+
+        fungible_asset
+            .set_and_verify_collection(
+                &mut context,
+                collection_nft.metadata_pubkey(),
+                &collection_authority,
+                collection_authority.pubkey(),
+                collection_nft.mint_pubkey(),
+                collection_nft.edition_pubkey().unwrap(),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // Unlock the collection and enable migration.
+        collection.start(&mut context).await.unwrap();
+
+        let TestCollection {
+            parent: collection_nft,
+            rule_set,
+            mut migratorr,
+            ..
+        } = collection;
+
+        let payer = context.payer.dirty_clone();
+
         // Attempt to migrate the Fungible
         // Error: IncorrectFreezeAuthority
         let err = migratorr
@@ -745,7 +868,7 @@ mod ineligible_scenarios {
         assert_custom_error_ix!(0, err, MigrationError::IncorrectFreezeAuthority);
 
         // Attempt to migrate the Print Edition
-        // Error: ImmutableMetadata
+        // Error: NotMasterEdition
         let err = migratorr
             .migrate_print_edition(
                 &mut context,
@@ -757,7 +880,7 @@ mod ineligible_scenarios {
             .await
             .unwrap_err();
 
-        assert_custom_error_ix!(0, err, MigrationError::ImmutableMetadata);
+        assert_custom_error_ix!(0, err, MigrationError::NotMasterEdition);
 
         // Migrate the NonFungible.
         migratorr
@@ -798,4 +921,584 @@ mod ineligible_scenarios {
 
         assert_custom_error_ix!(0, err, MigrationError::IncorrectTokenStandard);
     }
+
+    #[tokio::test]
+    async fn forged_edition_fails() {
+        // Attempt to migrate an NFT whose edition account has been replaced
+        // with a forged account. This should fail whether the forged account
+        // is owned by another program or is not a master edition.
+        let mut context = setup_pnft_context().await;
+
+        let mut collection = TestCollection::builder()
+            .items(1)
+            .build(&mut context)
+            .await
+            .unwrap();
+
+        // Unlock the collection and enable migration.
+        collection.start(&mut context).await.unwrap();
+
+        let TestCollection {
+            parent: collection_nft,
+            items,
+            mut migratorr,
+            ..
+        } = collection;
+        let [nft]: [NfTest; 1] = items.try_into().unwrap();
+
+        let payer = context.payer.dirty_clone();
+
+        let edition_account = get_account(&mut context, &nft.edition_pubkey().unwrap()).await;
+
+        // Forge the edition with the real master edition data but owned by another program.
+        // Error: IncorrectEditionProgramOwner
+        nft.inject_edition(
+            &mut context,
+            Pubkey::new_unique(),
+            edition_account.data.clone(),
+        )
+        .await;
+
+        let err = migratorr
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                payer.pubkey(),
+                &nft,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::IncorrectEditionProgramOwner);
+
+        // Forge the edition as a Token Metadata owned print edition.
+        // Error: NotMasterEdition
+        let forged_edition = Edition {
+            key: Key::EditionV1,
+            parent: Pubkey::new_unique(),
+            edition: 1,
+        };
+        let mut data = forged_edition.try_to_vec().unwrap();
+        data.resize(edition_account.data.len(), 0);
+
+        nft.inject_edition(&mut context, mpl_token_metadata::ID, data)
+            .await;
+
+        warp100(&mut context).await;
+
+        let err = migratorr
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                payer.pubkey(),
+                &nft,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::NotMasterEdition);
+    }
+
+    #[tokio::test]
+    async fn incorrect_bumps_fail() {
+        // The bumps passed in the instruction data must derive the item's accounts and
+        // the payer record. A bump of zero is searched for instead.
+        let mut context = setup_pnft_context().await;
+
+        let mut collection = TestCollection::builder()
+            .items(1)
+            .build(&mut context)
+            .await
+            .unwrap();
+
+        // Creating the owner program list and the bounty vault records their bumps.
+        collection
+            .migratorr
+            .set_owner_program_list(
+                &mut context,
+                &collection.authority,
+                &collection.authority,
+                SetOwnerProgramListArgs {
+                    list_type: ListType::Deny,
+                    programs: vec![],
+                },
+            )
+            .await
+            .unwrap();
+
+        collection
+            .migratorr
+            .fund_bounty(
+                &mut context,
+                &collection.authority,
+                FundBountyArgs {
+                    lamports: 0,
+                    reward: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        collection.start(&mut context).await.unwrap();
+
+        let TestCollection {
+            parent: collection_nft,
+            items,
+            mut migratorr,
+            ..
+        } = collection;
+        let [nft]: [NfTest; 1] = items.try_into().unwrap();
+
+        let collection_mint = collection_nft.mint_pubkey();
+
+        // Start records the bumps of the migration state, the delegate record and
+        // the collection signer.
+        migratorr.refresh_state(&mut context).await.unwrap();
+        let state = migratorr.state();
+        assert_eq!(state.bump, find_migrate_state_pda(&collection_mint).1);
+        assert_eq!(
+            state.delegate_record_bump,
+            find_delegate_record_pda(&collection_mint).1
+        );
+        assert_eq!(
+            state.collection_signer_bump,
+            find_collection_signer_pda(&collection_mint).1
+        );
+        assert_eq!(
+            state.owner_program_list_bump,
+            find_owner_program_list_pda(&collection_mint).1
+        );
+        assert_eq!(
+            state.bounty_vault_bump,
+            find_bounty_vault_pda(&collection_mint).1
+        );
+
+        let payer = context.payer.dirty_clone();
+
+        let accounts = migratorr
+            .migrate_accounts(
+                &mut context,
+                &payer,
+                nft.mint_pubkey(),
+                nft.token_pubkey(),
+                payer.pubkey(),
+                collection_mint,
+            )
+            .await;
+        let args = accounts.args();
+
+        let send = |args: MigrateItemArgs| {
+            let instruction = Instruction {
+                data: MigrationInstruction::Migrate(args).try_to_vec().unwrap(),
+                ..accounts.instruction().unwrap()
+            };
+            let transaction = Transaction::new_signed_with_payer(
+                &[instruction],
+                Some(&payer.pubkey()),
+                &[&payer],
+                context.last_blockhash,
+            );
+            let mut banks_client = context.banks_client.clone();
+            async move { banks_client.process_transaction(transaction).await }
+        };
+
+        // Error: MetadataMintMistmatch
+        let err = send(MigrateItemArgs {
+            metadata_bump: args.metadata_bump.wrapping_sub(1),
+            ..args.clone()
+        })
+        .await
+        .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::MetadataMintMistmatch);
+
+        // Error: InvalidEditionDerivation
+        let err = send(MigrateItemArgs {
+            edition_bump: args.edition_bump.wrapping_sub(1),
+            ..args.clone()
+        })
+        .await
+        .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::InvalidEditionDerivation);
+
+        // Error: InvalidPayerRecordDerivation
+        let err = send(MigrateItemArgs {
+            payer_record_bump: args.payer_record_bump.wrapping_sub(1),
+            ..args.clone()
+        })
+        .await
+        .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::InvalidPayerRecordDerivation);
+
+        // Error: InvalidPayerRecordDerivation
+        // A new payer record at a non-canonical address would count the payer twice.
+        let payer_pubkey = payer.pubkey();
+        let (payer_record, _) = find_payer_record_pda(&collection_mint, &payer_pubkey);
+        let (non_canonical_record, non_canonical_bump) = (1..args.payer_record_bump)
+            .rev()
+            .find_map(|bump| {
+                let seeds: &[&[u8]] = &[
+                    b"payer",
+                    collection_mint.as_ref(),
+                    payer_pubkey.as_ref(),
+                    &[bump],
+                ];
+                Pubkey::create_program_address(seeds, &mpl_migration_validator::ID)
+                    .ok()
+                    .map(|key| (key, bump))
+            })
+            .unwrap();
+
+        let mut instruction = Instruction {
+            data: MigrationInstruction::Migrate(MigrateItemArgs {
+                payer_record_bump: non_canonical_bump,
+                ..args.clone()
+            })
+            .try_to_vec()
+            .unwrap(),
+            ..accounts.instruction().unwrap()
+        };
+        for account in instruction.accounts.iter_mut() {
+            if account.pubkey == payer_record {
+                account.pubkey = non_canonical_record;
+            }
+        }
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&payer.pubkey()),
+            &[&payer],
+            context.last_blockhash,
+        );
+        let err = context
+            .banks_client
+            .clone()
+            .process_transaction(transaction)
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::InvalidPayerRecordDerivation);
+
+        // Unknown bumps are searched for.
+        send(MigrateItemArgs {
+            metadata_bump: 0,
+            edition_bump: 0,
+            payer_record_bump: 0,
+        })
+        .await
+        .unwrap();
+
+        // The payer record is created with its canonical bump.
+        let payer_record_bump = find_payer_record_pda(&collection_mint, &payer.pubkey()).1;
+        let account = get_account(&mut context, &payer_record).await;
+        let record = PayerRecord::safe_deserialize(&account.data).unwrap();
+        assert_eq!(record.bump, payer_record_bump);
+    }
+
+    #[tokio::test]
+    async fn denied_owner_program_fails() {
+        // Attempt to migrate an NFT held by a program excluded by the
+        // owner program list. The token owner here is a system account
+        // so we deny the System Program.
+        let mut context = setup_pnft_context().await;
+
+        let mut collection = TestCollection::builder()
+            .items(1)
+            .build(&mut context)
+            .await
+            .unwrap();
+
+        // Unlock the collection and enable migration.
+        collection.start(&mut context).await.unwrap();
+
+        let TestCollection {
+            authority: collection_authority,
+            parent: collection_nft,
+            items,
+            mut migratorr,
+            ..
+        } = collection;
+        let [nft]: [NfTest; 1] = items.try_into().unwrap();
+
+        let payer = context.payer.dirty_clone();
+
+        // Deny items held by System Program owned accounts.
+        let args = SetOwnerProgramListArgs {
+            list_type: ListType::Deny,
+            programs: vec![solana_program::system_program::ID],
+        };
+
+        migratorr
+            .set_owner_program_list(&mut context, &payer, &collection_authority, args)
+            .await
+            .unwrap();
+
+        // Error: TokenOwnerProgramNotAllowed
+        let err = migratorr
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                payer.pubkey(),
+                &nft,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::TokenOwnerProgramNotAllowed);
+
+        // An allow list only restricts programs, so the wallet-held item can migrate
+        // without listing the System Program.
+        let args = SetOwnerProgramListArgs {
+            list_type: ListType::Allow,
+            programs: vec![Pubkey::new_unique()],
+        };
+
+        migratorr
+            .set_owner_program_list(&mut context, &payer, &collection_authority, args)
+            .await
+            .unwrap();
+
+        migratorr
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                payer.pubkey(),
+                &nft,
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejected_delegate_and_frozen_tokens_fail() {
+        // With delegate handling set to Reject, items with an SPL token
+        // delegate or a frozen token account cannot be migrated.
+        let mut context = setup_pnft_context().await;
+
+        // Reject delegated and frozen items.
+        let mut collection = TestCollection::builder()
+            .items(2)
+            .delegate_handling(DelegateHandling::Reject)
+            .build(&mut context)
+            .await
+            .unwrap();
+
+        // Unlock the collection and enable migration.
+        collection.start(&mut context).await.unwrap();
+
+        let TestCollection {
+            parent: collection_nft,
+            items,
+            mut migratorr,
+            ..
+        } = collection;
+        let [delegated_nft, frozen_nft]: [NfTest; 2] = items.try_into().unwrap();
+
+        let payer = context.payer.dirty_clone();
+
+        // Assign a spl token delegate to the first NFT.
+        let delegate = Keypair::new();
+
+        delegated_nft
+            .spl_delegate(&mut context, &payer, &delegate.pubkey())
+            .await
+            .unwrap();
+
+        // Error: TokenDelegateNotAllowed
+        let err = migratorr
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                payer.pubkey(),
+                &delegated_nft,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::TokenDelegateNotAllowed);
+
+        // Simulate a frozen NFT by directly injecting the frozen state.
+        frozen_nft.inject_frozen_state(&mut context).await;
+
+        // Error: FrozenTokenNotAllowed
+        let err = migratorr
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                payer.pubkey(),
+                &frozen_nft,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::FrozenTokenNotAllowed);
+    }
+
+    #[tokio::test]
+    async fn collection_parent_step_order() {
+        // With the collection parent order set to First, items cannot be
+        // migrated before the parent and the parent cannot be migrated
+        // through the regular Migrate instruction.
+        let mut context = setup_pnft_context().await;
+
+        // Migrate the collection parent first.
+        let mut collection = TestCollection::builder()
+            .items(1)
+            .collection_parent_order(CollectionParentOrder::First)
+            .build(&mut context)
+            .await
+            .unwrap();
+
+        // Unlock the collection and enable migration.
+        collection.start(&mut context).await.unwrap();
+
+        let TestCollection {
+            parent: collection_nft,
+            items,
+            mut migratorr,
+            ..
+        } = collection;
+        let [nft]: [NfTest; 1] = items.try_into().unwrap();
+
+        let payer = context.payer.dirty_clone();
+
+        // Error: CollectionParentNotMigrated
+        let err = migratorr
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                payer.pubkey(),
+                &nft,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::CollectionParentNotMigrated);
+
+        // Error: UnexpectedCollectionParent
+        let err = migratorr
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                payer.pubkey(),
+                &collection_nft,
+            )
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::UnexpectedCollectionParent);
+
+        migratorr
+            .migrate_collection_parent(&mut context, &payer, payer.pubkey(), &collection_nft)
+            .await
+            .unwrap();
+
+        migratorr
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                payer.pubkey(),
+                &nft,
+            )
+            .await
+            .unwrap();
+
+        migratorr.refresh_state(&mut context).await.unwrap();
+        assert_eq!(migratorr.state().status.items_migrated, 1);
+        assert!(migratorr.state().status.collection_parent_migrated);
+    }
+
+    #[tokio::test]
+    async fn released_collection_parent_migrates_early() {
+        // With the collection parent order set to Last, an item that can never
+        // be migrated would hold the parent back until the authority releases it.
+        let mut context = setup_pnft_context().await;
+
+        let mut collection = TestCollection::builder()
+            .items(2)
+            .build(&mut context)
+            .await
+            .unwrap();
+
+        // Unlock the collection and enable migration.
+        collection.start(&mut context).await.unwrap();
+
+        let TestCollection {
+            authority,
+            parent: collection_nft,
+            items,
+            mut migratorr,
+            ..
+        } = collection;
+        let [nft, straggler]: [NfTest; 2] = items.try_into().unwrap();
+
+        let payer = context.payer.dirty_clone();
+
+        migratorr
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                payer.pubkey(),
+                &nft,
+            )
+            .await
+            .unwrap();
+
+        // Error: CollectionItemsNotMigrated
+        let err = migratorr
+            .migrate_collection_parent(&mut context, &payer, payer.pubkey(), &collection_nft)
+            .await
+            .unwrap_err();
+
+        assert_custom_error_ix!(0, err, MigrationError::CollectionItemsNotMigrated);
+
+        // The parent can be released while the migration is in progress.
+        let update_args = UpdateArgs {
+            rule_set: None,
+            collection_size: None,
+            new_update_authority: None,
+            delegate_handling: None,
+            collection_parent_order: None,
+            update_authority_mode: None,
+            release_collection_parent: Some(true),
+        };
+
+        migratorr
+            .update(&mut context, &authority, update_args)
+            .await
+            .unwrap();
+
+        migratorr
+            .migrate_collection_parent(&mut context, &payer, payer.pubkey(), &collection_nft)
+            .await
+            .unwrap();
+
+        // Items left behind can still follow the released parent.
+        migratorr
+            .migrate_item(
+                &mut context,
+                &payer,
+                collection_nft.mint_pubkey(),
+                payer.pubkey(),
+                &straggler,
+            )
+            .await
+            .unwrap();
+
+        migratorr.refresh_state(&mut context).await.unwrap();
+        assert_eq!(migratorr.state().status.items_migrated, 2);
+        assert!(migratorr.state().status.collection_parent_migrated);
+        assert!(migratorr.state().collection_parent_released);
+    }
 }
@@ -0,0 +1,94 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_migration_validator::{
+    errors::MigrationError, instruction::SetOwnerProgramListArgs, state::ListType,
+};
+use num_traits::FromPrimitive;
+use solana_program::{pubkey::Pubkey, system_program};
+use solana_program_test::{tokio, BanksClientError};
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, transaction::TransactionError,
+};
+use utils::*;
+
+#[tokio::test]
+async fn set_and_update_owner_program_list() {
+    let mut context = setup_pnft_context().await;
+
+    let TestCollection {
+        authority,
+        migratorr,
+        ..
+    } = TestCollection::builder().build(&mut context).await.unwrap();
+
+    let payer = context.payer.dirty_clone();
+
+    // Create a deny list with a single escrow program.
+    let escrow_program = Pubkey::new_unique();
+    let args = SetOwnerProgramListArgs {
+        list_type: ListType::Deny,
+        programs: vec![escrow_program],
+    };
+
+    migratorr
+        .set_owner_program_list(&mut context, &payer, &authority, args)
+        .await
+        .unwrap();
+
+    let owner_program_list = migratorr.owner_program_list(&mut context).await;
+
+    assert_eq!(owner_program_list.list_type, ListType::Deny);
+    assert_eq!(owner_program_list.programs, vec![escrow_program]);
+    assert!(!owner_program_list.allows(&escrow_program));
+    assert!(owner_program_list.allows(&system_program::ID));
+
+    // Replace it with a larger allow list, which resizes the account.
+    let staking_program = Pubkey::new_unique();
+    let args = SetOwnerProgramListArgs {
+        list_type: ListType::Allow,
+        programs: vec![escrow_program, staking_program],
+    };
+
+    migratorr
+        .set_owner_program_list(&mut context, &payer, &authority, args)
+        .await
+        .unwrap();
+
+    let owner_program_list = migratorr.owner_program_list(&mut context).await;
+
+    assert_eq!(owner_program_list.list_type, ListType::Allow);
+    assert_eq!(
+        owner_program_list.programs,
+        vec![escrow_program, staking_program]
+    );
+    assert!(owner_program_list.allows(&staking_program));
+    assert!(!owner_program_list.allows(&Pubkey::new_unique()));
+
+    // Items held by wallets are allowed without listing the System Program.
+    assert!(owner_program_list.allows(&system_program::ID));
+}
+
+#[tokio::test]
+async fn authority_must_match() {
+    let mut context = setup_pnft_context().await;
+
+    let TestCollection { migratorr, .. } =
+        TestCollection::builder().build(&mut context).await.unwrap();
+
+    let payer = context.payer.dirty_clone();
+
+    let incorrect_authority = Keypair::new();
+
+    let args = SetOwnerProgramListArgs {
+        list_type: ListType::Deny,
+        programs: vec![Pubkey::new_unique()],
+    };
+
+    let err = migratorr
+        .set_owner_program_list(&mut context, &payer, &incorrect_authority, args)
+        .await
+        .unwrap_err();
+
+    assert_custom_error_ix!(0, err, MigrationError::InvalidAuthority);
+}
@@ -2,9 +2,7 @@
 pub mod utils;
 
 use mpl_migration_validator::{
-    errors::MigrationError,
-    instruction::{InitializeArgs, UpdateArgs},
-    state::UnlockMethod,
+    errors::MigrationError, instruction::UpdateArgs, utils::find_collection_signer_pda,
     PROGRAM_SIGNER,
 };
 use mpl_token_metadata::{
@@ -12,9 +10,7 @@ use mpl_token_metadata::{
     state::{CollectionAuthorityRecord, TokenMetadataAccount},
 };
 use num_traits::FromPrimitive;
-use solana_program::{
-    native_token::LAMPORTS_PER_SOL, program_pack::Pack, pubkey::Pubkey, system_instruction,
-};
+use solana_program::{native_token::LAMPORTS_PER_SOL, program_pack::Pack, system_instruction};
 use solana_program_test::{tokio, BanksClientError, ProgramTest};
 use solana_sdk::{
     account::Account,
@@ -27,86 +23,36 @@ use utils::*;
 
 #[tokio::test]
 async fn start_migration() {
-    let mut context = setup_context().await;
-
-    // Create a default NFT to use as a collection.
-    let mut nft = NfTest::new();
-    nft.mint_default(&mut context, None).await.unwrap();
-
-    // Create our migration state manager.
-    let mut migratorr = Migratorr::new(nft.mint_pubkey());
-
-    // Set up our initialize args
-    let unlock_method = UnlockMethod::Timed;
-
-    let args = InitializeArgs {
-        rule_set: None, // this defaults to the default public key
-        unlock_method: UnlockMethod::Timed,
-        collection_size: 0,
-    };
+    let mut context = setup_pnft_context().await;
 
+    let mut collection = TestCollection::builder().build(&mut context).await.unwrap();
     let payer = context.payer.dirty_clone();
 
-    // Initialize the migration state account on-chain
-    migratorr
-        .initialize(&mut context, &payer, &payer, &nft, args)
-        .await
-        .unwrap();
-
-    // Refresh the migratorr's state from the on-chain account.
-    migratorr.refresh_state(&mut context).await.unwrap();
-
     // Check values are as expected.
-    assert_eq!(migratorr.authority(), payer.pubkey());
-    assert_eq!(migratorr.rule_set(), Pubkey::default());
-    assert_eq!(migratorr.unlock_method(), unlock_method);
+    let migratorr = &collection.migratorr;
+    assert_eq!(migratorr.authority(), collection.authority.pubkey());
+    assert_eq!(migratorr.rule_set(), collection.rule_set);
     assert_eq!(migratorr.collection_size(), 0);
-    assert_eq!(migratorr.mint(), nft.mint_pubkey());
+    assert_eq!(migratorr.mint(), collection.parent.mint_pubkey());
 
     // First we try to start the migration expecting it to fail because
-    // the current time will not be greater than or equal to the unlock
-    // time.
+    // the migration state has not been unlocked yet.
     let err = migratorr
-        .start(&mut context, &payer, &payer, &nft)
+        .start(
+            &mut context,
+            &payer,
+            &collection.authority,
+            &collection.parent,
+        )
         .await
         .unwrap_err();
 
     assert_custom_error_ix!(0, err, MigrationError::MigrationLocked);
 
-    // We need to inject the account with the state set to a timestamp
-    // that allows our migration to start.
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-
-    let mut state = migratorr.state().clone();
-    state.status.unlock_time = now as i64 - 2;
+    // Unlock the collection and start the migration expecting it to succeed.
+    collection.start(&mut context).await.unwrap();
 
-    // Set the state on the account.
-    migratorr.inject_state(&mut context, state).await;
-
-    // Warp ahead to ensure account is updated.
-    context.warp_to_slot(100).unwrap();
-
-    // Update the state account on-chain. This checks the current time
-    // and updates the is_unlocked field if the wait time has passed.s
-    let update_args = UpdateArgs {
-        rule_set: None,
-        collection_size: None,
-        new_update_authority: None,
-    };
-
-    migratorr
-        .update(&mut context, &payer, update_args)
-        .await
-        .unwrap();
-
-    // Now we try to start the migration expecting it to succeed.
-    migratorr
-        .start(&mut context, &payer, &payer, &nft)
-        .await
-        .unwrap();
+    let TestCollection { mut migratorr, .. } = collection;
 
     // Refresh the migratorr's state from the on-chain account.
     migratorr.refresh_state(&mut context).await.unwrap();
@@ -115,9 +61,10 @@ async fn start_migration() {
     assert!(migratorr.state().status.in_progress);
     assert!(!migratorr.state().status.is_locked);
 
-    // Ensure the collection delegate was created.
+    // Ensure the collection delegate was created for the collection's program signer.
+    let (program_signer, _) = find_collection_signer_pda(&migratorr.mint());
     let (delegate_record_pda, bump) =
-        find_collection_authority_account(&migratorr.mint(), &PROGRAM_SIGNER);
+        find_collection_authority_account(&migratorr.mint(), &program_signer);
 
     // This function call panics if the account doesn't exist.
     let delegate_record_account = get_account(&mut context, &delegate_record_pda).await;
@@ -138,31 +85,24 @@ async fn start_migration() {
 
 #[tokio::test]
 async fn wrong_authority_fails() {
-    let mut context = setup_context().await;
+    let mut context = setup_pnft_context().await;
 
     let fake_authority = Keypair::new();
 
-    // Create a default NFT to use as a collection.
-    let mut nft = NfTest::new();
-    nft.mint_default(&mut context, None).await.unwrap();
-
-    // Create our migration state manager.
-    let migratorr = Migratorr::new(nft.mint_pubkey());
+    let TestCollection {
+        authority,
+        parent,
+        mut migratorr,
+        ..
+    } = TestCollection::builder().build(&mut context).await.unwrap();
 
-    // Set up our initialize args
-    let unlock_method = UnlockMethod::Timed;
-
-    let args = InitializeArgs {
-        rule_set: None, // this defaults to the default public key
-        unlock_method,
-        collection_size: 0,
-    };
+    migratorr.unlock_collection(&mut context, &authority).await;
 
     let payer = context.payer.dirty_clone();
 
-    // Initialize the migration state account on-chain
+    // Only the authority stored in the migration state can start the migration.
     let err = migratorr
-        .initialize(&mut context, &payer, &fake_authority, &nft, args)
+        .start(&mut context, &payer, &fake_authority, &parent)
         .await
         .unwrap_err();
 
@@ -171,7 +111,7 @@ async fn wrong_authority_fails() {
 
 #[tokio::test]
 async fn incorrect_migration_state_fails() {
-    let mut context = setup_context().await;
+    let mut context = setup_pnft_context().await;
 
     let other_authority = Keypair::new();
     other_authority
@@ -179,63 +119,72 @@ async fn incorrect_migration_state_fails() {
         .await
         .unwrap();
 
-    // Create a default NFT to use as a collection.
-    let mut nft = NfTest::new();
-    nft.mint_default(&mut context, None).await.unwrap();
-
-    // NFT for someone else's migration state account.
-    let mut other_nft = NfTest::new();
-    other_nft
-        .mint_default(&mut context, Some(other_authority.dirty_clone()))
+    let TestCollection {
+        parent, migratorr, ..
+    } = TestCollection::builder().build(&mut context).await.unwrap();
+
+    // Someone else's migration state account.
+    let TestCollection {
+        migratorr: other_migratorr,
+        ..
+    } = TestCollection::builder()
+        .authority(other_authority)
+        .build(&mut context)
         .await
         .unwrap();
 
-    // Create our migration state managers.
-    let migratorr = Migratorr::new(nft.mint_pubkey());
-    let other_migratorr = Migratorr::new(other_nft.mint_pubkey());
-
-    // Set up our initialize args
-    let unlock_method = UnlockMethod::Timed;
-
-    let args = InitializeArgs {
-        rule_set: None, // this defaults to the default public key
-        unlock_method,
-        collection_size: 0,
-    };
+    let (delegate, _) = find_collection_signer_pda(&parent.mint_pubkey());
+    let (delegate_record, _) = find_collection_authority_account(&parent.mint_pubkey(), &delegate);
 
     let payer = context.payer.dirty_clone();
 
-    // Initialize both states.
-    migratorr
-        .initialize(&mut context, &payer, &payer, &nft, args.clone())
+    let err = migratorr
+        .start_full(
+            &mut context,
+            &payer,
+            &payer,
+            parent.mint_pubkey(),
+            parent.metadata_pubkey(),
+            delegate,
+            delegate_record,
+            other_migratorr.pubkey(),
+        )
         .await
-        .unwrap();
+        .unwrap_err();
 
-    other_migratorr
-        .initialize(&mut context, &payer, &other_authority, &other_nft, args)
-        .await
-        .unwrap();
+    assert_custom_error_ix!(0, err, MigrationError::InvalidMigrationStateDerivation);
+}
 
-    let delegate = PROGRAM_SIGNER;
-    let (delegate_record, _) = find_collection_authority_account(&nft.mint_pubkey(), &delegate);
+#[tokio::test]
+async fn global_program_signer_delegate_fails() {
+    let mut context = setup_pnft_context().await;
+
+    let TestCollection {
+        parent, migratorr, ..
+    } = TestCollection::builder().build(&mut context).await.unwrap();
 
     let payer = context.payer.dirty_clone();
 
+    // New migrations must delegate to the collection's own program signer
+    // rather than the signer shared by every collection.
+    let (delegate_record, _) =
+        find_collection_authority_account(&parent.mint_pubkey(), &PROGRAM_SIGNER);
+
     let err = migratorr
         .start_full(
             &mut context,
             &payer,
             &payer,
-            nft.mint_pubkey(),
-            nft.metadata_pubkey(),
-            delegate,
+            parent.mint_pubkey(),
+            parent.metadata_pubkey(),
+            PROGRAM_SIGNER,
             delegate_record,
-            other_migratorr.pubkey(),
+            migratorr.pubkey(),
         )
         .await
         .unwrap_err();
 
-    assert_custom_error_ix!(0, err, MigrationError::InvalidMigrationStateDerivation);
+    assert_custom_error_ix!(0, err, MigrationError::InvalidSignerDerivation);
 }
 
 #[tokio::test]
@@ -361,127 +310,30 @@ async fn restart_migration() {
     // been migrated. This lets us update the collection delegate for a new update authority.
     // If an item has been migrated, we don't allow this because all items must have the
     // same collection authority as the parent and that must match the migration state.
-    let mut context = setup_context().await;
-
-    let collection_authority = context.payer.dirty_clone();
-    let collection_authority_pubkey = collection_authority.pubkey();
-
-    // Create a default NFT to use as a collection.
-    let mut collection_nft = NfTest::new();
-    collection_nft
-        .mint_default(&mut context, None)
-        .await
-        .unwrap();
+    let mut context = setup_pnft_context().await;
 
     // Populate the collection with two NFTs.
-    let mut nft1 = NfTest::new();
-    nft1.mint_default(&mut context, None).await.unwrap();
-    nft1.set_and_verify_collection(
-        &mut context,
-        SetAndVerifyCollectionArgs {
-            collection_metadata: collection_nft.metadata_pubkey(),
-            collection_authority: collection_authority.dirty_clone(),
-            nft_update_authority: collection_authority.pubkey(),
-            collection_mint: collection_nft.mint_pubkey(),
-            collection_master_edition_account: collection_nft.edition_pubkey().unwrap(),
-            collection_authority_record: None,
-        },
-    )
-    .await
-    .unwrap();
-
-    let mut nft2 = NfTest::new();
-    nft2.mint_default(&mut context, None).await.unwrap();
-    nft2.set_and_verify_collection(
-        &mut context,
-        SetAndVerifyCollectionArgs {
-            collection_metadata: collection_nft.metadata_pubkey(),
-            collection_authority: collection_authority.dirty_clone(),
-            nft_update_authority: collection_authority_pubkey,
-            collection_mint: collection_nft.mint_pubkey(),
-            collection_master_edition_account: collection_nft.edition_pubkey().unwrap(),
-            collection_authority_record: None,
-        },
-    )
-    .await
-    .unwrap();
-
-    // Create our migration state manager.
-    let mut migratorr = Migratorr::new(collection_nft.mint_pubkey());
-
-    // Set up our initialize args
-    let unlock_method = UnlockMethod::Timed;
-
-    let args = InitializeArgs {
-        rule_set: None, // this defaults to the default public key
-        unlock_method: UnlockMethod::Timed,
-        collection_size: 0,
-    };
-
-    let payer = context.payer.dirty_clone();
-
-    // Initialize the migration state account on-chain
-    migratorr
-        .initialize(&mut context, &payer, &payer, &collection_nft, args)
+    let mut collection = TestCollection::builder()
+        .items(2)
+        .build(&mut context)
         .await
         .unwrap();
 
-    // Refresh the migratorr's state from the on-chain account.
-    migratorr.refresh_state(&mut context).await.unwrap();
-
-    // Check values are as expected.
-    assert_eq!(migratorr.authority(), payer.pubkey());
-    assert_eq!(migratorr.rule_set(), Pubkey::default());
-    assert_eq!(migratorr.unlock_method(), unlock_method);
-    assert_eq!(migratorr.collection_size(), 0);
-    assert_eq!(migratorr.mint(), collection_nft.mint_pubkey());
+    // Now we start the migration expecting it to succeed.
+    collection.start(&mut context).await.unwrap();
 
-    // First we try to start the migration expecting it to fail because
-    // the current time will not be greater than or equal to the unlock
-    // time.
-    let err = migratorr
-        .start(&mut context, &payer, &payer, &collection_nft)
-        .await
-        .unwrap_err();
-
-    assert_custom_error_ix!(0, err, MigrationError::MigrationLocked);
-
-    // We need to inject the account with the state set to a timestamp
-    // that allows our migration to start.
-    let now = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
-
-    let mut state = migratorr.state().clone();
-    state.status.unlock_time = now as i64 - 2;
-
-    // Set the state on the account.
-    migratorr.inject_state(&mut context, state).await;
-
-    // Warp ahead to ensure account is updated.
-    context.warp_to_slot(100).unwrap();
-
-    // Update the state account on-chain. This checks the current time
-    // and updates the is_unlocked field if the wait time has passed.s
-    let update_args = UpdateArgs {
-        rule_set: None,
-        collection_size: None,
-        new_update_authority: None,
-    };
-
-    migratorr
-        .update(&mut context, &payer, update_args)
-        .await
-        .unwrap();
+    warp100(&mut context).await;
 
-    // Now we try to start the migration expecting it to succeed.
-    migratorr
-        .start(&mut context, &payer, &payer, &collection_nft)
-        .await
-        .unwrap();
+    let TestCollection {
+        authority: collection_authority,
+        parent: collection_nft,
+        items,
+        mut migratorr,
+        ..
+    } = collection;
+    let [nft1, nft2]: [NfTest; 2] = items.try_into().unwrap();
 
-    warp100(&mut context).await;
+    let payer = context.payer.dirty_clone();
 
     // Refresh the migratorr's state from the on-chain account.
     migratorr.refresh_state(&mut context).await.unwrap();
@@ -490,9 +342,10 @@ async fn restart_migration() {
     assert!(migratorr.state().status.in_progress);
     assert!(!migratorr.state().status.is_locked);
 
-    // Ensure the collection delegate was created.
+    // Ensure the collection delegate was created for the collection's program signer.
+    let (program_signer, _) = find_collection_signer_pda(&migratorr.mint());
     let (delegate_record_pda, bump) =
-        find_collection_authority_account(&migratorr.mint(), &PROGRAM_SIGNER);
+        find_collection_authority_account(&migratorr.mint(), &program_signer);
 
     // This function call panics if the account doesn't exist.
     let delegate_record_account = get_account(&mut context, &delegate_record_pda).await;
@@ -559,25 +412,30 @@ async fn restart_migration() {
     migratorr
         .update(
             &mut context,
-            &payer,
+            &collection_authority,
             UpdateArgs {
                 rule_set: None,
                 collection_size: None,
                 new_update_authority: Some(new_update_authority.pubkey()),
+                delegate_handling: None,
+                collection_parent_order: None,
+                update_authority_mode: None,
+                release_collection_parent: None,
             },
         )
         .await
         .unwrap();
 
+    migratorr
+        .accept_authority(&mut context, &payer, &new_update_authority)
+        .await
+        .unwrap();
+
     migratorr.refresh_state(&mut context).await.unwrap();
     assert_eq!(migratorr.authority(), new_update_authority.pubkey());
 
     // Migration will fail because the update authority is now not the same
     // as the one stored in the delegate.
-
-    // Initialize the program signer
-    migratorr.init_signer(&mut context, &payer).await.unwrap();
-
     let token_owner = context.payer.pubkey();
 
     let err = migratorr
@@ -625,3 +483,49 @@ async fn restart_migration() {
         new_update_authority.pubkey(),
     );
 }
+
+#[tokio::test]
+async fn start_sized_collection() {
+    let mut context = setup_pnft_context().await;
+
+    // Create a sized collection with a manual size that the sized collection will override.
+    // The size is set to zero on creation.
+    let mut collection = TestCollection::builder()
+        .sized()
+        .collection_size(10)
+        .build(&mut context)
+        .await
+        .unwrap();
+
+    collection.start(&mut context).await.unwrap();
+
+    let TestCollection {
+        authority,
+        mut migratorr,
+        ..
+    } = collection;
+
+    migratorr.refresh_state(&mut context).await.unwrap();
+
+    // The size is read from the collection details.
+    assert_eq!(migratorr.collection_size(), 0);
+    assert!(migratorr.state().collection_info.is_sized);
+
+    // A manual size that conflicts with the sized collection is refused.
+    let update_args = UpdateArgs {
+        rule_set: None,
+        collection_size: Some(10),
+        new_update_authority: None,
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
+    };
+
+    let err = migratorr
+        .update(&mut context, &authority, update_args)
+        .await
+        .unwrap_err();
+
+    assert_custom_error_ix!(0, err, MigrationError::CollectionSizeMismatch);
+}
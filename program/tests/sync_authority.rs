@@ -0,0 +1,128 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_migration_validator::errors::MigrationError;
+use mpl_token_metadata::state::TokenState;
+use num_traits::FromPrimitive;
+use solana_program::native_token::LAMPORTS_PER_SOL;
+use solana_program_test::{tokio, BanksClientError};
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use utils::*;
+
+#[tokio::test]
+async fn sync_rotated_update_authority() {
+    let mut context = setup_pnft_context().await;
+
+    // We create a collection with two NFTs in it.
+    let mut collection = TestCollection::builder()
+        .items(2)
+        .build(&mut context)
+        .await
+        .unwrap();
+
+    collection.start(&mut context).await.unwrap();
+
+    let TestCollection {
+        authority: collection_authority,
+        parent: collection_nft,
+        items,
+        rule_set,
+        mut migratorr,
+        ..
+    } = collection;
+    let [nft1, mut nft2]: [NfTest; 2] = items.try_into().unwrap();
+
+    let payer = context.payer.dirty_clone();
+
+    let token_owner = context.payer.pubkey();
+
+    migratorr
+        .migrate_item(
+            &mut context,
+            &payer,
+            collection_nft.mint_pubkey(),
+            token_owner,
+            &nft1,
+        )
+        .await
+        .unwrap();
+
+    // The update authority of the collection and its remaining item is rotated
+    // in the middle of the migration.
+    let new_update_authority = Keypair::new();
+    new_update_authority
+        .airdrop(&mut context, LAMPORTS_PER_SOL)
+        .await
+        .unwrap();
+
+    for nft in [&collection_nft, &nft2] {
+        nft.set_new_update_authority(
+            &mut context,
+            SetNewUpdateAuthorityArgs {
+                update_authority: collection_authority.dirty_clone(),
+                new_update_authority: new_update_authority.pubkey(),
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    // Anyone can sync the stored authority with the collection metadata.
+    let cranker = Keypair::new();
+    cranker
+        .airdrop(&mut context, LAMPORTS_PER_SOL)
+        .await
+        .unwrap();
+
+    migratorr
+        .sync_authority(&mut context, &cranker, None)
+        .await
+        .unwrap();
+
+    migratorr.refresh_state(&mut context).await.unwrap();
+    assert_eq!(migratorr.authority(), new_update_authority.pubkey());
+
+    // The delegate record was approved by the old update authority.
+    let err = migratorr
+        .migrate_item(
+            &mut context,
+            &cranker,
+            collection_nft.mint_pubkey(),
+            token_owner,
+            &nft2,
+        )
+        .await
+        .unwrap_err();
+
+    assert_custom_error_ix!(0, err, MigrationError::InvalidDelegate);
+
+    // The new update authority signs to re-create the delegate record.
+    migratorr
+        .sync_authority(&mut context, &cranker, Some(&new_update_authority))
+        .await
+        .unwrap();
+
+    migratorr
+        .migrate_item(
+            &mut context,
+            &payer,
+            collection_nft.mint_pubkey(),
+            token_owner,
+            &nft2,
+        )
+        .await
+        .unwrap();
+
+    nft2.assert_pnft_migration(
+        &mut context,
+        Some(rule_set),
+        None,
+        None,
+        TokenState::Unlocked,
+    )
+    .await
+    .unwrap();
+}
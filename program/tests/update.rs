@@ -4,22 +4,24 @@ pub mod utils;
 use mpl_migration_validator::{
     self,
     errors::MigrationError,
-    instruction::{InitializeArgs, UpdateArgs},
-    state::UnlockMethod,
+    instruction::{update, UpdateArgs},
+    state::DelegateHandling,
 };
 use num_traits::FromPrimitive;
 use solana_program::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
 use solana_program_test::{tokio, BanksClientError};
 use solana_sdk::{
-    instruction::InstructionError, signature::Keypair, signer::Signer,
-    transaction::TransactionError,
+    instruction::InstructionError,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
 };
 
 use crate::utils::*;
 
 #[tokio::test]
 async fn update_rule_set() {
-    let mut context = setup_context().await;
+    let mut context = setup_pnft_context().await;
 
     // Create an authority that is separate from the payer.
     let authority = Keypair::new();
@@ -28,29 +30,13 @@ async fn update_rule_set() {
         .await
         .unwrap();
 
-    // Create a default NFT to use as a collection.
-    let mut nft = NfTest::new();
-    nft.mint_default(&mut context, Some(authority.dirty_clone()))
-        .await
-        .unwrap();
-
-    // Create our migration state manager.
-    let mut migratorr = Migratorr::new(nft.mint_pubkey());
-
-    // Set up our initialize args
-    let unlock_method = UnlockMethod::Timed;
-
-    let args = InitializeArgs {
-        rule_set: None, // this defaults to the default public key
-        unlock_method,
-        collection_size: 0,
-    };
-
-    let payer = context.payer.dirty_clone();
-
-    // Initialize the migration state account on-chain
-    migratorr
-        .initialize(&mut context, &payer, &authority, &nft, args)
+    let TestCollection {
+        parent: nft,
+        mut migratorr,
+        ..
+    } = TestCollection::builder()
+        .authority(authority.dirty_clone())
+        .build(&mut context)
         .await
         .unwrap();
 
@@ -65,6 +51,10 @@ async fn update_rule_set() {
         rule_set: Some(dummy_rule_set),
         collection_size: None,
         new_update_authority: None,
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
     };
 
     migratorr
@@ -79,7 +69,7 @@ async fn update_rule_set() {
 
 #[tokio::test]
 async fn update_collection_size() {
-    let mut context = setup_context().await;
+    let mut context = setup_pnft_context().await;
 
     // Create an authority that is separate from the payer.
     let authority = Keypair::new();
@@ -88,43 +78,87 @@ async fn update_collection_size() {
         .await
         .unwrap();
 
-    // Create a default NFT to use as a collection.
-    let mut nft = NfTest::new();
-    nft.mint_default(&mut context, Some(authority.dirty_clone()))
+    let TestCollection {
+        parent: nft,
+        mut migratorr,
+        ..
+    } = TestCollection::builder()
+        .authority(authority.dirty_clone())
+        .build(&mut context)
         .await
         .unwrap();
 
-    // Create our migration state manager.
-    let mut migratorr = Migratorr::new(nft.mint_pubkey());
+    // Refresh the migratorr's state from the on-chain account.
+    migratorr.refresh_state(&mut context).await.unwrap();
 
-    // Set up our initialize args
-    let unlock_method = UnlockMethod::Timed;
+    assert_eq!(migratorr.mint(), nft.mint_pubkey());
+    assert_eq!(migratorr.authority(), authority.pubkey());
 
-    let args = InitializeArgs {
-        rule_set: None, // this defaults to the default public key
-        unlock_method,
-        collection_size: 0,
+    let new_collection_size = 888;
+    let update_args = UpdateArgs {
+        rule_set: None,
+        collection_size: Some(new_collection_size),
+        new_update_authority: None,
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
     };
 
-    let payer = context.payer.dirty_clone();
-
-    // Initialize the migration state account on-chain
     migratorr
-        .initialize(&mut context, &payer, &authority, &nft, args)
+        .update(&mut context, &authority, update_args)
         .await
         .unwrap();
 
-    // Refresh the migratorr's state from the on-chain account.
     migratorr.refresh_state(&mut context).await.unwrap();
 
-    assert_eq!(migratorr.mint(), nft.mint_pubkey());
-    assert_eq!(migratorr.authority(), authority.pubkey());
+    assert_eq!(migratorr.collection_size(), new_collection_size);
+}
 
-    let new_collection_size = 888;
+#[tokio::test]
+async fn update_sized_collection_size() {
+    let mut context = setup_pnft_context().await;
+
+    // The sized collection is created with a size of zero, and the migration state
+    // has a conflicting manual size.
+    let TestCollection {
+        authority,
+        mut migratorr,
+        ..
+    } = TestCollection::builder()
+        .sized()
+        .collection_size(10)
+        .build(&mut context)
+        .await
+        .unwrap();
+
+    // The collection details are checked even before Start records the collection as sized.
     let update_args = UpdateArgs {
         rule_set: None,
-        collection_size: Some(new_collection_size),
+        collection_size: Some(10),
+        new_update_authority: None,
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
+    };
+
+    let err = migratorr
+        .update(&mut context, &authority, update_args)
+        .await
+        .unwrap_err();
+
+    assert_custom_error_ix!(0, err, MigrationError::CollectionSizeMismatch);
+
+    // The size from the collection details is accepted.
+    let update_args = UpdateArgs {
+        rule_set: None,
+        collection_size: Some(0),
         new_update_authority: None,
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
     };
 
     migratorr
@@ -134,12 +168,59 @@ async fn update_collection_size() {
 
     migratorr.refresh_state(&mut context).await.unwrap();
 
-    assert_eq!(migratorr.collection_size(), new_collection_size);
+    assert_eq!(migratorr.collection_size(), 0);
 }
 
 #[tokio::test]
-async fn update_authority() {
-    let mut context = setup_context().await;
+async fn update_with_other_collection_metadata_fails() {
+    let mut context = setup_pnft_context().await;
+
+    let TestCollection {
+        authority, parent, ..
+    } = TestCollection::builder().build(&mut context).await.unwrap();
+
+    let TestCollection {
+        parent: other_parent,
+        ..
+    } = TestCollection::builder()
+        .sized()
+        .build(&mut context)
+        .await
+        .unwrap();
+
+    let update_args = UpdateArgs {
+        rule_set: None,
+        collection_size: Some(10),
+        new_update_authority: None,
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
+    };
+
+    // Pass the metadata of another collection.
+    let mut instruction = update(authority.pubkey(), parent.mint_pubkey(), update_args);
+    instruction.accounts[2].pubkey = other_parent.metadata_pubkey();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&authority.pubkey()),
+        &[&authority],
+        context.last_blockhash,
+    );
+
+    let err = context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap_err();
+
+    assert_custom_error_ix!(0, err, MigrationError::MetadataMintMistmatch);
+}
+
+#[tokio::test]
+async fn update_delegate_handling() {
+    let mut context = setup_pnft_context().await;
 
     // Create an authority that is separate from the payer.
     let authority = Keypair::new();
@@ -148,44 +229,83 @@ async fn update_authority() {
         .await
         .unwrap();
 
-    // Create a default NFT to use as a collection.
-    let mut nft = NfTest::new();
-    nft.mint_default(&mut context, Some(authority.dirty_clone()))
+    let TestCollection { mut migratorr, .. } = TestCollection::builder()
+        .authority(authority.dirty_clone())
+        .build(&mut context)
         .await
         .unwrap();
 
-    // Create our migration state manager.
-    let mut migratorr = Migratorr::new(nft.mint_pubkey());
+    // Refresh the migratorr's state from the on-chain account.
+    migratorr.refresh_state(&mut context).await.unwrap();
 
-    // Set up our initialize args
-    let unlock_method = UnlockMethod::Timed;
+    // Delegates are carried over by default.
+    assert_eq!(
+        migratorr.state().delegate_handling,
+        DelegateHandling::CarryOver
+    );
 
-    let args = InitializeArgs {
-        rule_set: None, // this defaults to the default public key
-        unlock_method,
-        collection_size: 0,
+    let update_args = UpdateArgs {
+        rule_set: None,
+        collection_size: None,
+        new_update_authority: None,
+        delegate_handling: Some(DelegateHandling::Reject),
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
     };
 
-    let payer = context.payer.dirty_clone();
-
-    // Initialize the migration state account on-chain
     migratorr
-        .initialize(&mut context, &payer, &authority, &nft, args)
+        .update(&mut context, &authority, update_args)
+        .await
+        .unwrap();
+
+    migratorr.refresh_state(&mut context).await.unwrap();
+
+    assert_eq!(
+        migratorr.state().delegate_handling,
+        DelegateHandling::Reject
+    );
+}
+
+#[tokio::test]
+async fn update_authority() {
+    let mut context = setup_pnft_context().await;
+
+    // Create an authority that is separate from the payer.
+    let authority = Keypair::new();
+    authority
+        .airdrop(&mut context, 1_000_000_000)
+        .await
+        .unwrap();
+
+    let TestCollection {
+        parent: nft,
+        mut migratorr,
+        ..
+    } = TestCollection::builder()
+        .authority(authority.dirty_clone())
+        .build(&mut context)
         .await
         .unwrap();
 
+    let payer = context.payer.dirty_clone();
+
     // Refresh the migratorr's state from the on-chain account.
     migratorr.refresh_state(&mut context).await.unwrap();
 
     assert_eq!(migratorr.mint(), nft.mint_pubkey());
     assert_eq!(migratorr.authority(), authority.pubkey());
 
-    let new_authority = Pubkey::new_unique();
+    let new_authority = Keypair::new();
 
     let update_args = UpdateArgs {
         rule_set: None,
         collection_size: None,
-        new_update_authority: Some(new_authority),
+        new_update_authority: Some(new_authority.pubkey()),
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
     };
 
     migratorr
@@ -195,95 +315,134 @@ async fn update_authority() {
 
     migratorr.refresh_state(&mut context).await.unwrap();
 
-    assert_eq!(migratorr.authority(), new_authority);
+    // The authority is only proposed until the new authority accepts it.
+    assert_eq!(migratorr.authority(), authority.pubkey());
+    assert_eq!(migratorr.state().pending_authority, new_authority.pubkey());
+    assert!(migratorr.state().pending_authority_expiry > 0);
+
+    migratorr
+        .accept_authority(&mut context, &payer, &new_authority)
+        .await
+        .unwrap();
+
+    migratorr.refresh_state(&mut context).await.unwrap();
+
+    assert_eq!(migratorr.authority(), new_authority.pubkey());
+    assert_eq!(migratorr.state().pending_authority, Pubkey::default());
 }
 
 #[tokio::test]
-async fn update_after_items_migrated() {
-    let mut context = setup_context().await;
+async fn accept_authority_fails() {
+    let mut context = setup_pnft_context().await;
 
     // Create an authority that is separate from the payer.
     let authority = Keypair::new();
-    let authority_pubkey = authority.pubkey();
-
     authority
         .airdrop(&mut context, 1_000_000_000)
         .await
         .unwrap();
 
-    // Create a default NFT to use as a collection.
-    let mut collection_nft = NfTest::new();
-    collection_nft
-        .mint_default(&mut context, Some(authority.dirty_clone()))
-        .await
-        .unwrap();
-
-    // Create an item NFT and add it to our collection.
-    let mut item_nft = NfTest::new();
-    item_nft
-        .mint_default(&mut context, Some(authority.dirty_clone()))
-        .await
-        .unwrap();
-
-    item_nft
-        .set_and_verify_collection(
-            &mut context,
-            SetAndVerifyCollectionArgs {
-                collection_metadata: collection_nft.metadata_pubkey(),
-                collection_authority: authority.dirty_clone(),
-                nft_update_authority: authority_pubkey,
-                collection_mint: collection_nft.mint_pubkey(),
-                collection_master_edition_account: collection_nft.edition_pubkey().unwrap(),
-                collection_authority_record: None,
-            },
-        )
+    let TestCollection { mut migratorr, .. } = TestCollection::builder()
+        .authority(authority.dirty_clone())
+        .build(&mut context)
         .await
         .unwrap();
 
     let payer = context.payer.dirty_clone();
 
-    // Create our migration state manager.
-    let mut migratorr = Migratorr::new(collection_nft.mint_pubkey());
-    migratorr.init_signer(&mut context, &payer).await.unwrap();
+    let new_authority = Keypair::new();
 
-    // Set up our initialize args
-    let unlock_method = UnlockMethod::Timed;
+    // Nothing has been proposed yet.
+    let err = migratorr
+        .accept_authority(&mut context, &authority, &new_authority)
+        .await
+        .unwrap_err();
 
-    let args = InitializeArgs {
-        rule_set: None, // this defaults to the default public key
-        unlock_method,
-        collection_size: 1,
+    assert_custom_error_ix!(0, err, MigrationError::InvalidPendingAuthority);
+
+    let update_args = UpdateArgs {
+        rule_set: None,
+        collection_size: None,
+        new_update_authority: Some(new_authority.pubkey()),
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
     };
 
-    // Initialize the migration state account on-chain
     migratorr
-        .initialize(&mut context, &payer, &authority, &collection_nft, args)
+        .update(&mut context, &authority, update_args)
         .await
         .unwrap();
 
-    // Refresh the migratorr's state from the on-chain account.
+    // Only the proposed authority can accept.
+    let impostor = Keypair::new();
+    let err = migratorr
+        .accept_authority(&mut context, &payer, &impostor)
+        .await
+        .unwrap_err();
+
+    assert_custom_error_ix!(0, err, MigrationError::InvalidPendingAuthority);
+
+    // Expire the handoff.
     migratorr.refresh_state(&mut context).await.unwrap();
+    let mut state = migratorr.state().clone();
+    state.pending_authority_expiry = 1;
+    migratorr.inject_state(&mut context, state).await;
+
+    warp100(&mut context).await;
+
+    let err = migratorr
+        .accept_authority(&mut context, &payer, &new_authority)
+        .await
+        .unwrap_err();
 
-    assert_eq!(migratorr.mint(), collection_nft.mint_pubkey());
+    assert_custom_error_ix!(0, err, MigrationError::PendingAuthorityExpired);
+
+    migratorr.refresh_state(&mut context).await.unwrap();
     assert_eq!(migratorr.authority(), authority.pubkey());
+}
 
-    println!("migration state: {:?}", migratorr.state());
-    println!("migration pubkey: {:?}", migratorr.pubkey());
+#[tokio::test]
+async fn update_after_items_migrated() {
+    let mut context = setup_pnft_context().await;
 
-    // Inject timestamp state to allow the migration to be unlocked.
-    migratorr.unlock_collection(&mut context, &authority).await;
+    // Create an authority that is separate from the payer.
+    let authority = Keypair::new();
+    authority
+        .airdrop(&mut context, 1_000_000_000)
+        .await
+        .unwrap();
 
-    // Enable migration.
-    migratorr
-        .start(&mut context, &payer, &authority, &collection_nft)
+    let mut collection = TestCollection::builder()
+        .authority(authority.dirty_clone())
+        .items(1)
+        .build(&mut context)
         .await
         .unwrap();
 
+    // Enable migration.
+    collection.start(&mut context).await.unwrap();
+
+    let TestCollection {
+        parent: collection_nft,
+        items,
+        mut migratorr,
+        ..
+    } = collection;
+    let [item_nft]: [NfTest; 1] = items.try_into().unwrap();
+
+    let payer = context.payer.dirty_clone();
+
     // These all should still work because no items have been migrated yet.
     let update_args = UpdateArgs {
         rule_set: None,
         collection_size: Some(2),
         new_update_authority: None,
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
     };
 
     migratorr
@@ -301,6 +460,10 @@ async fn update_after_items_migrated() {
         rule_set: None,
         collection_size: None,
         new_update_authority: Some(new_authority.pubkey()),
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
     };
 
     migratorr
@@ -308,6 +471,11 @@ async fn update_after_items_migrated() {
         .await
         .unwrap();
 
+    migratorr
+        .accept_authority(&mut context, &payer, &new_authority)
+        .await
+        .unwrap();
+
     warp100(&mut context).await;
     warp100(&mut context).await;
 
@@ -316,6 +484,10 @@ async fn update_after_items_migrated() {
         rule_set: None,
         collection_size: None,
         new_update_authority: Some(authority.pubkey()),
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
     };
 
     migratorr
@@ -323,6 +495,11 @@ async fn update_after_items_migrated() {
         .await
         .unwrap();
 
+    migratorr
+        .accept_authority(&mut context, &payer, &authority)
+        .await
+        .unwrap();
+
     // Migrate the item.
     migratorr
         .migrate_item(
@@ -341,6 +518,10 @@ async fn update_after_items_migrated() {
         rule_set: Some(dummy_rule_set),
         collection_size: None,
         new_update_authority: None,
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
     };
 
     let err = migratorr
@@ -356,6 +537,28 @@ async fn update_after_items_migrated() {
         rule_set: None,
         collection_size: None,
         new_update_authority: Some(dummy_new_authority),
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
+    };
+
+    let err = migratorr
+        .update(&mut context, &authority, update_args)
+        .await
+        .unwrap_err();
+
+    assert_custom_error_ix!(0, err, MigrationError::MigrationInProgress);
+
+    // This will now fail because migrated items > 0.
+    let update_args = UpdateArgs {
+        rule_set: None,
+        collection_size: None,
+        new_update_authority: None,
+        delegate_handling: Some(DelegateHandling::Reject),
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
     };
 
     let err = migratorr
@@ -370,6 +573,10 @@ async fn update_after_items_migrated() {
         rule_set: None,
         collection_size: Some(2),
         new_update_authority: None,
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
     };
 
     migratorr
@@ -0,0 +1,111 @@
+#![cfg(feature = "test-bpf")]
+pub mod utils;
+
+use mpl_migration_validator::{
+    errors::MigrationError,
+    instruction::UpdateArgs,
+    state::{
+        CollectionInfoV0, Key, MigrationStateV0, MigrationStatusV0, UnlockMethod,
+        MIGRATION_STATE_V0_LEN,
+    },
+};
+use num_traits::FromPrimitive;
+use solana_program::pubkey::Pubkey;
+use solana_program_test::{tokio, BanksClientError};
+use solana_sdk::{
+    instruction::InstructionError, signature::Keypair, signer::Signer,
+    transaction::TransactionError,
+};
+use utils::*;
+
+#[tokio::test]
+async fn upgrade_legacy_state() {
+    let mut context = setup_context().await;
+
+    // Create a default NFT to use as a collection.
+    let mut nft = NfTest::new();
+    nft.mint_default(&mut context, None).await.unwrap();
+
+    let payer = context.payer.dirty_clone();
+    let rule_set = Pubkey::new_unique();
+
+    // Create our migration state manager.
+    let mut migratorr = Migratorr::new(nft.mint_pubkey());
+
+    // Inject a migration state account with the legacy layout.
+    let legacy_state = MigrationStateV0 {
+        collection_info: CollectionInfoV0 {
+            authority: payer.pubkey(),
+            mint: nft.mint_pubkey(),
+            rule_set,
+            delegate_record: Pubkey::default(),
+            size: 42,
+        },
+        unlock_method: UnlockMethod::Timed,
+        status: MigrationStatusV0 {
+            unlock_time: 0,
+            is_locked: true,
+            in_progress: false,
+            items_migrated: 0,
+        },
+    };
+
+    migratorr
+        .inject_legacy_state(&mut context, legacy_state)
+        .await;
+
+    let account = get_account(&mut context, &migratorr.pubkey()).await;
+    assert_eq!(account.data.len(), MIGRATION_STATE_V0_LEN);
+
+    // Legacy accounts cannot be written to until they are upgraded.
+
+    let update_args = UpdateArgs {
+        rule_set: None,
+        collection_size: Some(43),
+        new_update_authority: None,
+        delegate_handling: None,
+        collection_parent_order: None,
+        update_authority_mode: None,
+        release_collection_parent: None,
+    };
+
+    let err = migratorr
+        .update(&mut context, &payer, update_args.clone())
+        .await
+        .unwrap_err();
+
+    assert_custom_error_ix!(0, err, MigrationError::MigrationStateNotUpgraded);
+
+    // Anyone can pay to upgrade the account.
+    let cranker = Keypair::new();
+    cranker.airdrop(&mut context, 1_000_000_000).await.unwrap();
+
+    migratorr
+        .upgrade_state(&mut context, &cranker, nft.mint_pubkey())
+        .await
+        .unwrap();
+
+    migratorr.refresh_state(&mut context).await.unwrap();
+
+    // The legacy values are carried over to the current layout.
+    assert_eq!(migratorr.state().key, Key::MigrationStateV1);
+    assert_eq!(migratorr.authority(), payer.pubkey());
+    assert_eq!(migratorr.mint(), nft.mint_pubkey());
+    assert_eq!(migratorr.rule_set(), rule_set);
+    assert_eq!(migratorr.collection_size(), 42);
+    assert!(migratorr.state().status.is_locked);
+
+    // The bump of the migration state is recorded.
+    let (_, bump) = find_migrate_state_pda(&nft.mint_pubkey());
+    assert_eq!(migratorr.state().bump, bump);
+
+    // The upgraded account can now be updated.
+    migratorr
+        .update(&mut context, &payer, update_args)
+        .await
+        .unwrap();
+
+    migratorr.refresh_state(&mut context).await.unwrap();
+
+    assert_eq!(migratorr.collection_size(), 43);
+}
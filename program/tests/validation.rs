@@ -0,0 +1,174 @@
+use mpl_migration_validator::{
+    errors::MigrationError,
+    state::{DelegateHandling, ListType, OwnerProgramList},
+    validation::{check_eligibility, verified_collection_member, EligibilityContext},
+};
+use mpl_token_metadata::state::{Collection, Data, Key, Metadata, TokenStandard};
+use solana_program::{
+    bpf_loader_upgradeable::UpgradeableLoaderState, program_option::COption, pubkey::Pubkey,
+    system_program,
+};
+use spl_token::state::{Account, AccountState, Mint};
+
+fn metadata(mint: Pubkey, collection: Option<Collection>) -> Metadata {
+    Metadata {
+        key: Key::MetadataV1,
+        update_authority: Pubkey::new_unique(),
+        mint,
+        data: Data {
+            name: "Item".to_string(),
+            symbol: "ITEM".to_string(),
+            uri: "https://example.com".to_string(),
+            seller_fee_basis_points: 0,
+            creators: None,
+        },
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection,
+        uses: None,
+        collection_details: None,
+        programmable_config: None,
+    }
+}
+
+fn mint(freeze_authority: Pubkey) -> Mint {
+    Mint {
+        mint_authority: COption::Some(freeze_authority),
+        supply: 1,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::Some(freeze_authority),
+    }
+}
+
+fn token(mint: Pubkey) -> Account {
+    Account {
+        mint,
+        owner: Pubkey::new_unique(),
+        amount: 1,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    }
+}
+
+#[test]
+fn eligible_item_passes() {
+    let mint_pubkey = Pubkey::new_unique();
+    let edition = Pubkey::new_unique();
+
+    let report = check_eligibility(&EligibilityContext {
+        metadata: &metadata(mint_pubkey, None),
+        mint: &mint(edition),
+        edition: &edition,
+        is_master_edition: true,
+        token: &token(mint_pubkey),
+        token_owner_program: &system_program::ID,
+        token_owner_program_buffer: None,
+        delegate_handling: DelegateHandling::CarryOver,
+        owner_program_list: None,
+    });
+
+    assert!(report.is_eligible());
+    assert_eq!(report.result(), Ok(()));
+}
+
+#[test]
+fn report_lists_every_failure_in_program_order() {
+    let mint_pubkey = Pubkey::new_unique();
+    let edition = Pubkey::new_unique();
+
+    let mut item_metadata = metadata(mint_pubkey, None);
+    item_metadata.is_mutable = false;
+
+    let mut item_token = token(mint_pubkey);
+    item_token.state = AccountState::Frozen;
+
+    let owner_program_list = OwnerProgramList {
+        key: mpl_migration_validator::state::Key::OwnerProgramList,
+        list_type: ListType::Deny,
+        programs: vec![system_program::ID],
+    };
+    let buffer = UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address: None,
+    };
+
+    let report = check_eligibility(&EligibilityContext {
+        metadata: &item_metadata,
+        // The freeze authority is not the edition.
+        mint: &mint(Pubkey::new_unique()),
+        edition: &edition,
+        is_master_edition: true,
+        token: &item_token,
+        token_owner_program: &system_program::ID,
+        token_owner_program_buffer: Some(&buffer),
+        delegate_handling: DelegateHandling::Reject,
+        owner_program_list: Some(&owner_program_list),
+    });
+
+    assert_eq!(
+        report.failures,
+        vec![
+            MigrationError::IncorrectFreezeAuthority,
+            MigrationError::ImmutableMetadata,
+            MigrationError::FrozenTokenNotAllowed,
+            MigrationError::TokenOwnerProgramNotAllowed,
+            MigrationError::ImmutableProgramOwner,
+        ]
+    );
+    assert_eq!(
+        report.result(),
+        Err(MigrationError::IncorrectFreezeAuthority)
+    );
+}
+
+#[test]
+fn items_without_token_standard_need_a_master_edition() {
+    let mint_pubkey = Pubkey::new_unique();
+    let edition = Pubkey::new_unique();
+
+    let mut item_metadata = metadata(mint_pubkey, None);
+    item_metadata.token_standard = None;
+
+    let report = check_eligibility(&EligibilityContext {
+        metadata: &item_metadata,
+        mint: &mint(edition),
+        edition: &edition,
+        is_master_edition: false,
+        token: &token(mint_pubkey),
+        token_owner_program: &system_program::ID,
+        token_owner_program_buffer: None,
+        delegate_handling: DelegateHandling::CarryOver,
+        owner_program_list: None,
+    });
+
+    assert_eq!(report.result(), Err(MigrationError::IncorrectTokenStandard));
+}
+
+#[test]
+fn collection_membership_must_be_verified() {
+    let collection_mint = Pubkey::new_unique();
+
+    let unverified = metadata(
+        Pubkey::new_unique(),
+        Some(Collection {
+            verified: false,
+            key: collection_mint,
+        }),
+    );
+    assert_eq!(
+        verified_collection_member(&unverified, &collection_mint),
+        Err(MigrationError::NotCollectionMember)
+    );
+
+    let no_collection = metadata(Pubkey::new_unique(), None);
+    assert_eq!(
+        verified_collection_member(&no_collection, &collection_mint),
+        Err(MigrationError::CollectionNotFound)
+    );
+}
@@ -0,0 +1,228 @@
+use borsh::BorshSerialize;
+use mpl_migration_validator::{
+    errors::MigrationError,
+    state::{
+        CollectionInfo, CollectionInfoV0, CollectionParentOrder, DelegateHandling, MigrationState,
+        MigrationStateV0, MigrationStatus, MigrationStatusV0, UnlockMethod, UpdateAuthorityMode,
+        MIGRATION_STATE_ACCOUNT_LEN, MIGRATION_STATE_LEN,
+    },
+    view::MigrationStateView,
+};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+// A state with a distinct value in every field, so misplaced offsets show up.
+fn state() -> MigrationState {
+    MigrationState {
+        collection_info: CollectionInfo {
+            authority: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            rule_set: Pubkey::new_unique(),
+            delegate_record: Pubkey::new_unique(),
+            size: 1_000,
+            is_sized: true,
+        },
+        unlock_method: UnlockMethod::Vote,
+        status: MigrationStatus {
+            unlock_time: 1_700_000_000,
+            is_locked: false,
+            in_progress: true,
+            items_migrated: 41,
+            collection_parent_migrated: false,
+            token_record_rent_paid: 123_456,
+            unique_payers: 7,
+            first_migration_slot: 100,
+            last_migration_slot: 200,
+            first_migration_timestamp: 1_700_000_100,
+            last_migration_timestamp: 1_700_000_200,
+        },
+        delegate_handling: DelegateHandling::Reject,
+        collection_parent_order: CollectionParentOrder::First,
+        bounty_reward: 5_000,
+        pending_authority: Pubkey::new_unique(),
+        pending_authority_expiry: 1_700_600_000,
+        update_authority_mode: UpdateAuthorityMode::VerifiedCollection,
+        bump: 254,
+        delegate_record_bump: 253,
+        collection_parent_released: true,
+        payer_record_rent_paid: 8_900,
+        collection_signer_bump: 252,
+        owner_program_list_bump: 251,
+        bounty_vault_bump: 250,
+        ..MigrationState::default()
+    }
+}
+
+fn error(error: MigrationError) -> ProgramError {
+    error.into()
+}
+
+#[test]
+fn layout_has_a_fixed_length() {
+    assert_eq!(
+        MigrationState::default().try_to_vec().unwrap().len(),
+        MIGRATION_STATE_LEN
+    );
+    assert_eq!(state().try_to_vec().unwrap().len(), MIGRATION_STATE_LEN);
+}
+
+#[test]
+fn reserved_region_is_ignored() {
+    // Accounts are allocated with zeroed bytes after the state for fields added later.
+    let state = state();
+    let mut data = state.try_to_vec().unwrap();
+    data.resize(MIGRATION_STATE_ACCOUNT_LEN, 0);
+
+    let decoded = MigrationState::from_data(&data).unwrap();
+    assert_eq!(decoded.try_to_vec().unwrap(), state.try_to_vec().unwrap());
+
+    let view = MigrationStateView::new(&data[..]).unwrap();
+    assert_eq!(view.delegate_record_bump(), state.delegate_record_bump);
+}
+
+#[test]
+fn view_reads_the_borsh_encoding() {
+    let state = state();
+    let data = state.try_to_vec().unwrap();
+    let view = MigrationStateView::new(&data[..]).unwrap();
+
+    let info = &state.collection_info;
+    assert_eq!(view.authority(), info.authority);
+    assert_eq!(view.mint(), info.mint);
+    assert_eq!(view.rule_set(), info.rule_set);
+    assert_eq!(view.delegate_record(), info.delegate_record);
+    assert_eq!(view.size(), info.size);
+    assert_eq!(view.is_sized().unwrap(), info.is_sized);
+    assert_eq!(view.unlock_method().unwrap(), state.unlock_method);
+
+    let status = &state.status;
+    assert_eq!(view.unlock_time(), status.unlock_time);
+    assert_eq!(view.is_locked().unwrap(), status.is_locked);
+    assert_eq!(view.in_progress().unwrap(), status.in_progress);
+    assert_eq!(view.items_migrated(), status.items_migrated);
+    assert_eq!(
+        view.collection_parent_migrated().unwrap(),
+        status.collection_parent_migrated
+    );
+    assert_eq!(view.token_record_rent_paid(), status.token_record_rent_paid);
+    assert_eq!(view.unique_payers(), status.unique_payers);
+    assert_eq!(view.first_migration_slot(), status.first_migration_slot);
+    assert_eq!(view.last_migration_slot(), status.last_migration_slot);
+    assert_eq!(
+        view.first_migration_timestamp(),
+        status.first_migration_timestamp
+    );
+    assert_eq!(
+        view.last_migration_timestamp(),
+        status.last_migration_timestamp
+    );
+
+    assert_eq!(view.delegate_handling().unwrap(), state.delegate_handling);
+    assert_eq!(
+        view.collection_parent_order().unwrap(),
+        state.collection_parent_order
+    );
+    assert_eq!(view.bounty_reward(), state.bounty_reward);
+    assert_eq!(view.pending_authority(), state.pending_authority);
+    assert_eq!(
+        view.pending_authority_expiry(),
+        state.pending_authority_expiry
+    );
+    assert_eq!(
+        view.update_authority_mode().unwrap(),
+        state.update_authority_mode
+    );
+    assert_eq!(view.bump(), state.bump);
+    assert_eq!(view.delegate_record_bump(), state.delegate_record_bump);
+    assert_eq!(
+        view.collection_parent_released().unwrap(),
+        state.collection_parent_released
+    );
+    assert_eq!(view.payer_record_rent_paid(), state.payer_record_rent_paid);
+    assert_eq!(view.collection_signer_bump(), state.collection_signer_bump);
+    assert_eq!(
+        view.owner_program_list_bump(),
+        state.owner_program_list_bump
+    );
+    assert_eq!(view.bounty_vault_bump(), state.bounty_vault_bump);
+}
+
+#[test]
+fn view_writes_match_the_borsh_encoding() {
+    // The first migration of a collection sets the first and last migration times.
+    let mut state = state();
+    state.status = MigrationStatus::default();
+
+    let mut data = state.try_to_vec().unwrap();
+
+    for (slot, timestamp) in [(300, 1_700_000_300), (400, 1_700_000_400)] {
+        state.status.token_record_rent_paid += 2_000;
+        state.status.unique_payers += 1;
+        state.payer_record_rent_paid += 900;
+        state.status.record_migration_time(slot, timestamp);
+        state.status.items_migrated += 1;
+
+        let mut view = MigrationStateView::new(&mut data[..]).unwrap();
+        view.set_token_record_rent_paid(view.token_record_rent_paid() + 2_000);
+        view.set_unique_payers(view.unique_payers() + 1);
+        view.set_payer_record_rent_paid(view.payer_record_rent_paid() + 900);
+        view.record_migration_time(slot, timestamp).unwrap();
+        view.set_items_migrated(view.items_migrated() + 1);
+
+        assert_eq!(data, state.try_to_vec().unwrap());
+    }
+
+    state.status.collection_parent_migrated = true;
+    MigrationStateView::new(&mut data[..])
+        .unwrap()
+        .set_collection_parent_migrated(true);
+
+    assert_eq!(data, state.try_to_vec().unwrap());
+    assert_eq!(state.status.first_migration_slot, 300);
+    assert_eq!(state.status.last_migration_slot, 400);
+}
+
+#[test]
+fn view_rejects_other_layouts() {
+    // Legacy accounts must be upgraded first.
+    let legacy = MigrationStateV0 {
+        collection_info: CollectionInfoV0 {
+            authority: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            rule_set: Pubkey::default(),
+            delegate_record: Pubkey::default(),
+            size: 10,
+        },
+        unlock_method: UnlockMethod::Timed,
+        status: MigrationStatusV0 {
+            unlock_time: 0,
+            is_locked: true,
+            in_progress: false,
+            items_migrated: 0,
+        },
+    };
+    let data = legacy.try_to_vec().unwrap();
+    assert_eq!(
+        MigrationStateView::new(&data[..]).err(),
+        Some(error(MigrationError::MigrationStateNotUpgraded))
+    );
+
+    // Other program accounts have a different key.
+    let mut data = state().try_to_vec().unwrap();
+    data[0] = 0;
+    assert_eq!(
+        MigrationStateView::new(&data[..]).err(),
+        Some(error(MigrationError::InvalidMigrationStateKey))
+    );
+
+    // Values Borsh would not decode are not read either.
+    let state = state();
+    let mut data = state.try_to_vec().unwrap();
+    let unlock_method = 1 + 32 * 4 + 4 + 1;
+    data[unlock_method] = 2;
+    let view = MigrationStateView::new(&data[..]).unwrap();
+    assert_eq!(
+        view.unlock_method().err(),
+        Some(error(MigrationError::InvalidMigrationState))
+    );
+    assert_eq!(view.mint(), state.collection_info.mint);
+}